@@ -53,13 +53,19 @@ fn turn(server: &mut Server<CustomRules>) {
     // Display the order of initiative.
     let initiative = server.battle().rounds().model();
     println!("{}", initiative);
-    // Find out who should act.
-    // In this game it's always the creature at the top of the initiative table.
-    let actor_id = initiative.top();
-    println!("It's the turn of: {}", actor_id);
+    // Ask the rounds rules who should act.
+    let actors = server
+        .battle()
+        .rounds()
+        .next_actors(server.battle().entities());
+    for actor_id in &actors {
+        println!("It's the turn of: {}", actor_id);
+    }
     println!();
     // Start the turn.
-    StartTurn::trigger(server, actor_id).fire().unwrap();
+    StartTurn::trigger_with_actors(server, actors)
+        .fire()
+        .unwrap();
     // Since this's an example, creatures do nothing and immediately end the turn.
     EndTurn::trigger(server).fire().unwrap();
 }
@@ -64,6 +64,8 @@ impl RoundsRules<CustomRules> for CustomRoundsRules {
     type RoundsSeed = ();
     // The model is a struct to hold the initiative of all actors.
     type RoundsModel = InitiativeModel;
+    // Turns are not divided in phases.
+    type Phase = ();
 
     fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {
         // Return a default model.
@@ -79,6 +81,19 @@ impl RoundsRules<CustomRules> for CustomRoundsRules {
         }
     }
 
+    fn next_actors(
+        &self,
+        model: &Self::RoundsModel,
+        _: &Entities<CustomRules>,
+    ) -> Vec<EntityId<CustomRules>> {
+        // It's always the turn of the creature at the top of the initiative table.
+        if model.actors.is_empty() {
+            Vec::new()
+        } else {
+            vec![model.top()]
+        }
+    }
+
     fn on_end(
         &self,
         entities: &Entities<CustomRules>,
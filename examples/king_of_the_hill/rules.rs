@@ -76,6 +76,8 @@ impl RoundsRules<CustomRules> for MyRoundsRules {
     type RoundsSeed = ();
     // The model is just a counter.
     type RoundsModel = u8;
+    // Turns are not divided in phases.
+    type Phase = ();
 
     fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {
         // The first player to move will be the one at index 0.
@@ -115,6 +117,7 @@ impl SpaceRules<CustomRules> for MySpaceRules {
     // Array with the id of cards on the table.
     type SpaceModel = [Option<EntityId<CustomRules>>; 3];
     type SpaceAlteration = ();
+    type Direction = ();
 
     fn generate_model(&self, _seed: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
         // At the start the table is empty.
@@ -143,6 +146,7 @@ impl SpaceRules<CustomRules> for MySpaceRules {
         model: &mut Self::SpaceModel,
         claim: PositionClaim<CustomRules>,
         position: Option<&Self::Position>,
+        _event_queue: &mut Option<EventQueue<CustomRules>>,
         _: &mut WriteMetrics<CustomRules>,
     ) {
         match position {
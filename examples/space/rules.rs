@@ -23,6 +23,7 @@ impl SpaceRules<CustomRules> for CustomSpaceRules {
     type SpaceModel = Battlefield;
     // A vector containing the position of new traps.
     type SpaceAlteration = Vec<Square>;
+    type Direction = ();
 
     fn generate_model(&self, seed: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
         Battlefield::from_seed(*seed)
@@ -47,17 +48,15 @@ impl SpaceRules<CustomRules> for CustomSpaceRules {
         model: &mut Self::SpaceModel,
         claim: PositionClaim<CustomRules>,
         position: Option<&Self::Position>,
+        _event_queue: &mut Option<EventQueue<CustomRules>>,
         _metrics: &mut WriteMetrics<CustomRules>,
     ) {
         if let Some(position) = position {
             // We simply insert the entity's id into a square of the model.
-            match claim {
-                PositionClaim::Spawn(id) => model.insert(position, *id),
-                PositionClaim::Movement(entity) => model.insert(position, *entity.entity_id()),
-            }
+            model.insert(position, *claim.entity_id());
         } else {
             // Free the entity position.
-            if let PositionClaim::Movement(entity) = claim {
+            if let Some(entity) = claim.entity() {
                 model.free(entity.position());
             }
         }
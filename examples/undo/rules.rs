@@ -47,6 +47,7 @@ impl SpaceRules<CustomRules> for CustomSpaceRules {
     type SpaceModel = Battlefield;
     // In this example we don't alter the space.
     type SpaceAlteration = ();
+    type Direction = ();
 
     fn generate_model(&self, _seed: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
         Battlefield::new()
@@ -75,12 +76,13 @@ impl SpaceRules<CustomRules> for CustomSpaceRules {
         model: &mut Self::SpaceModel,
         claim: PositionClaim<CustomRules>,
         position: Option<&Self::Position>,
+        _event_queue: &mut Option<EventQueue<CustomRules>>,
         _metrics: &mut WriteMetrics<CustomRules>,
     ) {
         if let Some(position) = position {
-            match claim {
-                PositionClaim::Spawn(_) => model.insert(*position),
-                PositionClaim::Movement(entity) => model.change(*entity.position(), *position),
+            match claim.entity() {
+                None => model.insert(*position),
+                Some(entity) => model.change(*entity.position(), *position),
             }
         }
         // In this example the entity never leaves the battlefield, thus we don't care about the
@@ -22,6 +22,10 @@ impl UserRules<CustomRules> for CustomUserRules {
     type UserMetricId = String;
     // The type we will use to serialize and deserialize all user events.
     type UserEventPackage = EventPackage;
+    // We don't need custom conditions.
+    type UserCondition = ();
+    // Errors are simple strings.
+    type UserError = String;
 }
 
 /// An user defined event.
@@ -5,6 +5,7 @@ use crate::battle::{Battle, BattleRules};
 use crate::entity::EntityId;
 use crate::error::{WeaselError, WeaselResult};
 use crate::event::{Event, EventKind, EventProcessor, EventQueue, EventRights, EventTrigger};
+use crate::round::{end_turn_if_exhausted, RoundsRules};
 use crate::util::Id;
 #[cfg(feature = "serialization")]
 use serde::{Deserialize, Serialize};
@@ -157,6 +158,10 @@ impl<R: BattleRules + 'static> Event<R> for ActivateAbility<R> {
             if !battle.state.rounds.is_acting(&self.entity_id) {
                 return Err(WeaselError::ActorNotReady(self.entity_id.clone()));
             }
+            // Verify that the actor is not incapacitated.
+            if actor.is_incapacitated() {
+                return Err(WeaselError::CharacterIncapacitated(self.entity_id.clone()));
+            }
             // Verify if the creature knowns this ability.
             if let Some(ability) = actor.ability(&self.ability_id) {
                 // Verify if this ability can be activated.
@@ -170,7 +175,14 @@ impl<R: BattleRules + 'static> Event<R> for ActivateAbility<R> {
                             self.ability_id.clone(),
                             Box::new(err),
                         )
-                    })
+                    })?;
+                // Verify if the actor can pay the ability's cost.
+                let rounds = battle.rounds();
+                let cost = rounds.rules().activation_cost(
+                    rounds.model(),
+                    Action::new(actor, ability, &self.activation),
+                );
+                rounds.check_cost(&self.entity_id, &cost)
             } else {
                 Err(WeaselError::AbilityNotKnown(
                     self.entity_id.clone(),
@@ -196,6 +208,13 @@ impl<R: BattleRules + 'static> Event<R> for ActivateAbility<R> {
                 self.ability_id, self.entity_id
             )
         });
+        // Pay the ability's cost.
+        let rounds = &battle.state.rounds;
+        let cost = rounds.rules().activation_cost(
+            rounds.model(),
+            Action::new(actor, ability, &self.activation),
+        );
+        battle.state.rounds.spend(&self.entity_id, &cost);
         battle.rules.actor_rules().activate(
             &battle.state,
             Action::new(actor, ability, &self.activation),
@@ -203,6 +222,7 @@ impl<R: BattleRules + 'static> Event<R> for ActivateAbility<R> {
             &mut battle.entropy,
             &mut battle.metrics.write_handle(),
         );
+        end_turn_if_exhausted(battle, event_queue);
     }
 
     fn kind(&self) -> EventKind {
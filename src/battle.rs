@@ -2,6 +2,7 @@
 
 use crate::actor::ActorRules;
 use crate::character::CharacterRules;
+use crate::clock::Timestamp;
 use crate::entity::Entities;
 use crate::entropy::{Entropy, EntropyRules};
 use crate::error::{WeaselError, WeaselResult};
@@ -12,7 +13,7 @@ use crate::event::{
 use crate::fight::FightRules;
 use crate::history::History;
 use crate::metric::{Metrics, ReadMetrics, WriteMetrics};
-use crate::player::{Rights, RightsHandle, RightsHandleMut};
+use crate::player::{PlayerId, Rights, RightsHandle, RightsHandleMut};
 use crate::round::{Rounds, RoundsRules};
 use crate::space::{Space, SpaceRules};
 use crate::team::{ConcludeObjectives, TeamId, TeamRules};
@@ -53,6 +54,7 @@ impl<R: BattleRules + 'static> Battle<R> {
         BattleBuilder {
             rules,
             event_callback: None,
+            deployment: false,
         }
     }
 
@@ -73,6 +75,15 @@ impl<R: BattleRules + 'static> Battle<R> {
                 return Err(WeaselError::ConditionUnsatisfied);
             }
         }
+        if let Some(condition) = event.event_condition() {
+            if !condition.evaluate(&self.state, &self.rules) {
+                return Err(WeaselError::ConditionUnsatisfied);
+            }
+        }
+        // Verify phase restrictions on root events.
+        if event.origin().is_none() {
+            self.verify_phase(event.kind())?;
+        }
         // Verify event.
         self.verify_event(&***event)
     }
@@ -102,13 +113,43 @@ impl<R: BattleRules + 'static> Battle<R> {
                 version.clone(),
             ));
         }
+        // Verify condition.
+        if let Some(condition) = event.condition() {
+            if !condition.evaluate(&self.state, &self.rules) {
+                return Err(WeaselError::ConditionUnsatisfied);
+            }
+        }
+        // Verify phase restrictions.
+        self.verify_phase(event.kind())?;
         // Verify event.
         self.verify_event(&***event)
     }
 
+    /// Verifies that an event of the given kind can be fired in the current phase.
+    fn verify_phase(&self, kind: EventKind) -> WeaselResult<(), R> {
+        if self.state.rounds.phase_allows(kind) {
+            Ok(())
+        } else {
+            Err(WeaselError::NotAllowedInPhase(kind))
+        }
+    }
+
     /// Promotes an `EventPrototype` into an `EventWrapper`.
-    pub(crate) fn promote(&self, event: EventPrototype<R>) -> EventWrapper<R> {
-        event.promote(self.history.next_id())
+    /// The event's metadata is stamped with `timestamp`, `player` and the rounds' counters.
+    pub(crate) fn promote(
+        &self,
+        event: EventPrototype<R>,
+        timestamp: Option<Timestamp>,
+        player: Option<PlayerId>,
+    ) -> EventWrapper<R> {
+        let mut event = event.promote(self.history.next_id());
+        event.metadata.stamp(
+            timestamp,
+            self.state.rounds.completed_rounds(),
+            self.state.rounds.completed_turns(),
+            player,
+        );
+        event
     }
 
     /// Apply an event to the world.
@@ -132,6 +173,11 @@ impl<R: BattleRules + 'static> Battle<R> {
         }
     }
 
+    /// Starts the battle, concluding the deployment phase.
+    pub(crate) fn start(&mut self) {
+        self.state.phase = BattlePhase::Started;
+    }
+
     /// Ends the battle.
     pub(crate) fn end(&mut self) {
         self.state.phase = BattlePhase::Ended;
@@ -244,6 +290,10 @@ impl<R: BattleRules + 'static> Battle<R> {
     ) where
         P: EventProcessor<R>,
     {
+        // Objectives matter only while the battle is being fought.
+        if state.phase != BattlePhase::Started {
+            return;
+        }
         /// Put common login into a macro.
         macro_rules! run_check {
             ($function: ident) => {{
@@ -314,6 +364,8 @@ impl<R: BattleRules> BattleState<R> {
 /// All possible phases in which a battle can be.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum BattlePhase {
+    /// Teams are deploying their creatures. Turns can't start and objectives are not checked.
+    Deployment,
     /// The battle has started.
     Started,
     /// The battle has ended.
@@ -398,6 +450,7 @@ pub trait BattleController<R: BattleRules> {
 pub struct BattleBuilder<R: BattleRules> {
     rules: R,
     event_callback: Option<EventCallback<R>>,
+    deployment: bool,
 }
 
 impl<R: BattleRules> BattleBuilder<R> {
@@ -407,14 +460,28 @@ impl<R: BattleRules> BattleBuilder<R> {
         self
     }
 
+    /// Makes the battle begin in the `Deployment` phase, which lasts until a `StartBattle`
+    /// event is fired.\
+    /// All battles sharing the same events, such as those of a server and its clients,
+    /// must be built with the same phase.
+    pub fn deployment(mut self) -> Self {
+        self.deployment = true;
+        self
+    }
+
     /// Creates a new battle.
     pub fn build(mut self) -> Battle<R> {
+        let phase = if self.deployment {
+            BattlePhase::Deployment
+        } else {
+            BattlePhase::Started
+        };
         Battle {
             state: BattleState {
                 entities: Entities::new(),
                 space: Space::new(None, self.rules.space_rules()),
                 rounds: Rounds::new(None, self.rules.rounds_rules()),
-                phase: BattlePhase::Started,
+                phase,
             },
             entropy: Entropy::new(None, self.rules.entropy_rules()),
             history: History::new(),
@@ -426,6 +493,111 @@ impl<R: BattleRules> BattleBuilder<R> {
     }
 }
 
+/// Event to conclude the deployment phase and start the battle.
+///
+/// During the deployment phase teams place their creatures inside the deployment zones
+/// defined by the space rules. Teams can position their creatures secretly and
+/// simultaneously by submitting `MoveEntity` orders during a planning phase of the server.
+///
+/// # Examples
+/// ```
+/// use weasel::{
+///     battle_rules, rules::empty::*, battle::BattlePhase, Battle, BattleController,
+///     BattleRules, EventKind, EventTrigger, Server, StartBattle,
+/// };
+///
+/// battle_rules! {}
+///
+/// let battle = Battle::builder(CustomRules::new()).deployment().build();
+/// let mut server = Server::builder(battle).build();
+/// assert_eq!(server.battle().phase(), BattlePhase::Deployment);
+///
+/// StartBattle::trigger(&mut server).fire().unwrap();
+/// assert_eq!(server.battle().phase(), BattlePhase::Started);
+/// ```
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct StartBattle<R> {
+    #[cfg_attr(feature = "serialization", serde(skip))]
+    _phantom: PhantomData<R>,
+}
+
+impl<R: BattleRules> StartBattle<R> {
+    /// Returns a trigger for this event.
+    pub fn trigger<P: EventProcessor<R>>(processor: &mut P) -> StartBattleTrigger<'_, R, P> {
+        StartBattleTrigger {
+            processor,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<R> std::fmt::Debug for StartBattle<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "StartBattle {{ }}")
+    }
+}
+
+impl<R> Clone for StartBattle<R> {
+    fn clone(&self) -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<R: BattleRules + 'static> Event<R> for StartBattle<R> {
+    fn verify(&self, battle: &Battle<R>) -> WeaselResult<(), R> {
+        // The battle can start only once.
+        if battle.phase() != BattlePhase::Deployment {
+            return Err(WeaselError::BattleAlreadyStarted);
+        }
+        Ok(())
+    }
+
+    fn apply(&self, battle: &mut Battle<R>, _: &mut Option<EventQueue<R>>) {
+        battle.start();
+    }
+
+    fn kind(&self) -> EventKind {
+        EventKind::StartBattle
+    }
+
+    fn box_clone(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Trigger to build and fire a `StartBattle` event.
+pub struct StartBattleTrigger<'a, R, P>
+where
+    R: BattleRules,
+    P: EventProcessor<R>,
+{
+    processor: &'a mut P,
+    _phantom: PhantomData<R>,
+}
+
+impl<'a, R, P> EventTrigger<'a, R, P> for StartBattleTrigger<'a, R, P>
+where
+    R: BattleRules + 'static,
+    P: EventProcessor<R>,
+{
+    fn processor(&'a mut self) -> &'a mut P {
+        self.processor
+    }
+
+    /// Returns a `StartBattle` event.
+    fn event(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(StartBattle {
+            _phantom: self._phantom,
+        })
+    }
+}
+
 /// Event to end the battle. After the battle has ended new events can't be processed.
 ///
 /// # Examples
@@ -6,6 +6,7 @@ use crate::entropy::Entropy;
 use crate::error::{WeaselError, WeaselResult};
 use crate::event::{Event, EventKind, EventProcessor, EventQueue, EventTrigger, Prioritized};
 use crate::metric::WriteMetrics;
+use crate::round::end_turn_if_incapacitated;
 use crate::status::{AppliedStatus, Potency, Status, StatusId};
 use crate::util::Id;
 #[cfg(feature = "serialization")]
@@ -198,6 +199,14 @@ pub trait Character<R: BattleRules>: Entity<R> {
     /// Removes a status.
     /// Returns the removed status, if present.
     fn remove_status(&mut self, id: &StatusId<R>) -> Option<AppliedStatus<R>>;
+
+    /// Returns whether this character is incapacitated.
+    ///
+    /// Incapacitated characters remain in the battle, but they can't act until revived.
+    fn is_incapacitated(&self) -> bool;
+
+    /// Sets whether this character is incapacitated.
+    fn set_incapacitated(&mut self, incapacitated: bool);
 }
 
 /// An event to alter the statistics of a character.
@@ -315,7 +324,13 @@ impl<R: BattleRules + 'static> Event<R> for AlterStatistics<R> {
             &mut battle.entropy,
             &mut battle.metrics.write_handle(),
         );
-        // Change the character's existence if needed.
+        // Change the character's existence if needed, skipping transmutations
+        // that wouldn't alter its current state.
+        let transmutation = transmutation.filter(|transmutation| match transmutation {
+            Transmutation::REMOVAL => true,
+            Transmutation::INCAPACITATION => !character.is_incapacitated(),
+            Transmutation::REVIVAL => character.is_incapacitated(),
+        });
         if let Some(transmutation) = transmutation {
             transmute_entity(
                 &self.id,
@@ -367,6 +382,222 @@ where
     }
 }
 
+/// An event to transmute a character.
+///
+/// Removal takes the character out of the battle, while incapacitation keeps it
+/// on the battlefield without letting it act until it's revived.\
+/// Incapacitating an actor during its turn ends the turn.
+///
+/// # Examples
+/// ```
+/// use weasel::{
+///     battle_rules, rules::empty::*, Battle, BattleController, BattleRules, Character,
+///     CreateCreature, CreateTeam, EntityId, EventKind, EventTrigger, Server,
+///     TransmuteCharacter, Transmutation,
+/// };
+///
+/// battle_rules! {}
+///
+/// let battle = Battle::builder(CustomRules::new()).build();
+/// let mut server = Server::builder(battle).build();
+///
+/// let team_id = 1;
+/// CreateTeam::trigger(&mut server, team_id).fire().unwrap();
+/// let creature_id = 1;
+/// let position = ();
+/// CreateCreature::trigger(&mut server, creature_id, team_id, position)
+///     .fire()
+///     .unwrap();
+///
+/// TransmuteCharacter::trigger(
+///     &mut server,
+///     EntityId::Creature(creature_id),
+///     Transmutation::INCAPACITATION,
+/// )
+/// .fire()
+/// .unwrap();
+/// assert_eq!(
+///     server.battle().history().events().iter().last().unwrap().kind(),
+///     EventKind::TransmuteCharacter
+/// );
+/// let creature = server.battle().entities().creature(&creature_id).unwrap();
+/// assert!(creature.is_incapacitated());
+/// ```
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct TransmuteCharacter<R: BattleRules> {
+    #[cfg_attr(
+        feature = "serialization",
+        serde(bound(
+            serialize = "EntityId<R>: Serialize",
+            deserialize = "EntityId<R>: Deserialize<'de>"
+        ))
+    )]
+    id: EntityId<R>,
+
+    transmutation: Transmutation,
+}
+
+impl<R: BattleRules> TransmuteCharacter<R> {
+    /// Returns a trigger for this event.
+    pub fn trigger<P: EventProcessor<R>>(
+        processor: &mut P,
+        id: EntityId<R>,
+        transmutation: Transmutation,
+    ) -> TransmuteCharacterTrigger<'_, R, P> {
+        TransmuteCharacterTrigger {
+            processor,
+            id,
+            transmutation,
+        }
+    }
+
+    /// Returns the character's entity id.
+    pub fn id(&self) -> &EntityId<R> {
+        &self.id
+    }
+
+    /// Returns the transmutation to be applied to the character.
+    pub fn transmutation(&self) -> Transmutation {
+        self.transmutation
+    }
+}
+
+impl<R: BattleRules> Debug for TransmuteCharacter<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "TransmuteCharacter {{ id: {:?}, transmutation: {:?} }}",
+            self.id, self.transmutation
+        )
+    }
+}
+
+impl<R: BattleRules> Clone for TransmuteCharacter<R> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            transmutation: self.transmutation,
+        }
+    }
+}
+
+impl<R: BattleRules + 'static> Event<R> for TransmuteCharacter<R> {
+    fn verify(&self, battle: &Battle<R>) -> WeaselResult<(), R> {
+        let character = verify_get_character(battle.entities(), &self.id)?;
+        // Incapacitation and revival must change the character's state.
+        match self.transmutation {
+            Transmutation::REMOVAL => Ok(()),
+            Transmutation::INCAPACITATION => {
+                if character.is_incapacitated() {
+                    Err(WeaselError::CharacterIncapacitated(self.id.clone()))
+                } else {
+                    Ok(())
+                }
+            }
+            Transmutation::REVIVAL => {
+                if character.is_incapacitated() {
+                    Ok(())
+                } else {
+                    Err(WeaselError::CharacterNotIncapacitated(self.id.clone()))
+                }
+            }
+        }
+    }
+
+    fn apply(&self, battle: &mut Battle<R>, event_queue: &mut Option<EventQueue<R>>) {
+        if self.transmutation == Transmutation::REMOVAL {
+            // Removal is carried out by the entity's specific event.
+            transmute_entity(
+                &self.id,
+                self.transmutation,
+                &mut event_queue.as_mut().map(|queue| Prioritized::new(queue)),
+            );
+            return;
+        }
+        // Update the character's state.
+        let incapacitated = self.transmutation == Transmutation::INCAPACITATION;
+        battle
+            .state
+            .entities
+            .character_mut(&self.id)
+            .unwrap_or_else(|| panic!("constraint violated: character {:?} not found", self.id))
+            .set_incapacitated(incapacitated);
+        // Notify the rounds module.
+        if let Some(actor) = battle.state.entities.actor(&self.id) {
+            if incapacitated {
+                battle.state.rounds.on_actor_incapacitated(
+                    actor,
+                    &mut battle.entropy,
+                    &mut battle.metrics.write_handle(),
+                );
+            } else {
+                battle.state.rounds.on_actor_revived(
+                    actor,
+                    &mut battle.entropy,
+                    &mut battle.metrics.write_handle(),
+                );
+            }
+        }
+        // Invoke the character's rules callback.
+        let character = battle
+            .state
+            .entities
+            .character(&self.id)
+            .unwrap_or_else(|| panic!("constraint violated: character {:?} not found", self.id));
+        battle.rules.character_rules().on_character_transmuted(
+            &battle.state,
+            character,
+            self.transmutation,
+            event_queue,
+            &mut battle.entropy,
+            &mut battle.metrics.write_handle(),
+        );
+        // An incapacitated actor can't go on with its turn.
+        end_turn_if_incapacitated(battle, &self.id, event_queue);
+    }
+
+    fn kind(&self) -> EventKind {
+        EventKind::TransmuteCharacter
+    }
+
+    fn box_clone(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Trigger to build and fire a `TransmuteCharacter` event.
+pub struct TransmuteCharacterTrigger<'a, R, P>
+where
+    R: BattleRules,
+    P: EventProcessor<R>,
+{
+    processor: &'a mut P,
+    id: EntityId<R>,
+    transmutation: Transmutation,
+}
+
+impl<'a, R, P> EventTrigger<'a, R, P> for TransmuteCharacterTrigger<'a, R, P>
+where
+    R: BattleRules + 'static,
+    P: EventProcessor<R>,
+{
+    fn processor(&'a mut self) -> &'a mut P {
+        self.processor
+    }
+
+    /// Returns a `TransmuteCharacter` event.
+    fn event(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(TransmuteCharacter {
+            id: self.id.clone(),
+            transmutation: self.transmutation,
+        })
+    }
+}
+
 /// An event to regenerate the statistics of a character.
 ///
 /// A new set of statistics is created from a seed.\
@@ -0,0 +1,28 @@
+//! Measurement of time.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Type to represent a point in time, expressed in milliseconds.
+pub type Timestamp = u64;
+
+/// A source of timestamps.
+///
+/// Clocks are injected into servers, in order to decouple the battle from any particular
+/// notion of time. For example, tests can use a clock under their full control.
+pub trait Clock: Send {
+    /// Returns the current time.
+    fn now(&self) -> Timestamp;
+}
+
+/// A clock returning the number of milliseconds elapsed since the UNIX epoch.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct SystemClock {}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as Timestamp)
+            .unwrap_or_default()
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::ability::{AbilitiesSeed, Ability, AbilityId};
 use crate::actor::{Actor, ActorRules};
-use crate::battle::{Battle, BattleRules, Checkpoint};
+use crate::battle::{Battle, BattlePhase, BattleRules, Checkpoint};
 use crate::character::{Character, CharacterRules, Statistic, StatisticId, StatisticsSeed};
 use crate::entity::{Entity, EntityId, Transmutation};
 use crate::error::{WeaselError, WeaselResult};
@@ -46,6 +46,7 @@ pub struct Creature<R: BattleRules> {
     statistics: Statistics<R>,
     statuses: Statuses<R>,
     abilities: Abilities<R>,
+    incapacitated: bool,
 }
 
 impl<R: BattleRules> Creature<R> {
@@ -128,6 +129,14 @@ impl<R: BattleRules> Character<R> for Creature<R> {
     fn remove_status(&mut self, id: &StatusId<R>) -> Option<AppliedStatus<R>> {
         self.statuses.remove(id)
     }
+
+    fn is_incapacitated(&self) -> bool {
+        self.incapacitated
+    }
+
+    fn set_incapacitated(&mut self, incapacitated: bool) {
+        self.incapacitated = incapacitated;
+    }
 }
 
 impl<R: BattleRules> Actor<R> for Creature<R> {
@@ -324,7 +333,16 @@ impl<R: BattleRules + 'static> Event<R> for CreateCreature<R> {
                 PositionClaim::Spawn(&EntityId::Creature(self.id.clone())),
                 &self.position,
             )
-            .map_err(|err| WeaselError::PositionError(None, self.position.clone(), Box::new(err)))
+            .map_err(|err| {
+                WeaselError::PositionError(None, self.position.clone(), Box::new(err))
+            })?;
+        // During deployment, creatures must be placed in their team's zone.
+        if battle.phase() == BattlePhase::Deployment {
+            battle
+                .space()
+                .check_deployment(&self.team_id, &self.position)?;
+        }
+        Ok(())
     }
 
     fn apply(&self, battle: &mut Battle<R>, event_queue: &mut Option<EventQueue<R>>) {
@@ -350,11 +368,13 @@ impl<R: BattleRules + 'static> Event<R> for CreateCreature<R> {
             statistics,
             statuses: IndexMap::new(),
             abilities,
+            incapacitated: false,
         };
         // Take the position.
         battle.state.space.move_entity(
             PositionClaim::Spawn(&EntityId::Creature(self.id.clone())),
             Some(&self.position),
+            event_queue,
             &mut battle.metrics.write_handle(),
         );
         // Notify the rounds module.
@@ -768,6 +788,7 @@ impl<R: BattleRules + 'static> Event<R> for RemoveCreature<R> {
         battle.state.space.move_entity(
             PositionClaim::Movement(&creature as &dyn Entity<R>),
             None,
+            event_queue,
             &mut battle.metrics.write_handle(),
         );
     }
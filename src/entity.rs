@@ -2,7 +2,7 @@
 
 use crate::actor::Actor;
 use crate::battle::BattleRules;
-use crate::character::Character;
+use crate::character::{Character, TransmuteCharacter};
 use crate::creature::{Creature, CreatureId, RemoveCreature};
 use crate::error::{WeaselError, WeaselResult};
 use crate::event::{Event, EventProcessor, EventTrigger};
@@ -157,9 +157,15 @@ impl<R: BattleRules> Hash for EntityId<R> {
 }
 
 /// Represents a change to an entity's existence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
 pub enum Transmutation {
     /// Entity entirely removed from the battle.
     REMOVAL,
+    /// Entity incapacitated. It stays on the battlefield, but it can't act until revived.
+    INCAPACITATION,
+    /// Entity brought back from incapacitation.
+    REVIVAL,
 }
 
 /// Triggers an event to transmute an entity.
@@ -180,6 +186,9 @@ pub(crate) fn transmute_entity<R, P>(
                 RemoveObject::trigger(processor, id.clone()).fire();
             }
         },
+        Transmutation::INCAPACITATION | Transmutation::REVIVAL => {
+            TransmuteCharacter::trigger(processor, id.clone(), transmutation).fire();
+        }
     }
 }
 
@@ -211,6 +220,16 @@ impl<R: BattleRules> Entities<R> {
         self.creatures.values_mut()
     }
 
+    /// Returns an iterator over creatures which are not incapacitated.
+    pub fn active_creatures(&self) -> impl Iterator<Item = &Creature<R>> {
+        self.creatures.values().filter(|e| !e.is_incapacitated())
+    }
+
+    /// Returns an iterator over incapacitated creatures.
+    pub fn incapacitated_creatures(&self) -> impl Iterator<Item = &Creature<R>> {
+        self.creatures.values().filter(|e| e.is_incapacitated())
+    }
+
     /// Returns the creature with the given id.
     pub fn creature(&self, id: &CreatureId<R>) -> Option<&Creature<R>> {
         self.creatures.get(id)
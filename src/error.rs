@@ -4,7 +4,7 @@ use crate::ability::AbilityId;
 use crate::battle::{BattleRules, Version};
 use crate::creature::CreatureId;
 use crate::entity::EntityId;
-use crate::event::{DefaultOutput, Event, EventId, EventSinkId};
+use crate::event::{DefaultOutput, Event, EventId, EventKind, EventSinkId};
 use crate::metric::MetricIdType;
 use crate::object::ObjectId;
 use crate::player::PlayerId;
@@ -12,6 +12,7 @@ use crate::power::PowerId;
 use crate::space::Position;
 use crate::status::StatusId;
 use crate::team::TeamId;
+use crate::user::UserError;
 use std::ops::Range;
 use std::result::Result;
 use std::{fmt, fmt::Debug};
@@ -29,6 +30,7 @@ pub type WeaselErrorType<R> = WeaselError<
     StatusId<R>,
     MetricIdType<R>,
     Box<dyn Event<R> + Send>,
+    UserError<R>,
 >;
 
 /// Alias for a `Result` returning a `WeaselError`.
@@ -36,7 +38,7 @@ pub type WeaselResult<T, R> = Result<T, WeaselErrorType<R>>;
 
 /// Error type for all kind of errors generated by weasel.
 #[derive(Debug, Clone, PartialEq)]
-pub enum WeaselError<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E> {
+pub enum WeaselError<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E, UE> {
     /// A generic error.
     GenericError,
     /// Duplicated creature id.
@@ -61,6 +63,24 @@ pub enum WeaselError<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E> {
     TeamNotEmpty(TI),
     /// Position is invalid.
     PositionError(Option<PI>, PI, Box<Self>),
+    /// The position is outside of the battlefield.
+    PositionOutOfBounds(PI),
+    /// The position is already occupied by another entity.
+    PositionOccupied(PI, EI),
+    /// The position is blocked by an obstacle.
+    PositionBlocked(PI),
+    /// The position can't support the entity.
+    PositionUnsupported(PI),
+    /// The position is too far to be reached in a single move.
+    PositionTooFar(PI),
+    /// The position is outside of the team's deployment zone.
+    OutsideDeploymentZone(TI, PI),
+    /// The path doesn't contain any step.
+    EmptyPath,
+    /// The step between two positions of a path is impassable.
+    ImpassableStep(PI, PI),
+    /// An entity can't swap position with itself.
+    SelfSwap(EI),
     /// The entity doesn't exist.
     EntityNotFound(EI),
     /// The event id is not contiguous.
@@ -69,10 +89,22 @@ pub enum WeaselError<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E> {
     TurnInProgress,
     /// No turn is in progress.
     NoTurnInProgress,
+    /// The current turn has no further phases.
+    NoNextPhase,
+    /// Events of this kind can't be fired in the current phase.
+    NotAllowedInPhase(EventKind),
+    /// A planning phase is in progress.
+    PlanningInProgress,
+    /// No planning phase is in progress.
+    NoPlanningInProgress,
+    /// The team already submitted its orders for the current planning phase.
+    OrdersAlreadySubmitted(TI),
     /// The actor can't start a new turn.
     ActorNotEligible(EI),
     /// The actor can't act at the moment.
     ActorNotReady(EI),
+    /// The actor doesn't have enough points left in its budget for this turn.
+    InsufficientActionPoints(EI),
     /// The actor doesn't know such ability.
     AbilityNotKnown(EI, AI),
     /// The ability can't be activated.
@@ -85,6 +117,10 @@ pub enum WeaselError<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E> {
     PowerNotInvocable(TI, WI, Box<Self>),
     /// Status not present on a character.
     StatusNotPresent(EI, SI),
+    /// The character is incapacitated.
+    CharacterIncapacitated(EI),
+    /// The character is not incapacitated.
+    CharacterNotIncapacitated(EI),
     /// The event processor is not valid.
     EmptyEventProcessor,
     /// The entity is not a character.
@@ -103,6 +139,10 @@ pub enum WeaselError<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E> {
     IncompatibleVersions(V, V),
     /// The battle has already ended.
     BattleEnded,
+    /// The battle is still in its deployment phase.
+    BattleNotStarted,
+    /// The battle has already started.
+    BattleAlreadyStarted,
     /// The metric's type is not correct.
     WrongMetricType(MI),
     /// The `EventPrototype`'s condition is not satisfied.
@@ -127,14 +167,17 @@ pub enum WeaselError<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E> {
     InvalidEvent(E, Box<Self>),
     /// An error containing multiple inner errors.
     MultiError(Vec<Self>),
+    /// A cascade of derived events exceeded the server's limits.\
+    /// Contains the ids of the chain of events, starting from the root, and the rejected event.
+    EventCascadeOverflow(Vec<EventId>, E),
     /// An user defined error.
-    UserError(String),
+    UserError(UE),
     /// A generic event sink error.
     EventSinkError(String),
 }
 
-impl<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E> fmt::Display
-    for WeaselError<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E>
+impl<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E, UE> fmt::Display
+    for WeaselError<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E, UE>
 where
     V: Debug,
     TI: Debug,
@@ -147,6 +190,7 @@ where
     SI: Debug,
     MI: Debug,
     E: Debug,
+    UE: Debug + fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use WeaselError::*;
@@ -179,14 +223,53 @@ where
                 "can't move entity from position {:?} to position {:?} due to {:?}",
                 source, destination, error
             ),
+            PositionOutOfBounds(position) => write!(f, "position {:?} is out of bounds", position),
+            PositionOccupied(position, id) => {
+                write!(f, "position {:?} is occupied by entity {:?}", position, id)
+            }
+            PositionBlocked(position) => write!(f, "position {:?} is blocked", position),
+            PositionUnsupported(position) => {
+                write!(f, "position {:?} can't support the entity", position)
+            }
+            PositionTooFar(position) => {
+                write!(
+                    f,
+                    "position {:?} is too far to be reached in one move",
+                    position
+                )
+            }
+            OutsideDeploymentZone(team_id, position) => write!(
+                f,
+                "position {:?} is outside of the deployment zone of team {:?}",
+                position, team_id
+            ),
+            EmptyPath => write!(f, "the path is empty"),
+            ImpassableStep(from, to) => write!(
+                f,
+                "the step from position {:?} to position {:?} is impassable",
+                from, to
+            ),
+            SelfSwap(id) => write!(f, "entity {:?} can't swap position with itself", id),
             EntityNotFound(id) => write!(f, "entity {:?} not found", id),
             NonContiguousEventId(id, expected) => {
                 write!(f, "event has id {:?}, expected {:?}", id, expected)
             }
             TurnInProgress => write!(f, "a turn is already in progress"),
             NoTurnInProgress => write!(f, "no turn is in progress"),
+            NoNextPhase => write!(f, "the current turn has no further phases"),
+            NotAllowedInPhase(kind) => {
+                write!(f, "{:?} events are not allowed in the current phase", kind)
+            }
+            PlanningInProgress => write!(f, "a planning phase is in progress"),
+            NoPlanningInProgress => write!(f, "no planning phase is in progress"),
+            OrdersAlreadySubmitted(id) => {
+                write!(f, "team {:?} already submitted its orders", id)
+            }
             ActorNotEligible(id) => write!(f, "actor {:?} is not eligible to start a new turn", id),
             ActorNotReady(id) => write!(f, "actor {:?} can't act outside of his turn", id),
+            InsufficientActionPoints(id) => {
+                write!(f, "actor {:?} doesn't have enough action points", id)
+            }
             AbilityNotKnown(actor_id, ability_id) => write!(
                 f,
                 "actor {:?} doesn't know ability {:?}",
@@ -211,6 +294,10 @@ where
                 "character {:?} is not afflicted by status {:?}",
                 character_id, status_id
             ),
+            CharacterIncapacitated(id) => write!(f, "character {:?} is incapacitated", id),
+            CharacterNotIncapacitated(id) => {
+                write!(f, "character {:?} is not incapacitated", id)
+            }
             NotACharacter(id) => write!(f, "entity {:?} is not a character", id),
             NotAnActor(id) => write!(f, "entity {:?} is not an actor", id),
             NotACreature(id) => write!(f, "entity {:?} is not a creature", id),
@@ -224,6 +311,8 @@ where
                 client, server
             ),
             BattleEnded => write!(f, "the battle has ended"),
+            BattleNotStarted => write!(f, "the battle has not started yet"),
+            BattleAlreadyStarted => write!(f, "the battle has already started"),
             WrongMetricType(id) => write!(
                 f,
                 "metric {:?} exists already with a different counter type",
@@ -259,14 +348,19 @@ where
                 }
                 write!(f, "]")
             }
-            UserError(msg) => write!(f, "user error: {}", msg),
+            EventCascadeOverflow(chain, event) => write!(
+                f,
+                "{:?} exceeds the limits of the cascade of derived events {:?}",
+                event, chain
+            ),
+            UserError(error) => write!(f, "user error: {}", error),
             EventSinkError(msg) => write!(f, "sink error: {}", msg),
         }
     }
 }
 
-impl<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E>
-    WeaselError<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E>
+impl<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E, UE>
+    WeaselError<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E, UE>
 {
     /// Unfolds an error, return the inner one in case the original is an `InvalidEvent`.
     /// If not, it returns the original.\
@@ -296,6 +390,38 @@ impl<V, TI, EI, CI, OI, PI, AI, WI, SI, MI, E>
         }
     }
 
+    /// Returns the user defined error carried by this error, if any.
+    ///
+    /// User errors are searched also inside `InvalidEvent`, `AbilityNotActivable`,
+    /// `PowerNotInvocable` and `PositionError`.
+    ///
+    /// # Examples
+    /// ```
+    /// use weasel::{
+    ///     battle_rules, error::WeaselErrorType, event::DummyEvent, rules::empty::*, BattleRules,
+    ///     EventTrigger, WeaselError,
+    /// };
+    ///
+    /// battle_rules! {}
+    /// let mut processor = ();
+    /// let trigger = DummyEvent::trigger(&mut processor);
+    /// let error: WeaselErrorType<CustomRules> = WeaselError::InvalidEvent(
+    ///     trigger.event(),
+    ///     Box::new(WeaselError::UserError("not enough mana".to_string())),
+    /// );
+    /// assert_eq!(error.user_error(), Some(&"not enough mana".to_string()));
+    /// ```
+    pub fn user_error(&self) -> Option<&UE> {
+        match self {
+            Self::UserError(error) => Some(error),
+            Self::InvalidEvent(_, inner)
+            | Self::AbilityNotActivable(_, _, inner)
+            | Self::PowerNotInvocable(_, _, inner)
+            | Self::PositionError(_, _, inner) => inner.user_error(),
+            _ => None,
+        }
+    }
+
     /// Consumes this error and filters it with the given `filter` function.
     ///
     /// `filter` is applied to this error, to the error inside `InvalidEvent`
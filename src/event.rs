@@ -1,10 +1,13 @@
 //! Event module.
 
 use crate::battle::{Battle, BattleRules, BattleState, Version};
+use crate::clock::Timestamp;
+use crate::entity::EntityId;
 use crate::error::{WeaselError, WeaselResult};
 use crate::player::PlayerId;
-use crate::team::TeamId;
-use crate::user::UserEventId;
+use crate::round::{RoundsCount, TurnState, TurnsCount};
+use crate::team::{Conclusion, TeamId};
+use crate::user::{UserCondition, UserEventId, UserRules};
 use log::error;
 #[cfg(feature = "serialization")]
 use serde::{Deserialize, Serialize};
@@ -31,6 +34,12 @@ pub enum EventKind {
     CreateObject,
     /// Move an entity from one position to another.
     MoveEntity,
+    /// Move an entity along a path.
+    MoveAlongPath,
+    /// Force an entity to move towards a direction.
+    DisplaceEntity,
+    /// Exchange the positions of two entities.
+    SwapEntities,
     /// Start a new turn.
     StartTurn,
     /// End the current turn.
@@ -39,6 +48,10 @@ pub enum EventKind {
     EndRound,
     /// Perform a turn for the environment.
     EnvironmentTurn,
+    /// Move the current turn into its next phase.
+    AdvancePhase,
+    /// Delay the current turn.
+    DelayTurn,
     /// Activate an actor's ability.
     ActivateAbility,
     /// Invoke a team's power.
@@ -55,6 +68,8 @@ pub enum EventKind {
     AlterPowers,
     /// Regenerate the statistics of a character.
     RegenerateStatistics,
+    /// Transmute a character.
+    TransmuteCharacter,
     /// Regenerate the abilities of an actor.
     RegenerateAbilities,
     /// Regenerate the powers of a team.
@@ -85,6 +100,8 @@ pub enum EventKind {
     ResetRounds,
     /// Reset the space model.
     ResetSpace,
+    /// Start the battle.
+    StartBattle,
     /// End the battle.
     EndBattle,
     /// A user defined event with an unique id.
@@ -173,6 +190,70 @@ impl<R: BattleRules> PartialEq<Box<dyn Event<R> + Send>> for Box<dyn Event<R> +
     }
 }
 
+/// Additional information attached to an event when it's promoted by the server.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct EventMetadata {
+    /// Time at which the event was promoted, if the server has a clock.
+    timestamp: Option<Timestamp>,
+    /// Number of rounds completed when the event was promoted.
+    completed_rounds: RoundsCount,
+    /// Number of turns completed when the event was promoted.
+    completed_turns: TurnsCount,
+    /// Id of the player who submitted the event.
+    player: Option<PlayerId>,
+    /// User defined tags.
+    tags: Vec<String>,
+}
+
+impl EventMetadata {
+    /// Stamps this metadata with the time, the rounds' counters and the submitting player.
+    pub(crate) fn stamp(
+        &mut self,
+        timestamp: Option<Timestamp>,
+        completed_rounds: RoundsCount,
+        completed_turns: TurnsCount,
+        player: Option<PlayerId>,
+    ) {
+        self.timestamp = timestamp;
+        self.completed_rounds = completed_rounds;
+        self.completed_turns = completed_turns;
+        self.player = player;
+    }
+
+    /// Returns the time at which the event was promoted.
+    pub fn timestamp(&self) -> Option<Timestamp> {
+        self.timestamp
+    }
+
+    /// Returns the number of rounds completed when the event was promoted.
+    pub fn completed_rounds(&self) -> RoundsCount {
+        self.completed_rounds
+    }
+
+    /// Returns the number of turns completed when the event was promoted.
+    pub fn completed_turns(&self) -> TurnsCount {
+        self.completed_turns
+    }
+
+    /// Returns the id of the player who submitted the event.
+    ///
+    /// It is `None` for events fired by the server or derived from other events.
+    pub fn player(&self) -> Option<PlayerId> {
+        self.player
+    }
+
+    /// Returns the user defined tags of the event.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns true if the event has the given tag.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+}
+
 /// A wrapper to decorate verified events with additional data.
 pub struct EventWrapper<R: BattleRules> {
     /// Event Id is assigned only after events has been verified for consistency.
@@ -181,11 +262,18 @@ pub struct EventWrapper<R: BattleRules> {
     origin: Option<EventId>,
     /// The actual event wrapped inside this struct.
     pub(crate) event: Box<dyn Event<R> + Send>,
+    /// Additional information about the event.
+    pub(crate) metadata: EventMetadata,
 }
 
 impl<R: BattleRules> Clone for EventWrapper<R> {
     fn clone(&self) -> Self {
-        Self::new(self.id, self.origin, self.event.clone())
+        Self::new(
+            self.id,
+            self.origin,
+            self.event.clone(),
+            self.metadata.clone(),
+        )
     }
 }
 
@@ -195,8 +283,14 @@ impl<R: BattleRules> EventWrapper<R> {
         id: EventId,
         origin: Option<EventId>,
         event: Box<dyn Event<R> + Send>,
+        metadata: EventMetadata,
     ) -> Self {
-        Self { id, origin, event }
+        Self {
+            id,
+            origin,
+            event,
+            metadata,
+        }
     }
 
     /// Returns this event's id.
@@ -215,6 +309,11 @@ impl<R: BattleRules> EventWrapper<R> {
         &self.event
     }
 
+    /// Returns the metadata of this event.
+    pub fn metadata(&self) -> &EventMetadata {
+        &self.metadata
+    }
+
     /// Consume this event wrapper and returns a versioned instance of it.
     pub fn version(self, version: Version<R>) -> VersionedEventWrapper<R> {
         VersionedEventWrapper::new(self, version)
@@ -270,6 +369,97 @@ impl<R: BattleRules> Deref for VersionedEventWrapper<R> {
 /// in order to be applied.
 pub type Condition<R> = std::rc::Rc<dyn Fn(&BattleState<R>) -> bool>;
 
+/// A data driven condition that an event prototype must satisfy in order to be applied.
+///
+/// Differently from `Condition`, an `EventCondition` is carried over from clients to the server,
+/// which evaluates it authoritatively.
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serialization",
+    serde(bound(
+        serialize = "EntityId<R>: Serialize, TeamId<R>: Serialize, UserCondition<R>: Serialize",
+        deserialize = "EntityId<R>: Deserialize<'de>, TeamId<R>: Deserialize<'de>, \
+                       UserCondition<R>: Deserialize<'de>"
+    ))
+)]
+pub enum EventCondition<R: BattleRules> {
+    /// The entity with the given id exists.
+    EntityExists(EntityId<R>),
+    /// The team with the given id exists.
+    TeamExists(TeamId<R>),
+    /// The team with the given id exists and its objectives reached the given conclusion.
+    TeamConcluded(TeamId<R>, Conclusion),
+    /// No turn is in progress.
+    TurnReady,
+    /// The entity with the given id is acting in the current turn.
+    Acting(EntityId<R>),
+    /// An user defined condition, evaluated by `UserRules::check_condition`.
+    ///
+    /// Use it to express conditions which depend on the rules, such as statistics' thresholds.
+    User(UserCondition<R>),
+    /// The inner condition is not satisfied.
+    Not(Box<EventCondition<R>>),
+    /// All inner conditions are satisfied.
+    All(Vec<EventCondition<R>>),
+    /// At least one of the inner conditions is satisfied.
+    Any(Vec<EventCondition<R>>),
+}
+
+impl<R: BattleRules> EventCondition<R> {
+    /// Evaluates this condition against the current state of the battle.
+    pub fn evaluate(&self, state: &BattleState<R>, rules: &R) -> bool {
+        match self {
+            Self::EntityExists(id) => state.entities().entity(id).is_some(),
+            Self::TeamExists(id) => state.entities().team(id).is_some(),
+            Self::TeamConcluded(id, conclusion) => {
+                state.entities().team(id).and_then(|team| team.conclusion()) == Some(*conclusion)
+            }
+            Self::TurnReady => matches!(state.rounds().state(), TurnState::Ready),
+            Self::Acting(id) => state.rounds().is_acting(id),
+            Self::User(condition) => rules.user_rules().check_condition(state, condition),
+            Self::Not(condition) => !condition.evaluate(state, rules),
+            Self::All(conditions) => conditions.iter().all(|c| c.evaluate(state, rules)),
+            Self::Any(conditions) => conditions.iter().any(|c| c.evaluate(state, rules)),
+        }
+    }
+}
+
+impl<R: BattleRules> Debug for EventCondition<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::EntityExists(id) => write!(f, "EventCondition::EntityExists {{ {:?} }}", id),
+            Self::TeamExists(id) => write!(f, "EventCondition::TeamExists {{ {:?} }}", id),
+            Self::TeamConcluded(id, conclusion) => write!(
+                f,
+                "EventCondition::TeamConcluded {{ {:?}, {:?} }}",
+                id, conclusion
+            ),
+            Self::TurnReady => write!(f, "EventCondition::TurnReady"),
+            Self::Acting(id) => write!(f, "EventCondition::Acting {{ {:?} }}", id),
+            Self::User(condition) => write!(f, "EventCondition::User {{ {:?} }}", condition),
+            Self::Not(condition) => write!(f, "EventCondition::Not {{ {:?} }}", condition),
+            Self::All(conditions) => write!(f, "EventCondition::All {{ {:?} }}", conditions),
+            Self::Any(conditions) => write!(f, "EventCondition::Any {{ {:?} }}", conditions),
+        }
+    }
+}
+
+impl<R: BattleRules> Clone for EventCondition<R> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::EntityExists(id) => Self::EntityExists(id.clone()),
+            Self::TeamExists(id) => Self::TeamExists(id.clone()),
+            Self::TeamConcluded(id, conclusion) => Self::TeamConcluded(id.clone(), *conclusion),
+            Self::TurnReady => Self::TurnReady,
+            Self::Acting(id) => Self::Acting(id.clone()),
+            Self::User(condition) => Self::User(condition.clone()),
+            Self::Not(condition) => Self::Not(condition.clone()),
+            Self::All(conditions) => Self::All(conditions.clone()),
+            Self::Any(conditions) => Self::Any(conditions.clone()),
+        }
+    }
+}
+
 /// A prototype for tentative events that are not yet verified.
 pub struct EventPrototype<R: BattleRules> {
     /// Id of the event that generated this one.
@@ -278,6 +468,10 @@ pub struct EventPrototype<R: BattleRules> {
     event: Box<dyn Event<R> + Send>,
     /// Condition that must be satisfied for this prototype to be valid.
     condition: Option<Condition<R>>,
+    /// Replicated condition that must be satisfied for this prototype to be valid.
+    event_condition: Option<EventCondition<R>>,
+    /// User defined tags, copied into the event's metadata.
+    tags: Vec<String>,
 }
 
 impl<R: BattleRules> EventPrototype<R> {
@@ -287,11 +481,17 @@ impl<R: BattleRules> EventPrototype<R> {
             origin: None,
             event,
             condition: None,
+            event_condition: None,
+            tags: Vec::new(),
         }
     }
 
     pub(crate) fn promote(self, id: EventId) -> EventWrapper<R> {
-        EventWrapper::new(id, self.origin, self.event)
+        let metadata = EventMetadata {
+            tags: self.tags,
+            ..Default::default()
+        };
+        EventWrapper::new(id, self.origin, self.event, metadata)
     }
 
     /// Returns the id of the event that caused this one.
@@ -320,13 +520,42 @@ impl<R: BattleRules> EventPrototype<R> {
         self.condition = condition;
     }
 
+    /// Returns the prototype's replicated acceptance condition.
+    pub fn event_condition(&self) -> &Option<EventCondition<R>> {
+        &self.event_condition
+    }
+
+    /// Sets the replicated acceptance condition of this prototype.
+    pub fn set_event_condition(&mut self, condition: Option<EventCondition<R>>) {
+        self.event_condition = condition;
+    }
+
+    /// Returns the user defined tags of this prototype.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Adds an user defined tag to this prototype.
+    pub fn add_tag(&mut self, tag: String) {
+        self.tags.push(tag);
+    }
+
     /// Consume this event prototype and returns a `ClientEventPrototype` instance of it.
+    ///
+    /// The replicated condition is preserved, while the local `Condition` is dropped.
     pub fn client_prototype(
         self,
         version: Version<R>,
         player: Option<PlayerId>,
     ) -> ClientEventPrototype<R> {
-        ClientEventPrototype::new(self.origin, self.event, version, player)
+        ClientEventPrototype::new(
+            self.origin,
+            self.event,
+            version,
+            player,
+            self.event_condition,
+            self.tags,
+        )
     }
 }
 
@@ -344,6 +573,8 @@ impl<R: BattleRules> Clone for EventPrototype<R> {
             origin: self.origin,
             event: self.event.clone(),
             condition: self.condition.clone(),
+            event_condition: self.event_condition.clone(),
+            tags: self.tags.clone(),
         }
     }
 }
@@ -359,6 +590,10 @@ pub struct ClientEventPrototype<R: BattleRules> {
     pub(crate) version: Version<R>,
     /// Id of the player who fired this event.
     player: Option<PlayerId>,
+    /// Condition that must be satisfied for this prototype to be valid.
+    pub(crate) condition: Option<EventCondition<R>>,
+    /// User defined tags.
+    pub(crate) tags: Vec<String>,
 }
 
 impl<R: BattleRules> ClientEventPrototype<R> {
@@ -368,12 +603,16 @@ impl<R: BattleRules> ClientEventPrototype<R> {
         event: Box<dyn Event<R> + Send>,
         version: Version<R>,
         player: Option<PlayerId>,
+        condition: Option<EventCondition<R>>,
+        tags: Vec<String>,
     ) -> Self {
         Self {
             origin,
             event,
             version,
             player,
+            condition,
+            tags,
         }
     }
 
@@ -393,12 +632,24 @@ impl<R: BattleRules> ClientEventPrototype<R> {
         &self.event
     }
 
+    /// Returns the condition that must be satisfied for this prototype to be valid.
+    pub fn condition(&self) -> &Option<EventCondition<R>> {
+        &self.condition
+    }
+
+    /// Returns the user defined tags of this prototype.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
     /// Transforms this client event into an event prototype.
     pub(crate) fn prototype(self) -> EventPrototype<R> {
         EventPrototype {
             origin: self.origin,
             event: self.event,
             condition: None,
+            event_condition: self.condition,
+            tags: self.tags,
         }
     }
 
@@ -431,6 +682,8 @@ impl<R: BattleRules> Clone for ClientEventPrototype<R> {
             event: self.event.clone(),
             version: self.version.clone(),
             player: self.player,
+            condition: self.condition.clone(),
+            tags: self.tags.clone(),
         }
     }
 }
@@ -751,6 +1004,8 @@ where
 
 /// Decorator for event triggers to add a condition on the generated event prototype.
 ///
+/// The condition can be either a local `Condition` or a replicated `EventCondition`.
+///
 /// # Examples
 /// ```
 /// use weasel::{
@@ -785,7 +1040,8 @@ where
     P: 'a + EventProcessor<R>,
 {
     trigger: T,
-    condition: Condition<R>,
+    condition: Option<Condition<R>>,
+    event_condition: Option<EventCondition<R>>,
     _phantom: PhantomData<&'a P>,
 }
 
@@ -800,7 +1056,42 @@ where
     pub fn new(trigger: T, condition: Condition<R>) -> Self {
         Self {
             trigger,
-            condition,
+            condition: Some(condition),
+            event_condition: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a new `Conditional` decorator for an `EventTrigger`,
+    /// using a condition that is replicated to the server.
+    ///
+    /// # Examples
+    /// ```
+    /// use weasel::{
+    ///     battle_rules, event::Conditional, event::DummyEvent, event::EventCondition,
+    ///     rules::empty::*, Battle, BattleRules, EventTrigger, Server, WeaselError,
+    /// };
+    ///
+    /// battle_rules! {}
+    ///
+    /// let battle = Battle::builder(CustomRules::new()).build();
+    /// let mut server = Server::builder(battle).build();
+    ///
+    /// let result = Conditional::with_event_condition(
+    ///     DummyEvent::trigger(&mut server),
+    ///     EventCondition::TeamExists(1),
+    /// )
+    /// .fire();
+    /// assert_eq!(
+    ///     result.err().map(|e| e.unfold()),
+    ///     Some(WeaselError::ConditionUnsatisfied)
+    /// );
+    /// ```
+    pub fn with_event_condition(trigger: T, condition: EventCondition<R>) -> Self {
+        Self {
+            trigger,
+            condition: None,
+            event_condition: Some(condition),
             _phantom: PhantomData,
         }
     }
@@ -823,7 +1114,12 @@ where
 
     fn prototype(&self) -> EventPrototype<R> {
         let mut prototype = self.trigger.prototype();
-        prototype.set_condition(Some(self.condition.clone()));
+        if let Some(condition) = &self.condition {
+            prototype.set_condition(Some(condition.clone()));
+        }
+        if let Some(condition) = &self.event_condition {
+            prototype.set_event_condition(Some(condition.clone()));
+        }
         prototype
     }
 }
@@ -1113,6 +1409,77 @@ where
     }
 }
 
+/// Decorator for event triggers to add an user defined tag to the event's metadata.
+///
+/// # Examples
+/// ```
+/// use weasel::{
+///     battle_rules, event::DummyEvent, event::Tagged, rules::empty::*, Battle, BattleController,
+///     BattleRules, EventTrigger, Server,
+/// };
+///
+/// battle_rules! {}
+///
+/// let battle = Battle::builder(CustomRules::new()).build();
+/// let mut server = Server::builder(battle).build();
+///
+/// Tagged::new(DummyEvent::trigger(&mut server), "tutorial".to_string())
+///     .fire()
+///     .unwrap();
+/// assert!(server.battle().history().events()[0]
+///     .metadata()
+///     .has_tag("tutorial"));
+/// ```
+pub struct Tagged<'a, R, T, P>
+where
+    R: BattleRules,
+    T: EventTrigger<'a, R, P>,
+    P: 'a + EventProcessor<R>,
+{
+    trigger: T,
+    tag: String,
+    _phantom: PhantomData<&'a P>,
+    _phantom_: PhantomData<R>,
+}
+
+impl<'a, R, T, P> Tagged<'a, R, T, P>
+where
+    R: BattleRules,
+    T: EventTrigger<'a, R, P>,
+    P: 'a + EventProcessor<R>,
+{
+    /// Creates a new decorator to add a tag to an event.
+    pub fn new(trigger: T, tag: String) -> Self {
+        Self {
+            trigger,
+            tag,
+            _phantom: PhantomData,
+            _phantom_: PhantomData,
+        }
+    }
+}
+
+impl<'a, R, T, P> EventTrigger<'a, R, P> for Tagged<'a, R, T, P>
+where
+    R: BattleRules,
+    T: EventTrigger<'a, R, P>,
+    P: 'a + EventProcessor<R>,
+{
+    fn processor(&'a mut self) -> &'a mut P {
+        self.trigger.processor()
+    }
+
+    fn event(&self) -> Box<dyn Event<R> + Send> {
+        self.trigger.event()
+    }
+
+    fn prototype(&self) -> EventPrototype<R> {
+        let mut prototype = self.trigger.prototype();
+        prototype.add_tag(self.tag.clone());
+        prototype
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
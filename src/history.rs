@@ -1,10 +1,14 @@
 //! History of events.
 
 use crate::battle::BattleRules;
+use crate::clock::Timestamp;
 use crate::error::{WeaselError, WeaselResult};
 use crate::event::EventId;
 use crate::event::EventWrapper;
+use crate::player::PlayerId;
+use crate::round::{RoundsCount, TurnsCount};
 use std::convert::TryInto;
+use std::ops::Range;
 
 /// History is the place where all events are kept, in a way such that they
 /// construct a single, consistent timeline.
@@ -23,6 +27,46 @@ impl<R: BattleRules> History<R> {
         &self.events
     }
 
+    /// Returns the event with the given id.
+    pub fn event(&self, id: EventId) -> Option<&EventWrapper<R>> {
+        self.events.get(id as usize)
+    }
+
+    /// Returns an iterator over all events having the given tag.
+    pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a EventWrapper<R>> {
+        self.events
+            .iter()
+            .filter(move |event| event.metadata().has_tag(tag))
+    }
+
+    /// Returns an iterator over all events submitted by `player`.
+    pub fn by_player(&self, player: PlayerId) -> impl Iterator<Item = &EventWrapper<R>> {
+        self.events
+            .iter()
+            .filter(move |event| event.metadata().player() == Some(player))
+    }
+
+    /// Returns an iterator over all events promoted after exactly `rounds` completed rounds.
+    pub fn in_round(&self, rounds: RoundsCount) -> impl Iterator<Item = &EventWrapper<R>> {
+        self.events
+            .iter()
+            .filter(move |event| event.metadata().completed_rounds() == rounds)
+    }
+
+    /// Returns an iterator over all events promoted after exactly `turns` completed turns.
+    pub fn in_turn(&self, turns: TurnsCount) -> impl Iterator<Item = &EventWrapper<R>> {
+        self.events
+            .iter()
+            .filter(move |event| event.metadata().completed_turns() == turns)
+    }
+
+    /// Returns an iterator over all events whose timestamp is within `range`.
+    pub fn between(&self, range: Range<Timestamp>) -> impl Iterator<Item = &EventWrapper<R>> {
+        self.events.iter().filter(move |event| {
+            matches!(event.metadata().timestamp(), Some(timestamp) if range.contains(&timestamp))
+        })
+    }
+
     /// Stores a new event in the history logs.
     pub(crate) fn archive(&mut self, event: &EventWrapper<R>) {
         assert_eq!(event.id() as usize, self.events.len());
@@ -60,7 +104,7 @@ impl<R: BattleRules> History<R> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::event::{DummyEvent, EventTrigger};
+    use crate::event::{DummyEvent, EventMetadata, EventTrigger};
     use crate::{battle_rules, rules::empty::*};
 
     #[test]
@@ -68,7 +112,12 @@ mod tests {
         battle_rules! {}
         let mut history = History::<CustomRules>::new();
         let mut try_archive = |id| -> WeaselResult<(), _> {
-            let event = EventWrapper::new(id, None, DummyEvent::trigger(&mut ()).event());
+            let event = EventWrapper::new(
+                id,
+                None,
+                DummyEvent::trigger(&mut ()).event(),
+                EventMetadata::default(),
+            );
             history.verify_event(&event)?;
             history.archive(&event);
             Ok(())
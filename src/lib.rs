@@ -129,15 +129,21 @@ pub use crate::actor::{Action, Actor, ActorRules, AlterAbilities, RegenerateAbil
 
 pub mod battle;
 pub use crate::battle::{
-    Battle, BattleController, BattleRules, BattleState, EndBattle, EventCallback, Version,
+    Battle, BattleController, BattleRules, BattleState, EndBattle, EventCallback, StartBattle,
+    Version,
 };
 
 pub mod character;
-pub use crate::character::{AlterStatistics, Character, CharacterRules, RegenerateStatistics};
+pub use crate::character::{
+    AlterStatistics, Character, CharacterRules, RegenerateStatistics, TransmuteCharacter,
+};
 
 pub mod client;
 pub use crate::client::Client;
 
+pub mod clock;
+pub use crate::clock::{Clock, SystemClock, Timestamp};
+
 pub mod creature;
 pub use crate::creature::{ConvertCreature, CreateCreature, Creature, RemoveCreature};
 
@@ -152,9 +158,9 @@ pub use crate::error::{WeaselError, WeaselResult};
 
 pub mod event;
 pub use crate::event::{
-    ClientEventPrototype, Event, EventId, EventKind, EventProcessor, EventPrototype, EventQueue,
-    EventReceiver, EventRights, EventServer, EventTrigger, EventWrapper, LinkedQueue,
-    VersionedEventWrapper,
+    ClientEventPrototype, Event, EventCondition, EventId, EventKind, EventMetadata, EventProcessor,
+    EventPrototype, EventQueue, EventReceiver, EventRights, EventServer, EventTrigger,
+    EventWrapper, LinkedQueue, VersionedEventWrapper,
 };
 
 pub mod fight;
@@ -177,7 +183,8 @@ pub use crate::power::InvokePower;
 
 pub mod round;
 pub use crate::round::{
-    EndRound, EndTurn, EnvironmentTurn, ResetRounds, Rounds, RoundsRules, StartTurn,
+    AdvancePhase, DelayTurn, EndRound, EndTurn, EnvironmentTurn, ResetRounds, Rounds, RoundsRules,
+    StartTurn,
 };
 
 pub mod rules;
@@ -191,7 +198,10 @@ pub mod server;
 pub use crate::server::Server;
 
 pub mod space;
-pub use crate::space::{AlterSpace, MoveEntity, PositionClaim, ResetSpace, Space, SpaceRules};
+pub use crate::space::{
+    AlterSpace, Area, DisplaceEntity, MoveAlongPath, MoveEntity, PositionClaim, ResetSpace, Space,
+    SpaceRules, SpatialQueries, SwapEntities, ZoneReaction,
+};
 
 pub mod status;
 pub use crate::status::{AlterStatuses, Application, AppliedStatus, ClearStatus, InflictStatus};
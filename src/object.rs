@@ -36,6 +36,7 @@ pub struct Object<R: BattleRules> {
     position: Position<R>,
     statistics: Statistics<R>,
     statuses: Statuses<R>,
+    incapacitated: bool,
 }
 
 impl<R: BattleRules> Id for Object<R> {
@@ -112,6 +113,14 @@ impl<R: BattleRules> Character<R> for Object<R> {
     fn remove_status(&mut self, id: &StatusId<R>) -> Option<AppliedStatus<R>> {
         self.statuses.remove(id)
     }
+
+    fn is_incapacitated(&self) -> bool {
+        self.incapacitated
+    }
+
+    fn set_incapacitated(&mut self, incapacitated: bool) {
+        self.incapacitated = incapacitated;
+    }
 }
 
 /// Event to create a new object.
@@ -247,11 +256,13 @@ impl<R: BattleRules + 'static> Event<R> for CreateObject<R> {
             position: self.position.clone(),
             statistics,
             statuses: IndexMap::new(),
+            incapacitated: false,
         };
         // Take the position.
         battle.state.space.move_entity(
             PositionClaim::Spawn(&EntityId::Object(self.id.clone())),
             Some(&self.position),
+            event_queue,
             &mut battle.metrics.write_handle(),
         );
         // Invoke the character's rules callback.
@@ -423,6 +434,7 @@ impl<R: BattleRules + 'static> Event<R> for RemoveObject<R> {
         battle.state.space.move_entity(
             PositionClaim::Movement(&object as &dyn Entity<R>),
             None,
+            event_queue,
             &mut battle.metrics.write_handle(),
         );
     }
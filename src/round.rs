@@ -1,15 +1,16 @@
 //! Everything related to the battle's turns and rounds.
 
-use crate::actor::{Actor, ActorRules};
-use crate::battle::{Battle, BattleRules, Checkpoint};
+use crate::actor::{Action, Actor, ActorRules};
+use crate::battle::{Battle, BattlePhase, BattleRules, Checkpoint};
 use crate::entity::{Entities, Entity, EntityId};
 use crate::entropy::Entropy;
 use crate::error::{WeaselError, WeaselResult};
 use crate::event::{Event, EventKind, EventProcessor, EventQueue, EventRights, EventTrigger};
 use crate::metric::WriteMetrics;
-use crate::space::Space;
+use crate::space::{PathCost, Position, Space};
 use crate::status::update_statuses;
-use indexmap::IndexSet;
+use crate::team::TeamId;
+use indexmap::{IndexMap, IndexSet};
 #[cfg(feature = "serialization")]
 use serde::{Deserialize, Serialize};
 use std::any::Any;
@@ -23,6 +24,52 @@ pub type RoundsCount = u32;
 /// Type for counting the number of turns.
 pub type TurnsCount = u32;
 
+/// Type for counting action points.
+pub type ActionPoints = u32;
+
+/// Points that an actor can spend during a turn, divided between actions and movement.
+///
+/// The same struct is used to express the cost of activating an ability or of moving.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Budget {
+    actions: ActionPoints,
+    movement: ActionPoints,
+}
+
+impl Budget {
+    /// Creates a new budget.
+    pub fn new(actions: ActionPoints, movement: ActionPoints) -> Self {
+        Self { actions, movement }
+    }
+
+    /// Returns the points available for actions.
+    pub fn actions(&self) -> ActionPoints {
+        self.actions
+    }
+
+    /// Returns the points available for movement.
+    pub fn movement(&self) -> ActionPoints {
+        self.movement
+    }
+
+    /// Returns true if this budget has enough points to pay `cost`.
+    pub fn covers(&self, cost: &Budget) -> bool {
+        self.actions >= cost.actions && self.movement >= cost.movement
+    }
+
+    /// Returns true if no points are left.
+    pub fn is_exhausted(&self) -> bool {
+        self.actions == 0 && self.movement == 0
+    }
+
+    /// Removes `cost` from this budget.
+    fn spend(&mut self, cost: &Budget) {
+        self.actions = self.actions.saturating_sub(cost.actions);
+        self.movement = self.movement.saturating_sub(cost.movement);
+    }
+}
+
 /// Manages the battle's rounds. The main purpose is to tell which actor(s) will act next.
 pub struct Rounds<R: BattleRules> {
     state: TurnStateType<R>,
@@ -30,6 +77,12 @@ pub struct Rounds<R: BattleRules> {
     rules: R::RR,
     rounds: RoundsCount,
     turns: TurnsCount,
+    acted: IndexSet<EntityId<R>>,
+    delayed: IndexSet<EntityId<R>>,
+    ticked: IndexSet<EntityId<R>>,
+    budgets: IndexMap<EntityId<R>, Budget>,
+    phases: Vec<Phase<R>>,
+    phase_index: usize,
 }
 
 impl<R: BattleRules> Rounds<R> {
@@ -40,6 +93,12 @@ impl<R: BattleRules> Rounds<R> {
             rules,
             rounds: 0,
             turns: 0,
+            acted: IndexSet::new(),
+            delayed: IndexSet::new(),
+            ticked: IndexSet::new(),
+            budgets: IndexMap::new(),
+            phases: Vec::new(),
+            phase_index: 0,
         }
     }
 
@@ -60,9 +119,10 @@ impl<R: BattleRules> Rounds<R> {
         self.state.has_actor(entity_id)
     }
 
-    /// See [eligible](trait.RoundsRules.html#method.eligible).
+    /// See [eligible](trait.RoundsRules.html#method.eligible).\
+    /// Incapacitated actors are never eligible.
     fn eligible(&self, actor: &dyn Actor<R>) -> bool {
-        self.rules.eligible(&self.model, actor)
+        !actor.is_incapacitated() && self.rules.eligible(&self.model, actor)
     }
 
     /// Returns the state of the current turn.
@@ -72,9 +132,113 @@ impl<R: BattleRules> Rounds<R> {
 
     /// Sets the state of the current turn.
     pub(crate) fn set_state(&mut self, state: TurnStateType<R>) {
+        if let TurnState::Ready = state {
+            self.phases.clear();
+            self.phase_index = 0;
+        }
         self.state = state;
     }
 
+    /// Returns the phase of the current turn.\
+    /// `None` means that no turn is in progress or that turns are not divided in phases.
+    pub fn phase(&self) -> Option<&Phase<R>> {
+        self.phases.get(self.phase_index)
+    }
+
+    /// Returns all phases of the current turn, in order.
+    pub fn phases(&self) -> &[Phase<R>] {
+        &self.phases
+    }
+
+    /// Returns true if the current phase is followed by another one.
+    pub fn has_next_phase(&self) -> bool {
+        self.phase_index + 1 < self.phases.len()
+    }
+
+    /// Returns true if an event of the given kind can be fired in the current phase.\
+    /// Events to manage turns, such as `AdvancePhase` and `EndTurn`, are always allowed.
+    pub fn phase_allows(&self, kind: EventKind) -> bool {
+        match kind {
+            EventKind::AdvancePhase | EventKind::EndTurn | EventKind::DelayTurn => true,
+            _ => match self.phase() {
+                Some(phase) => self.rules.phase_allows(&self.model, phase, kind),
+                None => true,
+            },
+        }
+    }
+
+    /// Divides the turn just started in phases and enters the first one.
+    fn start_phases(
+        &mut self,
+        entities: &Entities<R>,
+        space: &Space<R>,
+        entropy: &mut Entropy<R>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.phases = self.rules.phases(&self.model);
+        self.phase_index = 0;
+        debug_assert!(
+            match self.rules.statuses_phase(&self.model) {
+                Some(phase) => self.phases.contains(&phase),
+                None => true,
+            },
+            "the statuses phase is not among the turn's phases"
+        );
+        if let Some(phase) = self.phases.first() {
+            self.rules
+                .on_phase(entities, space, &mut self.model, phase, entropy, metrics);
+        }
+    }
+
+    /// Enters the next phase of the current turn.
+    fn next_phase(
+        &mut self,
+        entities: &Entities<R>,
+        space: &Space<R>,
+        entropy: &mut Entropy<R>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.phase_index += 1;
+        if let Some(phase) = self.phases.get(self.phase_index) {
+            self.rules
+                .on_phase(entities, space, &mut self.model, phase, entropy, metrics);
+        }
+    }
+
+    /// Returns true if the statuses of the current actors should be updated
+    /// upon entering the current phase.
+    fn statuses_due(&self) -> bool {
+        match self.rules.statuses_phase(&self.model) {
+            Some(phase) => self.phase() == Some(&phase),
+            None => self.phase_index == 0,
+        }
+    }
+
+    /// Returns true if the statuses of the current actors have already been updated
+    /// during this turn.
+    fn statuses_updated(&self) -> bool {
+        match self.rules.statuses_phase(&self.model) {
+            Some(phase) => self
+                .phases
+                .iter()
+                .take(self.phase_index + 1)
+                .any(|p| *p == phase),
+            None => true,
+        }
+    }
+
+    /// Returns true if the statuses of the given actor must be updated when
+    /// the current phase is entered.
+    fn must_update_statuses(&self, entity_id: &EntityId<R>) -> bool {
+        self.statuses_due() && !self.ticked.contains(entity_id)
+    }
+
+    /// Returns true if the statuses of the given actor haven't been updated yet
+    /// during this turn.
+    fn statuses_pending(&self, entity_id: &EntityId<R>) -> bool {
+        !self.statuses_updated() && !self.ticked.contains(entity_id)
+    }
+
     /// Returns the `RoundRules` in use.
     pub fn rules(&self) -> &R::RR {
         &self.rules
@@ -90,9 +254,76 @@ impl<R: BattleRules> Rounds<R> {
         self.rounds
     }
 
-    /// Increases the rounds counter.
-    pub(crate) fn increase_completed_rounds(&mut self) {
-        self.rounds += 1;
+    /// Returns true if the entity with the given id has completed a turn in the current round.
+    pub fn has_acted(&self, entity_id: &EntityId<R>) -> bool {
+        self.acted.contains(entity_id)
+    }
+
+    /// Returns an iterator over the ids of all actors who completed a turn in the current round.
+    pub fn acted(&self) -> impl Iterator<Item = &EntityId<R>> {
+        self.acted.iter()
+    }
+
+    /// Returns true if every actor eligible to start a turn has already acted in
+    /// the current round.
+    pub fn round_complete(&self, entities: &Entities<R>) -> bool {
+        entities
+            .actors()
+            .filter(|actor| self.eligible(*actor))
+            .all(|actor| self.acted.contains(actor.entity_id()))
+    }
+
+    /// Returns true if the entity with the given id delayed its turn in the current round
+    /// and it didn't complete a turn yet.
+    pub fn has_delayed(&self, entity_id: &EntityId<R>) -> bool {
+        self.delayed.contains(entity_id)
+    }
+
+    /// Returns the ids of the actors who should start the next turn.\
+    /// See [next_actors](trait.RoundsRules.html#method.next_actors).
+    /// Incapacitated actors are left out.
+    pub fn next_actors(&self, entities: &Entities<R>) -> Vec<EntityId<R>> {
+        let mut actors = self.rules.next_actors(&self.model, entities);
+        actors.retain(|id| !matches!(entities.actor(id), Some(actor) if actor.is_incapacitated()));
+        actors
+    }
+
+    /// Returns the budget left to the given actor in the current turn.\
+    /// Actors who delayed their turn keep the points they didn't spend.\
+    /// `None` means that the actor isn't acting or that its budget is unlimited.
+    pub fn budget(&self, entity_id: &EntityId<R>) -> Option<&Budget> {
+        self.budgets.get(entity_id)
+    }
+
+    /// Verifies that the given actor can pay `cost`.
+    pub(crate) fn check_cost(&self, entity_id: &EntityId<R>, cost: &Budget) -> WeaselResult<(), R> {
+        match self.budgets.get(entity_id) {
+            Some(budget) if !budget.covers(cost) => {
+                Err(WeaselError::InsufficientActionPoints(entity_id.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes `cost` from the budget of the given actor.
+    pub(crate) fn spend(&mut self, entity_id: &EntityId<R>, cost: &Budget) {
+        if let Some(budget) = self.budgets.get_mut(entity_id) {
+            budget.spend(cost);
+        }
+    }
+
+    /// Returns true if all current actors have a limited budget and they spent it all.
+    fn budgets_exhausted(&self) -> bool {
+        if let TurnState::Started(actors) = &self.state {
+            actors.iter().all(|actor_id| {
+                self.budgets
+                    .get(actor_id)
+                    .map(|budget| budget.is_exhausted())
+                    .unwrap_or(false)
+            })
+        } else {
+            false
+        }
     }
 
     /// Returns the number of completed turns.
@@ -123,10 +354,38 @@ impl<R: BattleRules> Rounds<R> {
         entropy: &mut Entropy<R>,
         metrics: &mut WriteMetrics<R>,
     ) {
+        self.acted.shift_remove(actor.entity_id());
+        self.delayed.shift_remove(actor.entity_id());
+        self.ticked.shift_remove(actor.entity_id());
+        self.budgets.shift_remove(actor.entity_id());
         self.rules
             .on_actor_removed(&mut self.model, actor, entropy, metrics);
     }
 
+    /// Called when an actor is incapacitated.
+    pub(crate) fn on_actor_incapacitated(
+        &mut self,
+        actor: &dyn Actor<R>,
+        entropy: &mut Entropy<R>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.delayed.shift_remove(actor.entity_id());
+        self.ticked.shift_remove(actor.entity_id());
+        self.rules
+            .on_actor_incapacitated(&mut self.model, actor, entropy, metrics);
+    }
+
+    /// Called when an incapacitated actor is revived.
+    pub(crate) fn on_actor_revived(
+        &mut self,
+        actor: &dyn Actor<R>,
+        entropy: &mut Entropy<R>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.rules
+            .on_actor_revived(&mut self.model, actor, entropy, metrics);
+    }
+
     /// Invoked when a turn ends.
     pub(crate) fn on_end(
         &mut self,
@@ -136,10 +395,50 @@ impl<R: BattleRules> Rounds<R> {
         entropy: &mut Entropy<R>,
         metrics: &mut WriteMetrics<R>,
     ) {
+        self.acted.insert(actor.entity_id().clone());
+        self.delayed.shift_remove(actor.entity_id());
+        self.ticked.shift_remove(actor.entity_id());
+        self.budgets.shift_remove(actor.entity_id());
         self.rules
             .on_end(entities, space, &mut self.model, actor, entropy, metrics);
     }
 
+    /// Invoked when an actor delays its turn.
+    fn on_delay(
+        &mut self,
+        entities: &Entities<R>,
+        space: &Space<R>,
+        actor: &dyn Actor<R>,
+        entropy: &mut Entropy<R>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.delayed.insert(actor.entity_id().clone());
+        if self.statuses_updated() {
+            self.ticked.insert(actor.entity_id().clone());
+        }
+        self.rules
+            .on_delay(entities, space, &mut self.model, actor, entropy, metrics);
+    }
+
+    /// Ends the current round and starts a new one.
+    pub(crate) fn end_round(
+        &mut self,
+        entities: &Entities<R>,
+        space: &Space<R>,
+        entropy: &mut Entropy<R>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.rules
+            .on_round_end(entities, space, &mut self.model, entropy, metrics);
+        self.rounds += 1;
+        self.acted.clear();
+        self.delayed.clear();
+        self.ticked.clear();
+        self.budgets.clear();
+        self.rules
+            .on_round_start(entities, space, &mut self.model, entropy, metrics);
+    }
+
     /// Regenerates this rounds' model starting from the given seed.
     pub(crate) fn regenerate_model(&mut self, seed: &Option<RoundsSeed<R>>) {
         self.model = self.rules.generate_model(seed)
@@ -191,10 +490,18 @@ pub trait RoundsRules<R: BattleRules> {
     /// See [RoundsModel](type.RoundsModel.html).
     type RoundsModel;
 
+    #[cfg(not(feature = "serialization"))]
+    /// See [Phase](type.Phase.html).
+    type Phase: Debug + Clone + PartialEq + Send;
+    #[cfg(feature = "serialization")]
+    /// See [Phase](type.Phase.html).
+    type Phase: Debug + Clone + PartialEq + Send + Serialize + for<'a> Deserialize<'a>;
+
     /// Generates a `RoundsModel` starting from a `RoundsSeed`.
     fn generate_model(&self, seed: &Option<Self::RoundsSeed>) -> Self::RoundsModel;
 
     /// Returns whether the given actor is eligible to start a new round.
+    /// Incapacitated actors are excluded beforehand, regardless of this method's result.
     ///
     /// The provided implementation accepts any actor.
     fn eligible(&self, _model: &Self::RoundsModel, _actor: &dyn Actor<R>) -> bool {
@@ -229,6 +536,204 @@ pub trait RoundsRules<R: BattleRules> {
     ) {
     }
 
+    /// Invoked when an actor delays its turn, through the `DelayTurn` event.\
+    /// Use this method to re-insert the actor later in the order of turns.
+    ///
+    /// The provided implementation does nothing.
+    fn on_delay(
+        &self,
+        _entities: &Entities<R>,
+        _space: &Space<R>,
+        _model: &mut Self::RoundsModel,
+        _actor: &dyn Actor<R>,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+    }
+
+    /// Invoked when the current round ends.
+    ///
+    /// The provided implementation does nothing.
+    fn on_round_end(
+        &self,
+        _entities: &Entities<R>,
+        _space: &Space<R>,
+        _model: &mut Self::RoundsModel,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+    }
+
+    /// Invoked when a new round begins, right after the end of the previous one.\
+    /// The first round is implicitly started at the beginning of the battle, hence
+    /// this method is not called for it.
+    ///
+    /// The provided implementation does nothing.
+    fn on_round_start(
+        &self,
+        _entities: &Entities<R>,
+        _space: &Space<R>,
+        _model: &mut Self::RoundsModel,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+    }
+
+    /// Returns whether an `EndRound` event should be fired automatically, as soon as
+    /// every eligible actor has completed a turn in the current round.
+    ///
+    /// The provided implementation returns false.
+    fn auto_end_round(&self, _model: &Self::RoundsModel) -> bool {
+        false
+    }
+
+    /// Returns the ids of the actors who should start the next turn.
+    ///
+    /// An empty vector means that no actor is ready to act. In such a case, the turn
+    /// driver will fire an `EnvironmentTurn` instead.
+    ///
+    /// The provided implementation returns an empty vector.
+    fn next_actors(&self, _model: &Self::RoundsModel, _entities: &Entities<R>) -> Vec<EntityId<R>> {
+        Vec::new()
+    }
+
+    /// Returns whether turns should advance automatically.
+    ///
+    /// If true, after the end of each turn (and of each round) the battle will fire
+    /// a `StartTurn` for the actors returned by `next_actors`,
+    /// or an `EnvironmentTurn` if there are none.\
+    /// The very first turn must still be started manually.
+    ///
+    /// The provided implementation returns false.
+    fn auto_advance_turns(&self, _model: &Self::RoundsModel) -> bool {
+        false
+    }
+
+    /// Returns the ordered list of phases in which a new turn is divided.\
+    /// Turns move from one phase to the next one with the `AdvancePhase` event.
+    ///
+    /// The provided implementation returns an empty vector, meaning that turns
+    /// are not divided in phases.
+    fn phases(&self, _model: &Self::RoundsModel) -> Vec<Self::Phase> {
+        Vec::new()
+    }
+
+    /// Invoked when the current turn enters a new phase, including the first one.
+    ///
+    /// The provided implementation does nothing.
+    fn on_phase(
+        &self,
+        _entities: &Entities<R>,
+        _space: &Space<R>,
+        _model: &mut Self::RoundsModel,
+        _phase: &Self::Phase,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+    }
+
+    /// Returns the phase upon whose start the statuses of the current actors are updated.\
+    /// The phase must be one of those returned by `phases`. If the turn ends before
+    /// reaching it, statuses are updated when the turn ends.
+    ///
+    /// The provided implementation returns `None`, meaning that statuses are updated
+    /// at the start of the turn.
+    fn statuses_phase(&self, _model: &Self::RoundsModel) -> Option<Self::Phase> {
+        None
+    }
+
+    /// Returns whether an event of the given kind can be fired during `phase`.
+    ///
+    /// The provided implementation accepts any event.
+    fn phase_allows(
+        &self,
+        _model: &Self::RoundsModel,
+        _phase: &Self::Phase,
+        _kind: EventKind,
+    ) -> bool {
+        true
+    }
+
+    /// Returns the budget of points that the given actor can spend during its turn.\
+    /// The budget is refilled each time the actor starts a new turn.
+    ///
+    /// The provided implementation returns `None`, meaning an unlimited budget.
+    fn budget(&self, _model: &Self::RoundsModel, _actor: &dyn Actor<R>) -> Option<Budget> {
+        None
+    }
+
+    /// Returns the cost of activating an ability.
+    ///
+    /// The provided implementation returns a cost of zero.
+    fn activation_cost(&self, _model: &Self::RoundsModel, _action: Action<R>) -> Budget {
+        Budget::default()
+    }
+
+    /// Returns the cost of moving an actor to `position`.
+    ///
+    /// The provided implementation returns a cost of zero.
+    fn movement_cost(
+        &self,
+        _model: &Self::RoundsModel,
+        _actor: &dyn Actor<R>,
+        _position: &Position<R>,
+    ) -> Budget {
+        Budget::default()
+    }
+
+    /// Returns the cost of moving an actor along `path`.\
+    /// `cost` is the total cost of the path's steps, according to the space rules.
+    ///
+    /// The provided implementation spends one movement point for each point of `cost`.
+    fn path_cost(
+        &self,
+        _model: &Self::RoundsModel,
+        _actor: &dyn Actor<R>,
+        _path: &[Position<R>],
+        cost: PathCost,
+    ) -> Budget {
+        Budget::new(0, cost)
+    }
+
+    /// Returns whether an `EndTurn` event should be fired automatically, as soon as
+    /// all current actors spent their whole budget.
+    ///
+    /// The provided implementation returns false.
+    fn auto_end_turn(&self, _model: &Self::RoundsModel) -> bool {
+        false
+    }
+
+    /// Invoked by `Server::tick` when the current turn exceeds the server's time limit.\
+    /// Use this method to fire a fallback action on behalf of `actors`, for instance
+    /// a defensive stance.
+    ///
+    /// If no event is fired, the server concludes the turn with an `EndTurn`.
+    ///
+    /// The provided implementation does nothing.
+    fn on_turn_expired(
+        &self,
+        _entities: &Entities<R>,
+        _model: &Self::RoundsModel,
+        _actors: &IndexSet<EntityId<R>>,
+        _event_queue: &mut Option<EventQueue<R>>,
+    ) {
+    }
+
+    /// Returns the order in which the batches of orders submitted during a planning
+    /// phase should be resolved.\
+    /// `teams` contains the ids of the teams who submitted a batch, in order of submission.
+    /// Teams left out of the returned vector won't have their orders resolved.
+    ///
+    /// The provided implementation keeps the order of submission.
+    fn order_batches(
+        &self,
+        _model: &Self::RoundsModel,
+        _entities: &Entities<R>,
+        teams: &[TeamId<R>],
+    ) -> Vec<TeamId<R>> {
+        teams.to_vec()
+    }
+
     /// Invoked when a new actor is added to the battle.
     ///
     /// The provided implementation does nothing.
@@ -241,39 +746,497 @@ pub trait RoundsRules<R: BattleRules> {
     ) {
     }
 
-    /// Invoked when an actor is removed from the battle.
-    ///
-    /// The provided implementation does nothing.
-    fn on_actor_removed(
-        &self,
-        _model: &mut Self::RoundsModel,
-        _actor: &dyn Actor<R>,
-        _entropy: &mut Entropy<R>,
-        _metrics: &mut WriteMetrics<R>,
-    ) {
+    /// Invoked when an actor is removed from the battle.
+    ///
+    /// The provided implementation does nothing.
+    fn on_actor_removed(
+        &self,
+        _model: &mut Self::RoundsModel,
+        _actor: &dyn Actor<R>,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+    }
+
+    /// Invoked when an actor is incapacitated.\
+    /// Incapacitated actors are never eligible, so the model should stop
+    /// waiting for them to act.
+    ///
+    /// The provided implementation does nothing.
+    fn on_actor_incapacitated(
+        &self,
+        _model: &mut Self::RoundsModel,
+        _actor: &dyn Actor<R>,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+    }
+
+    /// Invoked when an incapacitated actor is revived.
+    ///
+    /// The provided implementation does nothing.
+    fn on_actor_revived(
+        &self,
+        _model: &mut Self::RoundsModel,
+        _actor: &dyn Actor<R>,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+    }
+}
+
+/// Type to represent a rounds seed.
+/// It is used to bootstrap the `RoundsModel` for a game.
+pub type RoundsSeed<R> = <<R as BattleRules>::RR as RoundsRules<R>>::RoundsSeed;
+
+/// Type to represent a phase of a turn.
+///
+/// Phases split a turn in ordered steps, such as upkeep, movement and action.
+pub type Phase<R> = <<R as BattleRules>::RR as RoundsRules<R>>::Phase;
+
+/// Type to store all information about the order of turns and rounds in the game.
+///
+/// The round model should contain enough data to compute which actor will act next.
+/// It might be based on a round-robin policy, on the actor's quickness or on any other
+/// arbitrary metric.
+pub type RoundsModel<R> = <<R as BattleRules>::RR as RoundsRules<R>>::RoundsModel;
+
+/// Event to make an actor start a new turn.
+///
+/// When an actor starts a turn all his status effects will be updated.
+///
+/// # Examples
+/// ```
+/// use weasel::{
+///     battle_rules, rules::empty::*, Battle, BattleController, BattleRules, CreateCreature,
+///     CreateTeam, EntityId, EventTrigger, Server, StartTurn,
+/// };
+///
+/// battle_rules! {}
+///
+/// let battle = Battle::builder(CustomRules::new()).build();
+/// let mut server = Server::builder(battle).build();
+///
+/// let team_id = 1;
+/// CreateTeam::trigger(&mut server, team_id).fire().unwrap();
+/// let creature_id = 1;
+/// let position = ();
+/// CreateCreature::trigger(&mut server, creature_id, team_id, position)
+///     .fire()
+///     .unwrap();
+///
+/// StartTurn::trigger(&mut server, EntityId::Creature(creature_id))
+///     .fire()
+///     .unwrap();
+/// assert!(server
+///     .battle()
+///     .rounds()
+///     .state()
+///     .has_actor(&EntityId::Creature(creature_id)));
+/// ```
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct StartTurn<R: BattleRules> {
+    #[cfg_attr(
+        feature = "serialization",
+        serde(bound(
+            serialize = "Vec<EntityId<R>>: Serialize",
+            deserialize = "Vec<EntityId<R>>: Deserialize<'de>"
+        ))
+    )]
+    ids: Vec<EntityId<R>>,
+}
+
+impl<R: BattleRules> StartTurn<R> {
+    /// Returns a trigger for this event, to start a turn with a single actor.
+    pub fn trigger<P: EventProcessor<R>>(
+        processor: &mut P,
+        id: EntityId<R>,
+    ) -> StartTurnTrigger<R, P> {
+        StartTurnTrigger {
+            processor,
+            ids: vec![id],
+        }
+    }
+
+    /// Returns a trigger for this event, to start a turn with a list of actors.\
+    /// Duplicated ids will be dropped during the event's processing.
+    pub fn trigger_with_actors<P, I>(processor: &mut P, ids: I) -> StartTurnTrigger<R, P>
+    where
+        P: EventProcessor<R>,
+        I: IntoIterator<Item = EntityId<R>>,
+    {
+        StartTurnTrigger {
+            processor,
+            ids: ids.into_iter().collect(),
+        }
+    }
+
+    /// Returns the ids of the entities that will start the turn.
+    pub fn ids(&self) -> &Vec<EntityId<R>> {
+        &self.ids
+    }
+}
+
+impl<R: BattleRules> Debug for StartTurn<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "StartTurn {{ ids: {:?} }}", self.ids)
+    }
+}
+
+impl<R: BattleRules> Clone for StartTurn<R> {
+    fn clone(&self) -> Self {
+        Self {
+            ids: self.ids.clone(),
+        }
+    }
+}
+
+impl<R: BattleRules + 'static> Event<R> for StartTurn<R> {
+    fn verify(&self, battle: &Battle<R>) -> WeaselResult<(), R> {
+        // Verify that the battle has started.
+        if battle.phase() == BattlePhase::Deployment {
+            return Err(WeaselError::BattleNotStarted);
+        }
+        // Verify if a turn can start.
+        if let TurnState::Started(_) = battle.rounds().state() {
+            return Err(WeaselError::TurnInProgress);
+        }
+        for id in &self.ids {
+            // Verify if entity is an actor.
+            if !id.is_actor() {
+                return Err(WeaselError::NotAnActor(id.clone()));
+            }
+            // Verify if entity exists.
+            if let Some(actor) = battle.entities().actor(&id) {
+                // Verify if actor is eligible.
+                if !battle.rounds().eligible(actor) {
+                    return Err(WeaselError::ActorNotEligible(id.clone()));
+                }
+            } else {
+                return Err(WeaselError::EntityNotFound(id.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn apply(&self, battle: &mut Battle<R>, event_queue: &mut Option<EventQueue<R>>) {
+        // Set the turn state.
+        let actors_ids: IndexSet<_> = self.ids.iter().cloned().collect();
+        battle
+            .state
+            .rounds
+            .set_state(TurnState::Started(actors_ids.clone()));
+        // Enter the first phase.
+        battle.state.rounds.start_phases(
+            &battle.state.entities,
+            &battle.state.space,
+            &mut battle.entropy,
+            &mut battle.metrics.write_handle(),
+        );
+        // Perform some operations on every actor.
+        for id in &actors_ids {
+            let metrics = &mut battle.metrics.write_handle();
+            // Get the actor.
+            let actor = battle
+                .state
+                .entities
+                .actor(id)
+                .unwrap_or_else(|| panic!("constraint violated: actor {:?} not found", id));
+            // Invoke `RoundRules` callback.
+            battle.state.rounds.rules.on_start(
+                &battle.state.entities,
+                &battle.state.space,
+                &mut battle.state.rounds.model,
+                actor,
+                &mut battle.entropy,
+                metrics,
+            );
+            // Refill the actor's budget, unless it's resuming a delayed turn.
+            let rounds = &mut battle.state.rounds;
+            if !rounds.budgets.contains_key(id) {
+                match rounds.rules.budget(&rounds.model, actor) {
+                    Some(budget) => {
+                        rounds.budgets.insert(id.clone(), budget);
+                    }
+                    None => {
+                        rounds.budgets.shift_remove(id);
+                    }
+                }
+            }
+            // Invoke `ActorRules` callback, unless the actor is resuming a delayed turn.
+            if !battle.state.rounds.has_delayed(id) {
+                battle.rules.actor_rules().on_turn_start(
+                    &battle.state,
+                    actor,
+                    event_queue,
+                    &mut battle.entropy,
+                    metrics,
+                );
+            }
+            // Update all statuses afflicting the actor, unless it happens in a later phase
+            // or they were already updated before the actor delayed its turn.
+            if battle.state.rounds.must_update_statuses(id) {
+                update_statuses(id, battle, event_queue)
+                    .unwrap_or_else(|err| panic!("constraint violated: {:?}", err));
+            }
+        }
+    }
+
+    fn kind(&self) -> EventKind {
+        EventKind::StartTurn
+    }
+
+    fn box_clone(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn rights<'a>(&'a self, battle: &'a Battle<R>) -> EventRights<'a, R> {
+        // Collect all teams involved out of the list of actors.
+        let mut teams = Vec::new();
+        for id in &self.ids {
+            let actor =
+                battle.state.entities.actor(id).unwrap_or_else(|| {
+                    panic!("constraint violated: actor {:?} not found", id.clone())
+                });
+            teams.push(actor.team_id());
+        }
+        EventRights::Teams(teams)
+    }
+}
+
+/// Trigger to build and fire a `StartTurn` event.
+pub struct StartTurnTrigger<'a, R, P>
+where
+    R: BattleRules,
+    P: EventProcessor<R>,
+{
+    processor: &'a mut P,
+    ids: Vec<EntityId<R>>,
+}
+
+impl<'a, R, P> EventTrigger<'a, R, P> for StartTurnTrigger<'a, R, P>
+where
+    R: BattleRules + 'static,
+    P: EventProcessor<R>,
+{
+    fn processor(&'a mut self) -> &'a mut P {
+        self.processor
+    }
+
+    /// Returns an `StartTurn` event.
+    fn event(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(StartTurn {
+            ids: self.ids.clone(),
+        })
+    }
+}
+
+/// Event to end the current turn.
+///
+/// # Examples
+/// ```
+/// use weasel::{
+///     battle_rules, round::TurnState, rules::empty::*, Battle, BattleController, BattleRules,
+///     CreateCreature, CreateTeam, EndTurn, EntityId, EventTrigger, Server, StartTurn,
+/// };
+///
+/// battle_rules! {}
+///
+/// let battle = Battle::builder(CustomRules::new()).build();
+/// let mut server = Server::builder(battle).build();
+///
+/// let team_id = 1;
+/// CreateTeam::trigger(&mut server, team_id).fire().unwrap();
+/// let creature_id = 1;
+/// let position = ();
+/// CreateCreature::trigger(&mut server, creature_id, team_id, position)
+///     .fire()
+///     .unwrap();
+/// StartTurn::trigger(&mut server, EntityId::Creature(creature_id))
+///     .fire()
+///     .unwrap();
+///
+/// EndTurn::trigger(&mut server).fire().unwrap();
+/// assert_eq!(*server.battle().rounds().state(), TurnState::Ready);
+/// ```
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct EndTurn<R> {
+    #[cfg_attr(feature = "serialization", serde(skip))]
+    _phantom: PhantomData<R>,
+}
+
+impl<R: BattleRules> EndTurn<R> {
+    /// Returns a trigger for this event.
+    pub fn trigger<P: EventProcessor<R>>(processor: &mut P) -> EndTurnTrigger<R, P> {
+        EndTurnTrigger {
+            processor,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<R> Debug for EndTurn<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "EndTurn {{ }}")
+    }
+}
+
+impl<R> Clone for EndTurn<R> {
+    fn clone(&self) -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<R: BattleRules + 'static> Event<R> for EndTurn<R> {
+    fn verify(&self, battle: &Battle<R>) -> WeaselResult<(), R> {
+        // Verify if the turn can end.
+        if let TurnState::Ready = battle.rounds().state() {
+            return Err(WeaselError::NoTurnInProgress);
+        }
+        Ok(())
+    }
+
+    fn apply(&self, battle: &mut Battle<R>, event_queue: &mut Option<EventQueue<R>>) {
+        let actors_ids = if let TurnState::Started(actors) = battle.state.rounds.state() {
+            actors.clone()
+        } else {
+            panic!("constraint violated: end turn called when state is not started");
+        };
+        // Update the statuses of actors ending their turn before the statuses phase.
+        for actor_id in &actors_ids {
+            if battle.state.rounds.statuses_pending(actor_id) {
+                update_statuses(actor_id, battle, event_queue)
+                    .unwrap_or_else(|err| panic!("constraint violated: {:?}", err));
+            }
+        }
+        // End the turn for each actor.
+        for actor_id in actors_ids {
+            let actor = battle.state.entities.actor(&actor_id).unwrap_or_else(|| {
+                panic!(
+                    "constraint violated: actor {:?} not found",
+                    actor_id.clone()
+                )
+            });
+            let metrics = &mut battle.metrics.write_handle();
+            // Invoke `CharacterRules` callback.
+            battle.rules.actor_rules().on_turn_end(
+                &battle.state,
+                actor,
+                event_queue,
+                &mut battle.entropy,
+                metrics,
+            );
+            // Invoke `SpaceRules` callback.
+            let entity = battle.state.entities.entity(&actor_id).unwrap_or_else(|| {
+                panic!(
+                    "constraint violated: entity {:?} not found",
+                    actor_id.clone()
+                )
+            });
+            battle.state.space.on_turn_end(entity, event_queue, metrics);
+            // Invoke `RoundRules` callback.
+            battle.state.rounds.on_end(
+                &battle.state.entities,
+                &battle.state.space,
+                actor,
+                &mut battle.entropy,
+                metrics,
+            );
+            // Check teams' objectives.
+            Battle::check_objectives(
+                &battle.state,
+                &battle.rules.team_rules(),
+                &battle.metrics.read_handle(),
+                event_queue,
+                Checkpoint::TurnEnd,
+            );
+        }
+        // Increase the turns counter.
+        battle.rounds_mut().increase_completed_turns();
+        conclude_turn(battle, event_queue);
+    }
+
+    fn kind(&self) -> EventKind {
+        EventKind::EndTurn
+    }
+
+    fn box_clone(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn rights<'a>(&'a self, battle: &'a Battle<R>) -> EventRights<'a, R> {
+        let actors = if let TurnState::Started(actors) = battle.state.rounds.state() {
+            actors
+        } else {
+            panic!("constraint violated: end turn called when state is not started");
+        };
+        // Collect the rights to all teams involved.
+        let mut teams = Vec::new();
+        for actor_id in actors {
+            let actor = battle.state.entities.actor(actor_id).unwrap_or_else(|| {
+                panic!(
+                    "constraint violated: actor {:?} not found",
+                    actor_id.clone()
+                )
+            });
+            teams.push(actor.team_id());
+        }
+        EventRights::Teams(teams)
+    }
+}
+
+/// Trigger to build and fire an `EndTurn` event.
+pub struct EndTurnTrigger<'a, R, P>
+where
+    R: BattleRules,
+    P: EventProcessor<R>,
+{
+    processor: &'a mut P,
+    _phantom: PhantomData<R>,
+}
+
+impl<'a, R, P> EventTrigger<'a, R, P> for EndTurnTrigger<'a, R, P>
+where
+    R: BattleRules + 'static,
+    P: EventProcessor<R>,
+{
+    fn processor(&'a mut self) -> &'a mut P {
+        self.processor
+    }
+
+    /// Returns an `EndTurn` event.
+    fn event(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(EndTurn {
+            _phantom: self._phantom,
+        })
     }
 }
 
-/// Type to represent a rounds seed.
-/// It is used to bootstrap the `RoundsModel` for a game.
-pub type RoundsSeed<R> = <<R as BattleRules>::RR as RoundsRules<R>>::RoundsSeed;
-
-/// Type to store all information about the order of turns and rounds in the game.
-///
-/// The round model should contain enough data to compute which actor will act next.
-/// It might be based on a round-robin policy, on the actor's quickness or on any other
-/// arbitrary metric.
-pub type RoundsModel<R> = <<R as BattleRules>::RR as RoundsRules<R>>::RoundsModel;
-
-/// Event to make an actor start a new turn.
+/// Event to end the current turn without consuming the actors' slot in the round.
 ///
-/// When an actor starts a turn all his status effects will be updated.
+/// Actors who delay their turn are not considered to have acted, and
+/// `RoundsRules::on_delay` can re-insert them later in the order of turns.\
+/// When a delayed actor starts its turn again within the same round, it keeps the budget
+/// it didn't spend, its statuses are not updated a second time and
+/// `ActorRules::on_turn_start` is not invoked again. End of turn callbacks are invoked
+/// only once the turn is completed.\
+/// A delayed turn doesn't count as a completed turn.
 ///
 /// # Examples
 /// ```
 /// use weasel::{
-///     battle_rules, rules::empty::*, Battle, BattleController, BattleRules, CreateCreature,
-///     CreateTeam, EntityId, EventTrigger, Server, StartTurn,
+///     battle_rules, round::TurnState, rules::empty::*, Battle, BattleController, BattleRules,
+///     CreateCreature, CreateTeam, DelayTurn, EntityId, EventTrigger, Server, StartTurn,
 /// };
 ///
 /// battle_rules! {}
@@ -288,138 +1251,84 @@ pub type RoundsModel<R> = <<R as BattleRules>::RR as RoundsRules<R>>::RoundsMode
 /// CreateCreature::trigger(&mut server, creature_id, team_id, position)
 ///     .fire()
 ///     .unwrap();
-///
 /// StartTurn::trigger(&mut server, EntityId::Creature(creature_id))
 ///     .fire()
 ///     .unwrap();
-/// assert!(server
+///
+/// DelayTurn::trigger(&mut server).fire().unwrap();
+/// assert_eq!(*server.battle().rounds().state(), TurnState::Ready);
+/// assert!(!server
 ///     .battle()
 ///     .rounds()
-///     .state()
-///     .has_actor(&EntityId::Creature(creature_id)));
+///     .has_acted(&EntityId::Creature(creature_id)));
 /// ```
 #[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
-pub struct StartTurn<R: BattleRules> {
-    #[cfg_attr(
-        feature = "serialization",
-        serde(bound(
-            serialize = "Vec<EntityId<R>>: Serialize",
-            deserialize = "Vec<EntityId<R>>: Deserialize<'de>"
-        ))
-    )]
-    ids: Vec<EntityId<R>>,
+pub struct DelayTurn<R> {
+    #[cfg_attr(feature = "serialization", serde(skip))]
+    _phantom: PhantomData<R>,
 }
 
-impl<R: BattleRules> StartTurn<R> {
-    /// Returns a trigger for this event, to start a turn with a single actor.
-    pub fn trigger<P: EventProcessor<R>>(
-        processor: &mut P,
-        id: EntityId<R>,
-    ) -> StartTurnTrigger<R, P> {
-        StartTurnTrigger {
-            processor,
-            ids: vec![id],
-        }
-    }
-
-    /// Returns a trigger for this event, to start a turn with a list of actors.\
-    /// Duplicated ids will be dropped during the event's processing.
-    pub fn trigger_with_actors<P, I>(processor: &mut P, ids: I) -> StartTurnTrigger<R, P>
-    where
-        P: EventProcessor<R>,
-        I: IntoIterator<Item = EntityId<R>>,
-    {
-        StartTurnTrigger {
+impl<R: BattleRules> DelayTurn<R> {
+    /// Returns a trigger for this event.
+    pub fn trigger<P: EventProcessor<R>>(processor: &mut P) -> DelayTurnTrigger<'_, R, P> {
+        DelayTurnTrigger {
             processor,
-            ids: ids.into_iter().collect(),
+            _phantom: PhantomData,
         }
     }
-
-    /// Returns the ids of the entities that will start the turn.
-    pub fn ids(&self) -> &Vec<EntityId<R>> {
-        &self.ids
-    }
 }
 
-impl<R: BattleRules> Debug for StartTurn<R> {
+impl<R> Debug for DelayTurn<R> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "StartTurn {{ ids: {:?} }}", self.ids)
+        write!(f, "DelayTurn {{ }}")
     }
 }
 
-impl<R: BattleRules> Clone for StartTurn<R> {
+impl<R> Clone for DelayTurn<R> {
     fn clone(&self) -> Self {
         Self {
-            ids: self.ids.clone(),
+            _phantom: PhantomData,
         }
     }
 }
 
-impl<R: BattleRules + 'static> Event<R> for StartTurn<R> {
+impl<R: BattleRules + 'static> Event<R> for DelayTurn<R> {
     fn verify(&self, battle: &Battle<R>) -> WeaselResult<(), R> {
-        // Verify if a turn can start.
-        if let TurnState::Started(_) = battle.rounds().state() {
-            return Err(WeaselError::TurnInProgress);
-        }
-        for id in &self.ids {
-            // Verify if entity is an actor.
-            if !id.is_actor() {
-                return Err(WeaselError::NotAnActor(id.clone()));
-            }
-            // Verify if entity exists.
-            if let Some(actor) = battle.entities().actor(&id) {
-                // Verify if actor is eligible.
-                if !battle.rounds().eligible(actor) {
-                    return Err(WeaselError::ActorNotEligible(id.clone()));
-                }
-            } else {
-                return Err(WeaselError::EntityNotFound(id.clone()));
-            }
+        // Verify that a turn is in progress.
+        if let TurnState::Ready = battle.rounds().state() {
+            return Err(WeaselError::NoTurnInProgress);
         }
         Ok(())
     }
 
     fn apply(&self, battle: &mut Battle<R>, event_queue: &mut Option<EventQueue<R>>) {
-        // Set the turn state.
-        let actors_ids: IndexSet<_> = self.ids.iter().cloned().collect();
-        battle
-            .state
-            .rounds
-            .set_state(TurnState::Started(actors_ids.clone()));
-        // Perform some operations on every actor.
-        for id in &actors_ids {
-            let metrics = &mut battle.metrics.write_handle();
-            // Get the actor.
-            let actor = battle
-                .state
-                .entities
-                .actor(id)
-                .unwrap_or_else(|| panic!("constraint violated: actor {:?} not found", id));
+        let actors_ids = if let TurnState::Started(actors) = battle.state.rounds.state() {
+            actors.clone()
+        } else {
+            panic!("constraint violated: delay turn called when state is not started");
+        };
+        // Delay the turn of each actor.
+        for actor_id in actors_ids {
+            let actor = battle.state.entities.actor(&actor_id).unwrap_or_else(|| {
+                panic!(
+                    "constraint violated: actor {:?} not found",
+                    actor_id.clone()
+                )
+            });
             // Invoke `RoundRules` callback.
-            battle.state.rounds.rules.on_start(
+            battle.state.rounds.on_delay(
                 &battle.state.entities,
                 &battle.state.space,
-                &mut battle.state.rounds.model,
-                actor,
-                &mut battle.entropy,
-                metrics,
-            );
-            // Invoke `CharacterRules` callback.
-            battle.rules.actor_rules().on_turn_start(
-                &battle.state,
                 actor,
-                event_queue,
                 &mut battle.entropy,
-                metrics,
+                &mut battle.metrics.write_handle(),
             );
-            // Update all statuses afflicting the actor.
-            update_statuses(id, battle, event_queue)
-                .unwrap_or_else(|err| panic!("constraint violated: {:?}", err));
         }
+        conclude_turn(battle, event_queue);
     }
 
     fn kind(&self) -> EventKind {
-        EventKind::StartTurn
+        EventKind::DelayTurn
     }
 
     fn box_clone(&self) -> Box<dyn Event<R> + Send> {
@@ -431,30 +1340,37 @@ impl<R: BattleRules + 'static> Event<R> for StartTurn<R> {
     }
 
     fn rights<'a>(&'a self, battle: &'a Battle<R>) -> EventRights<'a, R> {
-        // Collect all teams involved out of the list of actors.
+        let actors = if let TurnState::Started(actors) = battle.state.rounds.state() {
+            actors
+        } else {
+            panic!("constraint violated: delay turn called when state is not started");
+        };
+        // Collect the rights to all teams involved.
         let mut teams = Vec::new();
-        for id in &self.ids {
-            let actor =
-                battle.state.entities.actor(id).unwrap_or_else(|| {
-                    panic!("constraint violated: actor {:?} not found", id.clone())
-                });
+        for actor_id in actors {
+            let actor = battle.state.entities.actor(actor_id).unwrap_or_else(|| {
+                panic!(
+                    "constraint violated: actor {:?} not found",
+                    actor_id.clone()
+                )
+            });
             teams.push(actor.team_id());
         }
         EventRights::Teams(teams)
     }
 }
 
-/// Trigger to build and fire a `StartTurn` event.
-pub struct StartTurnTrigger<'a, R, P>
+/// Trigger to build and fire a `DelayTurn` event.
+pub struct DelayTurnTrigger<'a, R, P>
 where
     R: BattleRules,
     P: EventProcessor<R>,
 {
     processor: &'a mut P,
-    ids: Vec<EntityId<R>>,
+    _phantom: PhantomData<R>,
 }
 
-impl<'a, R, P> EventTrigger<'a, R, P> for StartTurnTrigger<'a, R, P>
+impl<'a, R, P> EventTrigger<'a, R, P> for DelayTurnTrigger<'a, R, P>
 where
     R: BattleRules + 'static,
     P: EventProcessor<R>,
@@ -463,21 +1379,23 @@ where
         self.processor
     }
 
-    /// Returns an `StartTurn` event.
+    /// Returns a `DelayTurn` event.
     fn event(&self) -> Box<dyn Event<R> + Send> {
-        Box::new(StartTurn {
-            ids: self.ids.clone(),
+        Box::new(DelayTurn {
+            _phantom: self._phantom,
         })
     }
 }
 
-/// Event to end the current turn.
+/// Event to move the current turn into its next phase.
+///
+/// Phases are defined by `RoundsRules::phases`.
 ///
 /// # Examples
 /// ```
 /// use weasel::{
-///     battle_rules, round::TurnState, rules::empty::*, Battle, BattleController, BattleRules,
-///     CreateCreature, CreateTeam, EndTurn, EntityId, EventTrigger, Server, StartTurn,
+///     battle_rules, rules::empty::*, AdvancePhase, Battle, BattleController, BattleRules,
+///     CreateCreature, CreateTeam, EntityId, EventTrigger, Server, StartTurn, WeaselError,
 /// };
 ///
 /// battle_rules! {}
@@ -496,32 +1414,36 @@ where
 ///     .fire()
 ///     .unwrap();
 ///
-/// EndTurn::trigger(&mut server).fire().unwrap();
-/// assert_eq!(*server.battle().rounds().state(), TurnState::Ready);
+/// // Empty rounds rules don't divide turns in phases.
+/// let result = AdvancePhase::trigger(&mut server).fire();
+/// assert_eq!(
+///     result.err().map(|e| e.unfold()),
+///     Some(WeaselError::NoNextPhase)
+/// );
 /// ```
 #[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
-pub struct EndTurn<R> {
+pub struct AdvancePhase<R> {
     #[cfg_attr(feature = "serialization", serde(skip))]
     _phantom: PhantomData<R>,
 }
 
-impl<R: BattleRules> EndTurn<R> {
+impl<R: BattleRules> AdvancePhase<R> {
     /// Returns a trigger for this event.
-    pub fn trigger<P: EventProcessor<R>>(processor: &mut P) -> EndTurnTrigger<R, P> {
-        EndTurnTrigger {
+    pub fn trigger<P: EventProcessor<R>>(processor: &mut P) -> AdvancePhaseTrigger<'_, R, P> {
+        AdvancePhaseTrigger {
             processor,
             _phantom: PhantomData,
         }
     }
 }
 
-impl<R> Debug for EndTurn<R> {
+impl<R> Debug for AdvancePhase<R> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "EndTurn {{ }}")
+        write!(f, "AdvancePhase {{ }}")
     }
 }
 
-impl<R> Clone for EndTurn<R> {
+impl<R> Clone for AdvancePhase<R> {
     fn clone(&self) -> Self {
         Self {
             _phantom: PhantomData,
@@ -529,63 +1451,42 @@ impl<R> Clone for EndTurn<R> {
     }
 }
 
-impl<R: BattleRules + 'static> Event<R> for EndTurn<R> {
+impl<R: BattleRules + 'static> Event<R> for AdvancePhase<R> {
     fn verify(&self, battle: &Battle<R>) -> WeaselResult<(), R> {
-        // Verify if the turn can end.
+        // Verify that a turn is in progress.
         if let TurnState::Ready = battle.rounds().state() {
             return Err(WeaselError::NoTurnInProgress);
         }
+        // Verify that there's another phase.
+        if !battle.rounds().has_next_phase() {
+            return Err(WeaselError::NoNextPhase);
+        }
         Ok(())
     }
 
     fn apply(&self, battle: &mut Battle<R>, event_queue: &mut Option<EventQueue<R>>) {
+        battle.state.rounds.next_phase(
+            &battle.state.entities,
+            &battle.state.space,
+            &mut battle.entropy,
+            &mut battle.metrics.write_handle(),
+        );
+        // Update the statuses of all actors, if it's the right phase.
         let actors_ids = if let TurnState::Started(actors) = battle.state.rounds.state() {
             actors.clone()
         } else {
-            panic!("constraint violated: end turn called when state is not started");
+            panic!("constraint violated: advance phase called when state is not started");
         };
-        // End the turn for each actor.
-        for actor_id in actors_ids {
-            let actor = battle.state.entities.actor(&actor_id).unwrap_or_else(|| {
-                panic!(
-                    "constraint violated: actor {:?} not found",
-                    actor_id.clone()
-                )
-            });
-            let metrics = &mut battle.metrics.write_handle();
-            // Invoke `CharacterRules` callback.
-            battle.rules.actor_rules().on_turn_end(
-                &battle.state,
-                actor,
-                event_queue,
-                &mut battle.entropy,
-                metrics,
-            );
-            // Invoke `RoundRules` callback.
-            battle.state.rounds.on_end(
-                &battle.state.entities,
-                &battle.state.space,
-                actor,
-                &mut battle.entropy,
-                metrics,
-            );
-            // Check teams' objectives.
-            Battle::check_objectives(
-                &battle.state,
-                &battle.rules.team_rules(),
-                &battle.metrics.read_handle(),
-                event_queue,
-                Checkpoint::TurnEnd,
-            );
+        for id in &actors_ids {
+            if battle.state.rounds.must_update_statuses(id) {
+                update_statuses(id, battle, event_queue)
+                    .unwrap_or_else(|err| panic!("constraint violated: {:?}", err));
+            }
         }
-        // Set the turn state.
-        battle.state.rounds.set_state(TurnState::Ready);
-        // Increase the turns counter.
-        battle.rounds_mut().increase_completed_turns();
     }
 
     fn kind(&self) -> EventKind {
-        EventKind::EndTurn
+        EventKind::AdvancePhase
     }
 
     fn box_clone(&self) -> Box<dyn Event<R> + Send> {
@@ -600,7 +1501,7 @@ impl<R: BattleRules + 'static> Event<R> for EndTurn<R> {
         let actors = if let TurnState::Started(actors) = battle.state.rounds.state() {
             actors
         } else {
-            panic!("constraint violated: end turn called when state is not started");
+            panic!("constraint violated: advance phase called when state is not started");
         };
         // Collect the rights to all teams involved.
         let mut teams = Vec::new();
@@ -617,8 +1518,8 @@ impl<R: BattleRules + 'static> Event<R> for EndTurn<R> {
     }
 }
 
-/// Trigger to build and fire an `EndTurn` event.
-pub struct EndTurnTrigger<'a, R, P>
+/// Trigger to build and fire an `AdvancePhase` event.
+pub struct AdvancePhaseTrigger<'a, R, P>
 where
     R: BattleRules,
     P: EventProcessor<R>,
@@ -627,7 +1528,7 @@ where
     _phantom: PhantomData<R>,
 }
 
-impl<'a, R, P> EventTrigger<'a, R, P> for EndTurnTrigger<'a, R, P>
+impl<'a, R, P> EventTrigger<'a, R, P> for AdvancePhaseTrigger<'a, R, P>
 where
     R: BattleRules + 'static,
     P: EventProcessor<R>,
@@ -636,9 +1537,9 @@ where
         self.processor
     }
 
-    /// Returns an `EndTurn` event.
+    /// Returns an `AdvancePhase` event.
     fn event(&self) -> Box<dyn Event<R> + Send> {
-        Box::new(EndTurn {
+        Box::new(AdvancePhase {
             _phantom: self._phantom,
         })
     }
@@ -825,6 +1726,10 @@ impl<R> Clone for EnvironmentTurn<R> {
 
 impl<R: BattleRules + 'static> Event<R> for EnvironmentTurn<R> {
     fn verify(&self, battle: &Battle<R>) -> WeaselResult<(), R> {
+        // Verify that the battle has started.
+        if battle.phase() == BattlePhase::Deployment {
+            return Err(WeaselError::BattleNotStarted);
+        }
         // Verify that no other turn is in progress.
         if let TurnState::Started(_) = battle.rounds().state() {
             return Err(WeaselError::TurnInProgress);
@@ -894,7 +1799,9 @@ where
 /// number of these.\
 /// Managing rounds is optional and not required in order to start or end turns.
 ///
-/// The first round is implicitly started at the beginning of the battle.
+/// The first round is implicitly started at the beginning of the battle.\
+/// If `RoundsRules::auto_end_round` returns true, this event is fired automatically
+/// once every eligible actor has completed a turn in the current round.
 ///
 /// # Examples
 /// ```
@@ -953,8 +1860,14 @@ impl<R: BattleRules + 'static> Event<R> for EndRound<R> {
         Ok(())
     }
 
-    fn apply(&self, battle: &mut Battle<R>, _: &mut Option<EventQueue<R>>) {
-        battle.rounds_mut().increase_completed_rounds();
+    fn apply(&self, battle: &mut Battle<R>, event_queue: &mut Option<EventQueue<R>>) {
+        battle.state.rounds.end_round(
+            &battle.state.entities,
+            &battle.state.space,
+            &mut battle.entropy,
+            &mut battle.metrics.write_handle(),
+        );
+        advance_turn(battle, event_queue);
     }
 
     fn kind(&self) -> EventKind {
@@ -996,3 +1909,98 @@ where
         })
     }
 }
+
+/// Returns true if the given event queue contains an `EndBattle` event.
+fn battle_ending<R: BattleRules>(event_queue: &EventQueue<R>) -> bool {
+    event_queue
+        .iter()
+        .any(|prototype| prototype.kind() == EventKind::EndBattle)
+}
+
+/// Moves the rounds to the `Ready` state after the end of a turn, then either ends
+/// the round or advances to the next turn, as requested by `RoundsRules`.
+fn conclude_turn<R: BattleRules + 'static>(
+    battle: &mut Battle<R>,
+    event_queue: &mut Option<EventQueue<R>>,
+) {
+    // Set the turn state.
+    battle.state.rounds.set_state(TurnState::Ready);
+    // End the round, if every eligible actor has acted.
+    let rounds = &battle.state.rounds;
+    if rounds.rules.auto_end_round(&rounds.model) && rounds.round_complete(&battle.state.entities) {
+        if let Some(event_queue) = event_queue {
+            // Don't end the round if the battle is about to end.
+            if !battle_ending(event_queue) {
+                // `EndRound` will take care of advancing to the next turn.
+                EndRound::trigger(event_queue).fire();
+            }
+        }
+    } else {
+        advance_turn(battle, event_queue);
+    }
+}
+
+/// Fires the event to start the next turn, if `RoundsRules` want turns to advance
+/// automatically.
+fn advance_turn<R: BattleRules + 'static>(
+    battle: &Battle<R>,
+    event_queue: &mut Option<EventQueue<R>>,
+) {
+    let rounds = &battle.state.rounds;
+    if !rounds.rules.auto_advance_turns(&rounds.model) {
+        return;
+    }
+    if let Some(event_queue) = event_queue {
+        // Don't start a new turn if the battle is about to end.
+        if battle_ending(event_queue) {
+            return;
+        }
+        let actors = rounds.next_actors(&battle.state.entities);
+        if actors.is_empty() {
+            EnvironmentTurn::trigger(event_queue).fire();
+        } else {
+            StartTurn::trigger_with_actors(event_queue, actors).fire();
+        }
+    }
+}
+
+/// Fires an `EndTurn` event if `RoundsRules` want to end turns automatically
+/// and the current actors spent their whole budget.
+pub(crate) fn end_turn_if_exhausted<R: BattleRules + 'static>(
+    battle: &Battle<R>,
+    event_queue: &mut Option<EventQueue<R>>,
+) {
+    let rounds = &battle.state.rounds;
+    if !rounds.rules.auto_end_turn(&rounds.model) || !rounds.budgets_exhausted() {
+        return;
+    }
+    fire_end_turn(event_queue);
+}
+
+/// Fires an `EndTurn` event if the given entity was acting in the current turn
+/// and it has been incapacitated.
+pub(crate) fn end_turn_if_incapacitated<R: BattleRules + 'static>(
+    battle: &Battle<R>,
+    id: &EntityId<R>,
+    event_queue: &mut Option<EventQueue<R>>,
+) {
+    let incapacitated =
+        matches!(battle.entities().actor(id), Some(actor) if actor.is_incapacitated());
+    if incapacitated && battle.state.rounds.is_acting(id) {
+        fire_end_turn(event_queue);
+    }
+}
+
+/// Fires an `EndTurn` event, unless the turn is already ending or the battle is over.
+fn fire_end_turn<R: BattleRules + 'static>(event_queue: &mut Option<EventQueue<R>>) {
+    if let Some(event_queue) = event_queue {
+        // Don't end the turn twice, nor after the end of the battle.
+        if !battle_ending(event_queue)
+            && !event_queue.iter().any(|prototype| {
+                prototype.kind() == EventKind::EndTurn || prototype.kind() == EventKind::DelayTurn
+            })
+        {
+            EndTurn::trigger(event_queue).fire();
+        }
+    }
+}
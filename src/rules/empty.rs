@@ -86,6 +86,7 @@ impl<R: BattleRules> SpaceRules<R> for EmptySpaceRules {
     type SpaceSeed = ();
     type SpaceModel = ();
     type SpaceAlteration = ();
+    type Direction = ();
 
     fn generate_model(&self, _seed: &Option<Self::SpaceSeed>) -> Self::SpaceModel {}
 }
@@ -97,6 +98,7 @@ pub struct EmptyRoundsRules {}
 impl<R: BattleRules> RoundsRules<R> for EmptyRoundsRules {
     type RoundsSeed = ();
     type RoundsModel = ();
+    type Phase = ();
 
     fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {}
 }
@@ -118,6 +120,8 @@ impl<R: BattleRules> UserRules<R> for EmptyUserRules {
     type UserMetricId = u16;
     #[cfg(feature = "serialization")]
     type UserEventPackage = ();
+    type UserCondition = ();
+    type UserError = String;
 }
 
 /// Entropy rules that do not have randomness. They just return the average value.
@@ -4,5 +4,7 @@ pub mod ability;
 pub mod empty;
 pub mod entropy;
 mod generic;
+pub mod round;
+pub mod space;
 pub mod statistic;
 pub mod status;
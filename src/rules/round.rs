@@ -0,0 +1,609 @@
+//! Predefined rules for rounds.
+
+use crate::actor::Actor;
+use crate::battle::BattleRules;
+use crate::entity::{Entities, EntityId};
+use crate::entropy::Entropy;
+use crate::metric::WriteMetrics;
+use crate::round::RoundsRules;
+use crate::space::Space;
+use num_traits::{PrimInt, SaturatingMul};
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter, Result};
+use std::ops::Add;
+
+/// Trait to extract the speed of actors, used to compute their initiative.
+pub trait SpeedSource<R: BattleRules> {
+    #[cfg(not(feature = "serialization"))]
+    /// Type to represent speed and initiative scores.
+    type Speed: Copy + Default + Ord + Add<Output = Self::Speed> + Debug + Send;
+    #[cfg(feature = "serialization")]
+    /// Type to represent speed and initiative scores.
+    type Speed: Copy
+        + Default
+        + Ord
+        + Add<Output = Self::Speed>
+        + Debug
+        + Send
+        + Serialize
+        + for<'a> Deserialize<'a>;
+
+    /// Returns the speed of the given actor.
+    fn speed(&self, actor: &dyn Actor<R>) -> Self::Speed;
+
+    /// Applies a random variation to a speed value, each time it is added to an
+    /// actor's initiative.
+    ///
+    /// The provided implementation returns `speed` unchanged.
+    fn jitter(&self, speed: Self::Speed, _entropy: &mut Entropy<R>) -> Self::Speed {
+        speed
+    }
+}
+
+/// Queue of actors ordered by initiative score, from the highest to the lowest.
+///
+/// Ties are broken in favor of the actor who joined the battle first.
+pub struct InitiativeQueue<R: BattleRules, V> {
+    entries: Vec<InitiativeEntry<R, V>>,
+    sequence: u64,
+}
+
+struct InitiativeEntry<R: BattleRules, V> {
+    id: EntityId<R>,
+    score: V,
+    sequence: u64,
+}
+
+impl<R: BattleRules, V: Debug> Debug for InitiativeQueue<R, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "InitiativeQueue {{ entries: [")?;
+        for entry in &self.entries {
+            write!(f, "{{ id: {:?}, score: {:?} }}, ", entry.id, entry.score)?;
+        }
+        write!(f, "] }}")
+    }
+}
+
+impl<R: BattleRules, V: Copy + Ord> InitiativeQueue<R, V> {
+    fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            sequence: 0,
+        }
+    }
+
+    /// Returns the id of the actor at the top of the queue.
+    pub fn top(&self) -> Option<&EntityId<R>> {
+        self.entries.first().map(|entry| &entry.id)
+    }
+
+    /// Returns an iterator over the ids of all actors, in the order in which
+    /// they are going to act.
+    pub fn order(&self) -> impl Iterator<Item = &EntityId<R>> {
+        self.entries.iter().map(|entry| &entry.id)
+    }
+
+    /// Returns the initiative score of the given actor.
+    pub fn score(&self, id: &EntityId<R>) -> Option<V> {
+        self.index(id).map(|index| self.entries[index].score)
+    }
+
+    /// Returns the number of actors in the queue.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn index(&self, id: &EntityId<R>) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.id == *id)
+    }
+
+    fn insert(&mut self, id: EntityId<R>, score: V) {
+        self.entries.push(InitiativeEntry {
+            id,
+            score,
+            sequence: self.sequence,
+        });
+        self.sequence += 1;
+        self.sort();
+    }
+
+    fn remove(&mut self, id: &EntityId<R>) {
+        if let Some(index) = self.index(id) {
+            self.entries.remove(index);
+        }
+    }
+
+    fn set_score(&mut self, id: &EntityId<R>, score: V) {
+        if let Some(index) = self.index(id) {
+            self.entries[index].score = score;
+        }
+    }
+
+    /// Moves the given actor right behind the one following it, by matching its score
+    /// and by giving it the lowest precedence in ties between the two.
+    fn yield_turn(&mut self, id: &EntityId<R>) {
+        if let Some(index) = self.index(id) {
+            if index + 1 < self.entries.len() {
+                let (lhs, rhs) = self.entries.split_at_mut(index + 1);
+                let (actor, next) = (&mut lhs[index], &mut rhs[0]);
+                actor.score = next.score;
+                if actor.sequence < next.sequence {
+                    std::mem::swap(&mut actor.sequence, &mut next.sequence);
+                }
+                self.sort();
+            }
+        }
+    }
+
+    fn sort(&mut self) {
+        self.entries.sort_by(|lhs, rhs| {
+            rhs.score
+                .cmp(&lhs.score)
+                .then(lhs.sequence.cmp(&rhs.sequence))
+        });
+    }
+}
+
+/// Rounds rules in which actors act in order of initiative.
+///
+/// Each actor joins the queue with an initiative score equal to its speed.
+/// At the end of every turn, the speed of each actor is added to its score,
+/// while the score of the actors who just acted is reset.
+/// Only the actor at the top of the queue is eligible to act.\
+/// An actor who delays its turn is moved right behind the next actor in the queue.\
+/// Incapacitated actors leave the queue. Once revived, they join it again with an
+/// initiative score equal to their speed.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct InitiativeRules<S> {
+    speed_source: S,
+    auto_advance_turns: bool,
+}
+
+impl<S> InitiativeRules<S> {
+    /// Creates new initiative rules, extracting actors' speed from `speed_source`.
+    pub fn new(speed_source: S) -> Self {
+        Self {
+            speed_source,
+            auto_advance_turns: false,
+        }
+    }
+
+    /// Returns the speed source in use.
+    pub fn speed_source(&self) -> &S {
+        &self.speed_source
+    }
+
+    /// Sets whether turns should advance automatically.\
+    /// See [auto_advance_turns](../../round/trait.RoundsRules.html#method.auto_advance_turns).
+    pub fn set_auto_advance_turns(&mut self, value: bool) {
+        self.auto_advance_turns = value;
+    }
+}
+
+impl<R, S> RoundsRules<R> for InitiativeRules<S>
+where
+    R: BattleRules,
+    S: SpeedSource<R>,
+{
+    type RoundsSeed = ();
+    type RoundsModel = InitiativeQueue<R, S::Speed>;
+    type Phase = ();
+
+    fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {
+        InitiativeQueue::new()
+    }
+
+    fn eligible(&self, model: &Self::RoundsModel, actor: &dyn Actor<R>) -> bool {
+        model.top() == Some(actor.entity_id())
+    }
+
+    fn on_end(
+        &self,
+        entities: &Entities<R>,
+        _space: &Space<R>,
+        model: &mut Self::RoundsModel,
+        actor: &dyn Actor<R>,
+        entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        for other in entities.actors() {
+            if let Some(score) = model.score(other.entity_id()) {
+                let speed = self
+                    .speed_source
+                    .jitter(self.speed_source.speed(other), entropy);
+                model.set_score(other.entity_id(), score + speed);
+            }
+        }
+        model.set_score(actor.entity_id(), S::Speed::default());
+        model.sort();
+    }
+
+    fn on_delay(
+        &self,
+        _entities: &Entities<R>,
+        _space: &Space<R>,
+        model: &mut Self::RoundsModel,
+        actor: &dyn Actor<R>,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        model.yield_turn(actor.entity_id());
+    }
+
+    fn next_actors(&self, model: &Self::RoundsModel, _entities: &Entities<R>) -> Vec<EntityId<R>> {
+        model.top().cloned().into_iter().collect()
+    }
+
+    fn auto_advance_turns(&self, _model: &Self::RoundsModel) -> bool {
+        self.auto_advance_turns
+    }
+
+    fn on_actor_added(
+        &self,
+        model: &mut Self::RoundsModel,
+        actor: &dyn Actor<R>,
+        entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        let speed = self
+            .speed_source
+            .jitter(self.speed_source.speed(actor), entropy);
+        model.insert(actor.entity_id().clone(), speed);
+    }
+
+    fn on_actor_removed(
+        &self,
+        model: &mut Self::RoundsModel,
+        actor: &dyn Actor<R>,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        model.remove(actor.entity_id());
+    }
+    fn on_actor_incapacitated(
+        &self,
+        model: &mut Self::RoundsModel,
+        actor: &dyn Actor<R>,
+        entropy: &mut Entropy<R>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.on_actor_removed(model, actor, entropy, metrics);
+    }
+
+    fn on_actor_revived(
+        &self,
+        model: &mut Self::RoundsModel,
+        actor: &dyn Actor<R>,
+        entropy: &mut Entropy<R>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.on_actor_added(model, actor, entropy, metrics);
+    }
+}
+
+/// Trait to configure the timeline of `TimelineRules`.
+pub trait TimelineSource<R: BattleRules>: SpeedSource<R> {
+    /// Returns the readiness an actor must accumulate in order to act.
+    fn threshold(&self) -> Self::Speed;
+
+    /// Returns the additional readiness the given actor must accumulate before acting
+    /// again, after completing a turn.\
+    /// Use it to push back on the timeline actors who performed heavy actions.
+    ///
+    /// The provided implementation returns `Speed::default()`.
+    fn recovery(&self, _actor: &dyn Actor<R>) -> Self::Speed {
+        Self::Speed::default()
+    }
+}
+
+/// A continuous timeline on which actors accumulate readiness.
+///
+/// The timeline can be used as `RoundsSeed` to restore a previous state.
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serialization",
+    serde(bound(
+        serialize = "EntityId<R>: Serialize, V: Serialize",
+        deserialize = "EntityId<R>: Deserialize<'de>, V: Deserialize<'de>"
+    ))
+)]
+pub struct Timeline<R: BattleRules, V> {
+    threshold: V,
+    entries: Vec<TimelineEntry<R, V>>,
+    sequence: u64,
+}
+
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serialization",
+    serde(bound(
+        serialize = "EntityId<R>: Serialize, V: Serialize",
+        deserialize = "EntityId<R>: Deserialize<'de>, V: Deserialize<'de>"
+    ))
+)]
+struct TimelineEntry<R: BattleRules, V> {
+    id: EntityId<R>,
+    speed: V,
+    gauge: V,
+    recovery: V,
+    sequence: u64,
+}
+
+impl<R: BattleRules, V: Clone> Clone for TimelineEntry<R, V> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            speed: self.speed.clone(),
+            gauge: self.gauge.clone(),
+            recovery: self.recovery.clone(),
+            sequence: self.sequence,
+        }
+    }
+}
+
+impl<R: BattleRules, V: Clone> Clone for Timeline<R, V> {
+    fn clone(&self) -> Self {
+        Self {
+            threshold: self.threshold.clone(),
+            entries: self.entries.clone(),
+            sequence: self.sequence,
+        }
+    }
+}
+
+impl<R: BattleRules, V: Debug> Debug for Timeline<R, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Timeline {{ threshold: {:?}, entries: [", self.threshold)?;
+        for entry in &self.entries {
+            write!(
+                f,
+                "{{ id: {:?}, speed: {:?}, gauge: {:?}, recovery: {:?} }}, ",
+                entry.id, entry.speed, entry.gauge, entry.recovery
+            )?;
+        }
+        write!(f, "] }}")
+    }
+}
+
+impl<R, V> Timeline<R, V>
+where
+    R: BattleRules,
+    V: PrimInt + SaturatingMul,
+{
+    /// Creates an empty timeline in which actors act when their gauge reaches `threshold`.
+    pub fn new(threshold: V) -> Self {
+        Self {
+            threshold,
+            entries: Vec::new(),
+            sequence: 0,
+        }
+    }
+
+    /// Returns the readiness an actor must accumulate in order to act.
+    pub fn threshold(&self) -> V {
+        self.threshold
+    }
+
+    /// Returns the readiness accumulated by the given actor.
+    pub fn gauge(&self, id: &EntityId<R>) -> Option<V> {
+        self.index(id).map(|index| self.entries[index].gauge)
+    }
+
+    /// Returns the ids of the actors who will act in the next `turns` turns, in order.
+    ///
+    /// The prediction assumes that the speed of actors won't change
+    /// and that no actor will be pushed back.
+    pub fn predict(&self, turns: usize) -> Vec<EntityId<R>> {
+        let mut entries = self.entries.clone();
+        let mut actors = Vec::with_capacity(turns);
+        for _ in 0..turns {
+            match Self::advance(self.threshold, &mut entries) {
+                Some(index) => {
+                    let entry = &mut entries[index];
+                    entry.gauge = V::zero();
+                    entry.recovery = V::zero();
+                    actors.push(entry.id.clone());
+                }
+                None => break,
+            }
+        }
+        actors
+    }
+
+    /// Returns the id of the actor who will act next.
+    pub fn next(&self) -> Option<EntityId<R>> {
+        self.predict(1).pop()
+    }
+
+    fn index(&self, id: &EntityId<R>) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.id == *id)
+    }
+
+    /// Advances time until at least one actor is ready.
+    /// Returns the index of the first ready actor, if any.
+    fn advance(threshold: V, entries: &mut [TimelineEntry<R, V>]) -> Option<usize> {
+        // Jump straight to the moment in which the first actor becomes ready.
+        let ticks = entries
+            .iter()
+            .filter_map(|entry| Self::ticks_to_ready(threshold, entry))
+            .min()?;
+        if ticks > V::zero() {
+            for entry in entries.iter_mut() {
+                entry.gauge = entry
+                    .gauge
+                    .saturating_add(entry.speed.saturating_mul(&ticks));
+            }
+        }
+        entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.gauge >= threshold.saturating_add(entry.recovery))
+            .min_by(|(_, lhs), (_, rhs)| {
+                rhs.gauge
+                    .cmp(&lhs.gauge)
+                    .then(lhs.sequence.cmp(&rhs.sequence))
+            })
+            .map(|(index, _)| index)
+    }
+
+    /// Returns how many ticks the given entry needs to become ready,
+    /// or `None` if it will never be.
+    fn ticks_to_ready(threshold: V, entry: &TimelineEntry<R, V>) -> Option<V> {
+        let target = threshold.saturating_add(entry.recovery);
+        if entry.gauge >= target {
+            return Some(V::zero());
+        }
+        if entry.speed <= V::zero() {
+            return None;
+        }
+        let missing = target.saturating_sub(entry.gauge);
+        let ticks = missing / entry.speed;
+        if missing % entry.speed > V::zero() {
+            Some(ticks + V::one())
+        } else {
+            Some(ticks)
+        }
+    }
+}
+
+/// Rounds rules implementing an active time battle.
+///
+/// Actors accumulate readiness over time, proportionally to their speed.
+/// An actor becomes eligible when its gauge reaches the timeline's threshold.\
+/// Incapacitated actors leave the timeline. Once revived, they join it again
+/// with an empty gauge.\
+/// Speeds must be primitive integers. Gauges saturate instead of overflowing.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TimelineRules<S> {
+    source: S,
+    auto_advance_turns: bool,
+}
+
+impl<S> TimelineRules<S> {
+    /// Creates new timeline rules, configured by `source`.
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            auto_advance_turns: false,
+        }
+    }
+
+    /// Returns the timeline source in use.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Sets whether turns should advance automatically.\
+    /// See [auto_advance_turns](../../round/trait.RoundsRules.html#method.auto_advance_turns).
+    pub fn set_auto_advance_turns(&mut self, value: bool) {
+        self.auto_advance_turns = value;
+    }
+}
+
+impl<R, S> RoundsRules<R> for TimelineRules<S>
+where
+    R: BattleRules,
+    S: TimelineSource<R>,
+    S::Speed: PrimInt + SaturatingMul,
+{
+    type RoundsSeed = Timeline<R, S::Speed>;
+    type RoundsModel = Timeline<R, S::Speed>;
+    type Phase = ();
+
+    fn generate_model(&self, seed: &Option<Self::RoundsSeed>) -> Self::RoundsModel {
+        match seed {
+            Some(seed) => seed.clone(),
+            None => Timeline::new(self.source.threshold()),
+        }
+    }
+
+    fn eligible(&self, model: &Self::RoundsModel, actor: &dyn Actor<R>) -> bool {
+        model.next().as_ref() == Some(actor.entity_id())
+    }
+
+    fn on_end(
+        &self,
+        entities: &Entities<R>,
+        _space: &Space<R>,
+        model: &mut Self::RoundsModel,
+        actor: &dyn Actor<R>,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        // Move forward in time, up to the moment of this turn.
+        Timeline::advance(model.threshold, &mut model.entries);
+        if let Some(index) = model.index(actor.entity_id()) {
+            let entry = &mut model.entries[index];
+            entry.gauge = S::Speed::default();
+            entry.recovery = self.source.recovery(actor);
+        }
+        // Update the speed of all actors.
+        for entry in &mut model.entries {
+            if let Some(actor) = entities.actor(&entry.id) {
+                entry.speed = self.source.speed(actor);
+            }
+        }
+    }
+
+    fn next_actors(&self, model: &Self::RoundsModel, _entities: &Entities<R>) -> Vec<EntityId<R>> {
+        model.predict(1)
+    }
+
+    fn auto_advance_turns(&self, _model: &Self::RoundsModel) -> bool {
+        self.auto_advance_turns
+    }
+
+    fn on_actor_added(
+        &self,
+        model: &mut Self::RoundsModel,
+        actor: &dyn Actor<R>,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        model.entries.push(TimelineEntry {
+            id: actor.entity_id().clone(),
+            speed: self.source.speed(actor),
+            gauge: S::Speed::default(),
+            recovery: S::Speed::default(),
+            sequence: model.sequence,
+        });
+        model.sequence += 1;
+    }
+
+    fn on_actor_removed(
+        &self,
+        model: &mut Self::RoundsModel,
+        actor: &dyn Actor<R>,
+        _entropy: &mut Entropy<R>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        if let Some(index) = model.index(actor.entity_id()) {
+            model.entries.remove(index);
+        }
+    }
+    fn on_actor_incapacitated(
+        &self,
+        model: &mut Self::RoundsModel,
+        actor: &dyn Actor<R>,
+        entropy: &mut Entropy<R>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.on_actor_removed(model, actor, entropy, metrics);
+    }
+
+    fn on_actor_revived(
+        &self,
+        model: &mut Self::RoundsModel,
+        actor: &dyn Actor<R>,
+        entropy: &mut Entropy<R>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.on_actor_added(model, actor, entropy, metrics);
+    }
+}
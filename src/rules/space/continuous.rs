@@ -0,0 +1,690 @@
+//! Space rules for continuous arenas.
+
+use super::{can_replace, until_outside};
+use crate::battle::BattleRules;
+use crate::entity::{Entity, EntityId, RemoveEntity};
+use crate::error::{WeaselError, WeaselResult};
+use crate::event::{EventQueue, EventTrigger};
+use crate::metric::WriteMetrics;
+use crate::space::{Area, Distance, PositionClaim, SpaceRules, SpatialQueries};
+use indexmap::IndexMap;
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt::{Debug, Formatter, Result};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A fixed-point number with 16 fractional bits.
+///
+/// Continuous space rules use fixed-point arithmetic instead of floating point numbers,
+/// so that the same moves produce the same results on every platform.\
+/// Arithmetic operators saturate at `Fixed::MIN` and `Fixed::MAX` instead of overflowing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Fixed(i64);
+
+impl Fixed {
+    /// Number of bits reserved to the fractional part.
+    pub const FRACTIONAL_BITS: u32 = 16;
+
+    /// The number zero.
+    pub const ZERO: Fixed = Fixed(0);
+
+    /// The number one.
+    pub const ONE: Fixed = Fixed(1 << Self::FRACTIONAL_BITS);
+
+    /// The smallest representable number.
+    pub const MIN: Fixed = Fixed(i64::MIN);
+
+    /// The largest representable number.
+    pub const MAX: Fixed = Fixed(i64::MAX);
+
+    /// Creates a fixed-point number from an integer.
+    pub fn from_int(value: i32) -> Self {
+        Fixed(i64::from(value) << Self::FRACTIONAL_BITS)
+    }
+
+    /// Creates a fixed-point number equal to `numerator / denominator`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero.
+    pub fn from_ratio(numerator: i32, denominator: i32) -> Self {
+        Self::from_int(numerator) / Self::from_int(denominator)
+    }
+
+    /// Creates a fixed-point number from a wider integer.
+    /// Returns `None` if the value can't be represented.
+    pub fn checked_from_int(value: i64) -> Option<Self> {
+        value.checked_mul(Self::ONE.0).map(Fixed)
+    }
+
+    /// Creates a fixed-point number from its raw representation.
+    pub fn from_raw(raw: i64) -> Self {
+        Fixed(raw)
+    }
+
+    /// Returns the raw representation of this number.
+    pub fn raw(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the largest integer less than or equal to this number.
+    pub fn floor(self) -> i64 {
+        self.0 >> Self::FRACTIONAL_BITS
+    }
+
+    /// Converts this number into a floating point one.\
+    /// Meant for presentation only, results of the rules never depend on it.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::ONE.0 as f64
+    }
+
+    /// Returns the absolute value of this number.
+    pub fn abs(self) -> Self {
+        Fixed(self.0.saturating_abs())
+    }
+
+    /// Returns the sum of this number and `other`, or `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Fixed)
+    }
+
+    /// Returns the difference of this number and `other`, or `None` on overflow.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Fixed)
+    }
+
+    /// Returns the product of this number and `other`, or `None` on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let product = (i128::from(self.0) * i128::from(other.0)) >> Self::FRACTIONAL_BITS;
+        i64::try_from(product).ok().map(Fixed)
+    }
+
+    /// Returns the quotient of this number and `other`,
+    /// or `None` on overflow or if `other` is zero.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if other.0 == 0 {
+            return None;
+        }
+        let quotient = (i128::from(self.0) << Self::FRACTIONAL_BITS) / i128::from(other.0);
+        i64::try_from(quotient).ok().map(Fixed)
+    }
+
+    /// Converts a wide intermediate result back into a fixed-point number, saturating.
+    fn saturate(value: i128) -> Self {
+        Fixed(value.clamp(i128::from(i64::MIN), i128::from(i64::MAX)) as i64)
+    }
+
+    /// Returns the square root of this number, rounded down. Negative numbers yield zero.
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+        // Integer square root of the raw value scaled up, to keep the fractional bits.
+        let value = (self.0 as u128) << Self::FRACTIONAL_BITS;
+        let mut root = 0u128;
+        let mut bit = 1u128 << 126;
+        while bit > value {
+            bit >>= 2;
+        }
+        let mut rest = value;
+        while bit != 0 {
+            if rest >= root + bit {
+                rest -= root + bit;
+                root = (root >> 1) + bit;
+            } else {
+                root >>= 1;
+            }
+            bit >>= 2;
+        }
+        Fixed(root as i64)
+    }
+}
+
+impl From<i32> for Fixed {
+    fn from(value: i32) -> Self {
+        Self::from_int(value)
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Fixed(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Fixed(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Fixed(self.0.saturating_neg())
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::saturate((i128::from(self.0) * i128::from(other.0)) >> Self::FRACTIONAL_BITS)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    fn div(self, other: Self) -> Self {
+        Self::saturate((i128::from(self.0) << Self::FRACTIONAL_BITS) / i128::from(other.0))
+    }
+}
+
+/// A point in a continuous two dimensional space. It can also be used as a vector.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Point {
+    x: Fixed,
+    y: Fixed,
+}
+
+impl Point {
+    /// Creates a new point.
+    pub fn new<X: Into<Fixed>, Y: Into<Fixed>>(x: X, y: Y) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+
+    /// Returns the x coordinate of this point.
+    pub fn x(&self) -> Fixed {
+        self.x
+    }
+
+    /// Returns the y coordinate of this point.
+    pub fn y(&self) -> Fixed {
+        self.y
+    }
+
+    /// Returns the dot product of this vector and `other`.
+    pub fn dot(&self, other: &Point) -> Fixed {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns the length of this vector.
+    pub fn length(&self) -> Fixed {
+        self.dot(self).sqrt()
+    }
+
+    /// Returns the distance between this point and `other`.
+    pub fn distance(&self, other: &Point) -> Fixed {
+        (*other - *self).length()
+    }
+
+    /// Returns this vector multiplied by `factor`.
+    pub fn scale(&self, factor: Fixed) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+
+    /// Returns the distance between this point and the segment going from `from` to `to`.
+    pub fn segment_distance(&self, from: &Point, to: &Point) -> Fixed {
+        let segment = *to - *from;
+        let squared_length = segment.dot(&segment);
+        if squared_length == Fixed::ZERO {
+            return self.distance(from);
+        }
+        // Project this point on the segment.
+        let t = ((*self - *from).dot(&segment) / squared_length)
+            .max(Fixed::ZERO)
+            .min(Fixed::ONE);
+        self.distance(&(*from + segment.scale(t)))
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// The position of an entity in a continuous space: a circular collider.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Body {
+    center: Point,
+    radius: Fixed,
+}
+
+impl Body {
+    /// Creates a new body.
+    pub fn new<T: Into<Fixed>>(center: Point, radius: T) -> Self {
+        Self {
+            center,
+            radius: radius.into(),
+        }
+    }
+
+    /// Creates a new body, as long as its radius is not negative.
+    pub fn try_new<T: Into<Fixed>>(center: Point, radius: T) -> Option<Self> {
+        let radius = radius.into();
+        if radius >= Fixed::ZERO {
+            Some(Self { center, radius })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the center of this body.
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    /// Returns the radius of this body.
+    pub fn radius(&self) -> Fixed {
+        self.radius
+    }
+
+    /// Returns this body moved to `center`.
+    pub fn with_center(mut self, center: Point) -> Self {
+        self.center = center;
+        self
+    }
+
+    /// Returns the distance between the edges of this body and `other`,
+    /// or zero if they overlap.
+    pub fn gap(&self, other: &Body) -> Fixed {
+        (self.center.distance(&other.center) - self.radius - other.radius).max(Fixed::ZERO)
+    }
+
+    /// Returns true if this body and `other` overlap. Bodies that are only touching don't.
+    pub fn overlaps(&self, other: &Body) -> bool {
+        let reach = self.radius + other.radius;
+        let offset = other.center - self.center;
+        offset.dot(&offset) < reach * reach
+    }
+
+    /// Returns true if this body, moving along the segment from `from` to `to`,
+    /// would overlap with `other`.
+    pub fn sweep_overlaps(&self, from: &Point, to: &Point, other: &Body) -> bool {
+        other.center.segment_distance(from, to) < self.radius + other.radius
+    }
+}
+
+impl From<Point> for Body {
+    fn from(center: Point) -> Self {
+        Self::new(center, Fixed::ZERO)
+    }
+}
+
+/// The configuration of a rectangular arena: its size and how far entities can move
+/// in a single move.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct ArenaSeed {
+    width: Fixed,
+    height: Fixed,
+    max_move: Option<Fixed>,
+}
+
+impl ArenaSeed {
+    /// Creates a new arena seed, without limits to the length of movements.
+    pub fn new<W: Into<Fixed>, H: Into<Fixed>>(width: W, height: H) -> Self {
+        Self {
+            width: width.into(),
+            height: height.into(),
+            max_move: None,
+        }
+    }
+
+    /// Limits the distance entities can cover in a single move.
+    pub fn with_max_move<T: Into<Fixed>>(mut self, max_move: T) -> Self {
+        self.max_move = Some(max_move.into());
+        self
+    }
+
+    /// Returns the width of the arena.
+    pub fn width(&self) -> Fixed {
+        self.width
+    }
+
+    /// Returns the height of the arena.
+    pub fn height(&self) -> Fixed {
+        self.height
+    }
+
+    /// Returns the maximum distance entities can cover in a single move, if any.
+    pub fn max_move(&self) -> Option<Fixed> {
+        self.max_move
+    }
+}
+
+/// A rectangular arena, going from the origin to its size, containing circular bodies.
+pub struct Arena<R: BattleRules> {
+    seed: ArenaSeed,
+    bodies: IndexMap<EntityId<R>, Body>,
+}
+
+impl<R: BattleRules> Debug for Arena<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Arena {{ seed: {:?}, bodies: [", self.seed)?;
+        for (id, body) in &self.bodies {
+            write!(f, "{{ id: {:?}, body: {:?} }}, ", id, body)?;
+        }
+        write!(f, "] }}")
+    }
+}
+
+impl<R: BattleRules> Arena<R> {
+    /// Creates a new empty arena.
+    pub fn new(seed: ArenaSeed) -> Self {
+        Self {
+            seed,
+            bodies: IndexMap::new(),
+        }
+    }
+
+    /// Returns the seed of this arena.
+    pub fn seed(&self) -> ArenaSeed {
+        self.seed
+    }
+
+    /// Returns true if `body` is entirely inside the arena.
+    pub fn contains(&self, body: &Body) -> bool {
+        let (center, radius) = (body.center, body.radius);
+        let inside = |value: Fixed, size: Fixed| {
+            // Reject out of range values first, so that the math below can't overflow.
+            value >= Fixed::ZERO
+                && value <= size
+                && value - radius >= Fixed::ZERO
+                && matches!(value.checked_add(radius), Some(edge) if edge <= size)
+        };
+        radius >= Fixed::ZERO
+            && inside(center.x, self.seed.width)
+            && inside(center.y, self.seed.height)
+    }
+
+    /// Returns the body of the entity with the given id.
+    pub fn body(&self, id: &EntityId<R>) -> Option<&Body> {
+        self.bodies.get(id)
+    }
+
+    /// Returns an iterator over all bodies in the arena, together with their entity.
+    pub fn bodies(&self) -> impl Iterator<Item = (&EntityId<R>, &Body)> {
+        self.bodies.iter()
+    }
+
+    /// Returns an iterator over the entities whose body overlaps with `body`.
+    pub fn colliding<'a>(&'a self, body: &'a Body) -> impl Iterator<Item = &'a EntityId<R>> {
+        self.bodies
+            .iter()
+            .filter(move |(_, other)| body.overlaps(other))
+            .map(|(id, _)| id)
+    }
+
+    /// Returns an iterator over the entities whose body is at most `radius` away from `center`.
+    pub fn within<'a>(
+        &'a self,
+        center: &'a Point,
+        radius: Fixed,
+    ) -> impl Iterator<Item = &'a EntityId<R>> {
+        self.bodies
+            .iter()
+            .filter(move |(_, body)| body.center.distance(center) - body.radius <= radius)
+            .map(|(id, _)| id)
+    }
+
+    /// Returns an iterator over the entities hit by `body` moving from its center to `to`.
+    pub fn obstacles<'a>(
+        &'a self,
+        body: &'a Body,
+        to: &'a Point,
+    ) -> impl Iterator<Item = &'a EntityId<R>> {
+        self.bodies
+            .iter()
+            .filter(move |(_, other)| body.sweep_overlaps(&body.center, to, other))
+            .map(|(id, _)| id)
+    }
+
+    fn insert(&mut self, body: &Body, id: EntityId<R>) {
+        self.bodies.insert(id, *body);
+    }
+
+    fn free(&mut self, id: &EntityId<R>) {
+        self.bodies.shift_remove(id);
+    }
+}
+
+/// Space rules for a continuous rectangular arena, in which entities are circular bodies.
+///
+/// Positions use fixed-point arithmetic, to stay deterministic across platforms.\
+/// Bodies can't overlap with each other and they must be entirely inside the arena,
+/// whose size is defined by the space seed. Bodies can touch each other.\
+/// Radiuses can't be negative and an entity's radius can't change once it's been placed.\
+/// Entities moving on their own travel in a straight line and they can't go through
+/// other bodies, nor farther than the seed's maximum distance per move.
+/// Forced movements advance one unit at each step, in the direction given as a vector.\
+/// When the arena is resized with `ResetSpace`, entities are pushed inside it if possible.
+/// Otherwise they are removed from the battle.\
+/// These rules don't define paths: use `MoveEntity` to move entities.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ContinuousSpaceRules {}
+
+impl<R> SpaceRules<R> for ContinuousSpaceRules
+where
+    R: BattleRules<SR = Self> + 'static,
+{
+    type Position = Body;
+    type SpaceSeed = ArenaSeed;
+    type SpaceAlteration = ();
+    type Direction = Point;
+    type SpaceModel = Arena<R>;
+
+    fn generate_model(&self, seed: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
+        Arena::new(seed.unwrap_or_default())
+    }
+
+    fn check_move(
+        &self,
+        model: &Self::SpaceModel,
+        claim: PositionClaim<R>,
+        position: &Self::Position,
+    ) -> WeaselResult<(), R> {
+        // Entities can't resize their collider, nor have a negative one.
+        let radius_changed = matches!(
+            claim.position(),
+            Some(current) if current.radius != position.radius
+        );
+        if radius_changed || position.radius < Fixed::ZERO {
+            return Err(WeaselError::PositionUnsupported(*position));
+        }
+        if !model.contains(position) {
+            return Err(WeaselError::PositionOutOfBounds(*position));
+        }
+        let start = match claim {
+            PositionClaim::Movement(entity) => {
+                let start = entity.position().center;
+                if let Some(max_move) = model.seed.max_move {
+                    if start.distance(&position.center) > max_move {
+                        return Err(WeaselError::PositionTooFar(*position));
+                    }
+                }
+                start
+            }
+            PositionClaim::Displacement(entity) => entity.position().center,
+            _ => position.center,
+        };
+        // Check for collisions along the whole movement.
+        let body = position.with_center(start);
+        let obstacle = model
+            .obstacles(&body, &position.center)
+            .find(|id| !can_replace(&claim, id))
+            .cloned();
+        match obstacle {
+            Some(id) => Err(WeaselError::PositionOccupied(*position, id)),
+            None => Ok(()),
+        }
+    }
+
+    fn move_entity(
+        &self,
+        model: &mut Self::SpaceModel,
+        claim: PositionClaim<R>,
+        position: Option<&Self::Position>,
+        _event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        // Free the current position.
+        if let Some(entity) = claim.entity() {
+            model.free(entity.entity_id());
+        }
+        // Take the new one.
+        if let Some(position) = position {
+            model.insert(position, claim.entity_id().clone());
+        }
+    }
+
+    /// Entities move one unit at each step, along `direction`.
+    fn displacement_path(
+        &self,
+        model: &Self::SpaceModel,
+        entity: &dyn Entity<R>,
+        direction: &Self::Direction,
+        distance: Distance,
+    ) -> Vec<Self::Position> {
+        let length = direction.length();
+        if length == Fixed::ZERO {
+            return Vec::new();
+        }
+        let body = entity.position();
+        let path = (1..=distance).map(|step| {
+            let offset = direction.scale(fixed_distance(step) / length);
+            body.with_center(body.center + offset)
+        });
+        until_outside(path, |body| model.contains(body))
+    }
+
+    fn translate_entity(
+        &self,
+        model: &Self::SpaceModel,
+        new_model: &mut Self::SpaceModel,
+        entity: &mut dyn Entity<R>,
+        event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        // Push the body inside the new arena.
+        let body = *entity.position();
+        let seed = new_model.seed;
+        let clamp = |value: Fixed, limit: Fixed| value.min(limit - body.radius).max(body.radius);
+        let body = body.with_center(Point::new(
+            clamp(body.center.x, seed.width),
+            clamp(body.center.y, seed.height),
+        ));
+        let id = entity.entity_id();
+        // Don't collide with entities that are already placed, or that are going to stay.
+        let fits = new_model.contains(&body)
+            && new_model.colliding(&body).next().is_none()
+            && (body == *entity.position() || model.colliding(&body).all(|other| other == id));
+        if fits {
+            new_model.insert(&body, id.clone());
+            entity.set_position(body);
+        } else {
+            // There's no room left for this entity.
+            RemoveEntity::trigger(event_queue, id.clone()).fire();
+        }
+    }
+}
+
+/// Converts a distance into a fixed-point number, saturating if it doesn't fit.
+fn fixed_distance(distance: Distance) -> Fixed {
+    Fixed::checked_from_int(i64::from(distance)).unwrap_or(Fixed::MAX)
+}
+
+impl<R> SpatialQueries<R> for ContinuousSpaceRules
+where
+    R: BattleRules<SR = Self> + 'static,
+{
+    /// The distance between two bodies is measured between their edges, rounded down.
+    fn distance(
+        &self,
+        _model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> Distance {
+        from.gap(to).floor() as Distance
+    }
+
+    /// A body is in an area if it touches it, except for cones which must contain
+    /// the body's center. Cones span ninety degrees.
+    fn in_area(
+        &self,
+        _model: &Self::SpaceModel,
+        area: &Area<Self::Position>,
+        position: &Self::Position,
+    ) -> bool {
+        let center = position.center;
+        match area {
+            Area::Circle(origin, radius) => origin.gap(position) <= fixed_distance(*radius),
+            Area::Cone(origin, target, length) => {
+                let axis = target.center - origin.center;
+                let offset = center - origin.center;
+                // The cosine of the angle between the two vectors must be at least 1/√2.
+                offset.length() <= fixed_distance(*length)
+                    && axis.dot(&offset) * Fixed::from_int(2).sqrt()
+                        >= axis.length() * offset.length()
+            }
+            Area::Line(from, to) => {
+                center.segment_distance(&from.center, &to.center) <= position.radius
+            }
+            Area::Rectangle(corner, opposite) => {
+                let (corner, opposite) = (corner.center, opposite.center);
+                let closest = Point::new(
+                    center
+                        .x
+                        .max(corner.x.min(opposite.x))
+                        .min(corner.x.max(opposite.x)),
+                    center
+                        .y
+                        .max(corner.y.min(opposite.y))
+                        .min(corner.y.max(opposite.y)),
+                );
+                center.distance(&closest) <= position.radius
+            }
+        }
+    }
+
+    /// Line of sight is blocked by the bodies crossed by the segment between the two centers.
+    fn line_of_sight(
+        &self,
+        model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> bool {
+        let ray = Body::from(from.center);
+        model
+            .bodies()
+            .filter(|(_, body)| *body != from && *body != to)
+            .all(|(_, body)| !ray.sweep_overlaps(&from.center, &to.center, body))
+    }
+}
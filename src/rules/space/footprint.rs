@@ -0,0 +1,413 @@
+//! Space rules for square grids with entities spanning multiple squares.
+
+use super::{can_replace, square_in_area, until_outside, Coordinate, GridSize, Square, SquareGrid};
+use crate::battle::BattleRules;
+use crate::entity::{Entity, RemoveEntity};
+use crate::error::{WeaselError, WeaselResult};
+use crate::event::{EventQueue, EventTrigger};
+use crate::metric::WriteMetrics;
+use crate::space::{Area, Distance, PathCost, PositionClaim, SpaceRules, SpatialQueries};
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serialization")]
+use std::convert::TryFrom;
+use std::fmt::Debug;
+
+/// The shape of an entity, expressed as the offsets of the squares it covers
+/// relative to its anchor square.
+///
+/// The anchor square, with offset `(0, 0)`, is always covered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialization", serde(try_from = "FootprintData"))]
+pub struct Footprint {
+    offsets: Vec<Square>,
+}
+
+/// Unchecked representation of a deserialized `Footprint`.
+#[cfg(feature = "serialization")]
+#[derive(Deserialize)]
+struct FootprintData {
+    offsets: Vec<Square>,
+}
+
+#[cfg(feature = "serialization")]
+impl TryFrom<FootprintData> for Footprint {
+    type Error = String;
+
+    fn try_from(data: FootprintData) -> std::result::Result<Self, Self::Error> {
+        // Footprints coming from the outside must cover their anchor.
+        if data.offsets.contains(&Square::default()) {
+            Ok(Self::new(data.offsets))
+        } else {
+            Err("footprint doesn't cover its anchor".to_string())
+        }
+    }
+}
+
+impl Footprint {
+    /// Creates a new footprint covering the given offsets and the anchor square.
+    pub fn new(offsets: Vec<Square>) -> Self {
+        let mut footprint = Self {
+            offsets: vec![Square::default()],
+        };
+        for offset in offsets {
+            if !footprint.offsets.contains(&offset) {
+                footprint.offsets.push(offset);
+            }
+        }
+        footprint
+    }
+
+    /// Creates a footprint covering a single square.
+    pub fn single() -> Self {
+        Self::new(Vec::new())
+    }
+
+    /// Creates a rectangular footprint, extending right and down from the anchor.\
+    /// Sizes of zero are treated as one.
+    pub fn rectangle(width: u32, height: u32) -> Self {
+        let offsets = (0..height.max(1) as Coordinate)
+            .flat_map(|y| (0..width.max(1) as Coordinate).map(move |x| Square::new(x, y)))
+            .collect();
+        Self::new(offsets)
+    }
+
+    /// Returns the offsets of all covered squares, the anchor's included.
+    pub fn offsets(&self) -> &[Square] {
+        &self.offsets
+    }
+}
+
+impl Default for Footprint {
+    fn default() -> Self {
+        Self::single()
+    }
+}
+
+/// The direction an entity is facing.
+///
+/// Footprints are defined for entities facing north. Any other orientation
+/// rotates them clockwise around the anchor, in steps of 90 degrees.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum Orientation {
+    /// No rotation.
+    North,
+    /// Rotated by 90 degrees.
+    East,
+    /// Rotated by 180 degrees.
+    South,
+    /// Rotated by 270 degrees.
+    West,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for Orientation {
+    fn default() -> Self {
+        Orientation::North
+    }
+}
+
+impl Orientation {
+    /// Rotates `offset` from the north orientation to this one.
+    pub fn rotate(self, offset: &Square) -> Square {
+        let (x, y) = (offset.x, offset.y);
+        match self {
+            Orientation::North => Square::new(x, y),
+            Orientation::East => Square::new(-y, x),
+            Orientation::South => Square::new(-x, -y),
+            Orientation::West => Square::new(y, -x),
+        }
+    }
+}
+
+/// The position of an entity with a footprint: an anchor square, a shape and an orientation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Placement {
+    anchor: Square,
+    footprint: Footprint,
+    orientation: Orientation,
+}
+
+impl Placement {
+    /// Creates a new placement facing north.
+    pub fn new(anchor: Square, footprint: Footprint) -> Self {
+        Self {
+            anchor,
+            footprint,
+            orientation: Orientation::North,
+        }
+    }
+
+    /// Returns this placement facing `orientation`.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Returns this placement moved to `anchor`, with the same footprint and orientation.
+    pub fn with_anchor(mut self, anchor: Square) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Returns the anchor square.
+    pub fn anchor(&self) -> Square {
+        self.anchor
+    }
+
+    /// Returns the footprint.
+    pub fn footprint(&self) -> &Footprint {
+        &self.footprint
+    }
+
+    /// Returns the orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Returns all squares covered by this placement.
+    pub fn cells(&self) -> Vec<Square> {
+        self.footprint
+            .offsets
+            .iter()
+            .map(|offset| {
+                let offset = self.orientation.rotate(offset);
+                Square::new(self.anchor.x + offset.x, self.anchor.y + offset.y)
+            })
+            .collect()
+    }
+
+    /// Returns the minimum number of steps between any square of this placement
+    /// and any square of `other`.
+    pub fn distance(&self, other: &Placement) -> u32 {
+        let cells = other.cells();
+        self.cells()
+            .iter()
+            .flat_map(|a| cells.iter().map(move |b| a.distance(b)))
+            .min()
+            .unwrap_or_default()
+    }
+}
+
+impl From<Square> for Placement {
+    fn from(anchor: Square) -> Self {
+        Self::new(anchor, Footprint::single())
+    }
+}
+
+/// Space rules for a rectangular grid of squares, in which entities can cover
+/// more than one square.
+///
+/// Positions are placements made of an anchor, a footprint and an orientation.
+/// Each square can be occupied by at most one entity. Entities can rotate while moving,
+/// but they can't change their footprint.\
+/// The grid behaves like in `SquareGridRules`: its size is defined by the space seed and
+/// entities that don't fit after a resize are moved or removed.\
+/// Paths move the anchor to any of its eight neighbors, each step costing one point.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FootprintGridRules {}
+
+impl<R> SpaceRules<R> for FootprintGridRules
+where
+    R: BattleRules<SR = Self> + 'static,
+{
+    type Position = Placement;
+    type SpaceSeed = GridSize;
+    type SpaceAlteration = ();
+    type Direction = Square;
+    type SpaceModel = SquareGrid<R>;
+
+    fn generate_model(&self, seed: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
+        SquareGrid::new(seed.unwrap_or_default())
+    }
+
+    fn check_move(
+        &self,
+        model: &Self::SpaceModel,
+        claim: PositionClaim<R>,
+        position: &Self::Position,
+    ) -> WeaselResult<(), R> {
+        let footprint_changed = matches!(
+            claim.position(),
+            Some(current) if current.footprint != position.footprint
+        );
+        if footprint_changed {
+            return Err(WeaselError::PositionUnsupported(position.clone()));
+        }
+        for cell in position.cells() {
+            if !model.contains(&cell) {
+                return Err(WeaselError::PositionOutOfBounds(position.clone()));
+            }
+            match model.occupant(&cell) {
+                Some(id) if !can_replace(&claim, id) => {
+                    return Err(WeaselError::PositionOccupied(position.clone(), id.clone()));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn move_entity(
+        &self,
+        model: &mut Self::SpaceModel,
+        claim: PositionClaim<R>,
+        position: Option<&Self::Position>,
+        _event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        // Free the current position.
+        if let Some(entity) = claim.entity() {
+            for cell in entity.position().cells() {
+                model.free(&cell, entity.entity_id());
+            }
+        }
+        // Take the new one.
+        if let Some(position) = position {
+            for cell in position.cells() {
+                model.insert(&cell, claim.entity_id().clone());
+            }
+        }
+    }
+
+    fn neighbors(
+        &self,
+        model: &Self::SpaceModel,
+        position: &Self::Position,
+    ) -> Vec<Self::Position> {
+        position
+            .anchor
+            .neighbors()
+            .iter()
+            .map(|anchor| position.clone().with_anchor(*anchor))
+            .filter(|neighbor| neighbor.cells().iter().all(|cell| model.contains(cell)))
+            .collect()
+    }
+
+    fn estimate_cost(
+        &self,
+        _model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> PathCost {
+        from.anchor.distance(&to.anchor)
+    }
+
+    /// The anchor moves by `direction` at each step. Use `Square::direction_to` to push or pull.
+    fn displacement_path(
+        &self,
+        model: &Self::SpaceModel,
+        entity: &dyn Entity<R>,
+        direction: &Self::Direction,
+        distance: Distance,
+    ) -> Vec<Self::Position> {
+        let placement = entity.position();
+        let anchors = until_outside(placement.anchor.walk(direction, distance), |anchor| {
+            model.contains(anchor)
+        });
+        anchors
+            .into_iter()
+            .map(|anchor| placement.clone().with_anchor(anchor))
+            .collect()
+    }
+
+    fn translate_entity(
+        &self,
+        model: &Self::SpaceModel,
+        new_model: &mut Self::SpaceModel,
+        entity: &mut dyn Entity<R>,
+        event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        let id = entity.entity_id().clone();
+        let placement = entity.position().clone();
+        let position = if placement.cells().iter().all(|cell| new_model.is_free(cell)) {
+            Some(placement.clone())
+        } else {
+            // Don't take squares that other entities are going to keep.
+            let available = |cell: &Square| {
+                new_model.is_free(cell)
+                    && (model.occupant(cell).is_none() || model.occupant(cell) == Some(&id))
+            };
+            new_model
+                .closest_free_where(&placement.anchor, |anchor| {
+                    placement
+                        .clone()
+                        .with_anchor(*anchor)
+                        .cells()
+                        .iter()
+                        .all(&available)
+                })
+                .map(|anchor| placement.clone().with_anchor(anchor))
+        };
+        match position {
+            Some(position) => {
+                for cell in position.cells() {
+                    new_model.insert(&cell, id.clone());
+                }
+                entity.set_position(position);
+            }
+            None => {
+                // There's no room left for this entity.
+                RemoveEntity::trigger(event_queue, id).fire();
+            }
+        }
+    }
+}
+
+impl<R> SpatialQueries<R> for FootprintGridRules
+where
+    R: BattleRules<SR = Self> + 'static,
+{
+    fn distance(
+        &self,
+        _model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> Distance {
+        from.distance(to)
+    }
+
+    /// A placement is inside an area if any of its squares is.\
+    /// Circles are measured from all squares of the center placement, while
+    /// the other shapes are defined by the anchors of their placements.
+    fn in_area(
+        &self,
+        _model: &Self::SpaceModel,
+        area: &Area<Self::Position>,
+        position: &Self::Position,
+    ) -> bool {
+        let area = match area {
+            Area::Circle(center, radius) => return center.distance(position) <= *radius,
+            Area::Cone(origin, target, length) => Area::Cone(origin.anchor, target.anchor, *length),
+            Area::Line(from, to) => Area::Line(from.anchor, to.anchor),
+            Area::Rectangle(corner, opposite) => Area::Rectangle(corner.anchor, opposite.anchor),
+        };
+        position
+            .cells()
+            .iter()
+            .any(|cell| square_in_area(&area, cell))
+    }
+
+    /// Line of sight goes from anchor to anchor and it's blocked by entities, other than
+    /// the two at its ends, standing in between.
+    fn line_of_sight(
+        &self,
+        model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> bool {
+        let ends = [model.occupant(&from.anchor), model.occupant(&to.anchor)];
+        let line = from.anchor.line(&to.anchor);
+        line.iter()
+            .skip(1)
+            .take(line.len().saturating_sub(2))
+            .all(|square| match model.occupant(square) {
+                Some(id) => ends.contains(&Some(id)),
+                None => true,
+            })
+    }
+}
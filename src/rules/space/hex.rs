@@ -0,0 +1,411 @@
+//! Space rules for hexagonal grids.
+
+use super::{can_replace, until_outside, Coordinate};
+use crate::battle::BattleRules;
+use crate::entity::{Entity, EntityId, RemoveEntity};
+use crate::error::{WeaselError, WeaselResult};
+use crate::event::{EventQueue, EventTrigger};
+use crate::metric::WriteMetrics;
+use crate::space::{Area, Distance, PathCost, PositionClaim, SpaceRules, SpatialQueries};
+use indexmap::IndexMap;
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt::{Debug, Formatter, Result};
+
+/// Offsets of the six neighbors of a hexagon, in counterclockwise order starting from east.
+const HEX_DIRECTIONS: [(Coordinate, Coordinate); 6] =
+    [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
+/// A hexagonal cell in a grid, identified by its axial coordinates.
+///
+/// The third cube coordinate `s` is implicitly equal to `-q - r`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Hex {
+    q: Coordinate,
+    r: Coordinate,
+}
+
+impl Hex {
+    /// Creates a new hexagon from its axial coordinates.
+    pub fn new(q: Coordinate, r: Coordinate) -> Self {
+        Self { q, r }
+    }
+
+    /// Returns the `q` coordinate of this hexagon.
+    pub fn q(&self) -> Coordinate {
+        self.q
+    }
+
+    /// Returns the `r` coordinate of this hexagon.
+    pub fn r(&self) -> Coordinate {
+        self.r
+    }
+
+    /// Returns the `s` cube coordinate of this hexagon.\
+    /// Saturates if the value doesn't fit in a `Coordinate`.
+    pub fn s(&self) -> Coordinate {
+        self.q.saturating_neg().saturating_sub(self.r)
+    }
+
+    /// Returns the three cube coordinates of this hexagon, in a type wide enough
+    /// to never overflow.
+    fn cube(&self) -> (i64, i64, i64) {
+        let (q, r) = (i64::from(self.q), i64::from(self.r));
+        (q, r, -q - r)
+    }
+
+    /// Returns the neighbor in the given direction.\
+    /// Directions go from 0 to 5 counterclockwise, starting from east.
+    pub fn neighbor(&self, direction: usize) -> Hex {
+        let (dq, dr) = HEX_DIRECTIONS[direction % HEX_DIRECTIONS.len()];
+        Hex::new(self.q.saturating_add(dq), self.r.saturating_add(dr))
+    }
+
+    /// Returns all six neighbors of this hexagon.
+    pub fn neighbors(&self) -> [Hex; 6] {
+        let mut neighbors = [*self; 6];
+        for (direction, neighbor) in neighbors.iter_mut().enumerate() {
+            *neighbor = self.neighbor(direction);
+        }
+        neighbors
+    }
+
+    /// Returns the number of steps needed to reach `other`.
+    /// Saturates at `u32::MAX`.
+    pub fn distance(&self, other: &Hex) -> u32 {
+        let ((q1, r1, s1), (q2, r2, s2)) = (self.cube(), other.cube());
+        let distance = (q1 - q2).abs().max((r1 - r2).abs()).max((s1 - s2).abs());
+        u32::try_from(distance).unwrap_or(u32::MAX)
+    }
+
+    /// Returns all hexagons at exactly `radius` steps from this one.
+    pub fn ring(&self, radius: u32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+        let radius = Coordinate::try_from(radius).unwrap_or(Coordinate::MAX);
+        let (dq, dr) = HEX_DIRECTIONS[4];
+        let mut hex = Hex::new(
+            self.q.saturating_add(dq * radius),
+            self.r.saturating_add(dr * radius),
+        );
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        for direction in 0..HEX_DIRECTIONS.len() {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = hex.neighbor(direction);
+            }
+        }
+        ring
+    }
+
+    /// Returns the hexagons crossed by a straight line going from this hexagon to `other`,
+    /// both included.
+    pub fn line(&self, other: &Hex) -> Vec<Hex> {
+        let steps = self.distance(other);
+        if steps == 0 {
+            return vec![*self];
+        }
+        // Nudge the starting point to consistently break ties on edges.
+        let (q, r) = (self.q as f64 + 1e-6, self.r as f64 + 1e-6);
+        let (dq, dr) = (other.q as f64 + 1e-6 - q, other.r as f64 + 1e-6 - r);
+        (0..=steps)
+            .map(|step| {
+                let t = step as f64 / steps as f64;
+                Hex::round(q + dq * t, r + dr * t)
+            })
+            .collect()
+    }
+
+    /// Rounds fractional axial coordinates to the nearest hexagon.
+    fn round(q: f64, r: f64) -> Hex {
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Hex::new(rq as Coordinate, rr as Coordinate)
+    }
+}
+
+/// A hexagonal map of hexagons centered on the origin, keeping track of which entity
+/// occupies each hexagon.
+///
+/// The map contains all hexagons within `radius` steps from `(0, 0)`.
+pub struct HexGrid<R: BattleRules> {
+    radius: u32,
+    cells: IndexMap<Hex, EntityId<R>>,
+}
+
+impl<R: BattleRules> Debug for HexGrid<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "HexGrid {{ radius: {:?}, occupied: [", self.radius)?;
+        for (hex, id) in self.occupied() {
+            write!(f, "{{ hex: {:?}, id: {:?} }}, ", hex, id)?;
+        }
+        write!(f, "] }}")
+    }
+}
+
+impl<R: BattleRules> HexGrid<R> {
+    /// Creates a new empty grid.
+    pub fn new(radius: u32) -> Self {
+        Self {
+            radius,
+            cells: IndexMap::new(),
+        }
+    }
+
+    /// Returns the radius of this grid.
+    pub fn radius(&self) -> u32 {
+        self.radius
+    }
+
+    /// Returns true if the given hexagon is inside the grid.
+    pub fn contains(&self, hex: &Hex) -> bool {
+        hex.distance(&Hex::default()) <= self.radius
+    }
+
+    /// Returns the id of the entity occupying the given hexagon.
+    pub fn occupant(&self, hex: &Hex) -> Option<&EntityId<R>> {
+        self.cells.get(hex)
+    }
+
+    /// Returns true if the given hexagon is inside the grid and it's not occupied.
+    pub fn is_free(&self, hex: &Hex) -> bool {
+        self.contains(hex) && !self.cells.contains_key(hex)
+    }
+
+    /// Returns an iterator over all occupied hexagons, together with their occupant.
+    pub fn occupied(&self) -> impl Iterator<Item = (Hex, &EntityId<R>)> {
+        self.cells.iter().map(|(hex, id)| (*hex, id))
+    }
+
+    /// Returns the free hexagon closest to `hex`, if any.\
+    /// Ties are broken in favor of the hexagon that comes first in `Hex::ring` order.
+    pub fn closest_free(&self, hex: &Hex) -> Option<Hex> {
+        self.closest_free_where(hex, |_| true)
+    }
+
+    /// Returns the free hexagon closest to `hex` among those satisfying `filter`.
+    fn closest_free_where<F>(&self, hex: &Hex, filter: F) -> Option<Hex>
+    where
+        F: Fn(&Hex) -> bool,
+    {
+        // No hexagon of the grid is farther than this.
+        let max_distance = hex.distance(&Hex::default()) + self.radius;
+        (0..=max_distance)
+            .flat_map(|radius| hex.ring(radius))
+            .find(|candidate| self.is_free(candidate) && filter(candidate))
+    }
+
+    fn insert(&mut self, hex: &Hex, id: EntityId<R>) {
+        if self.contains(hex) {
+            self.cells.insert(*hex, id);
+        }
+    }
+
+    /// Frees a hexagon, only if it's occupied by the given entity.
+    fn free(&mut self, hex: &Hex, id: &EntityId<R>) {
+        if self.cells.get(hex) == Some(id) {
+            self.cells.shift_remove(hex);
+        }
+    }
+}
+
+/// Space rules for a hexagonal map of hexagons, in which each hexagon
+/// can be occupied by at most one entity.
+///
+/// The radius of the map is defined by the space seed. Without a seed the map
+/// is made of the single hexagon at the origin.\
+/// When the map is resized with `ResetSpace`, entities keep their position if possible.
+/// Otherwise they are moved to the closest free hexagon or, if the map is full,
+/// removed from the battle.\
+/// Paths go from one hexagon to any of its six neighbors, each step costing one point.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HexGridRules {}
+
+impl<R> SpaceRules<R> for HexGridRules
+where
+    R: BattleRules<SR = Self> + 'static,
+{
+    type Position = Hex;
+    type SpaceSeed = u32;
+    type SpaceAlteration = ();
+    type Direction = usize;
+    type SpaceModel = HexGrid<R>;
+
+    fn generate_model(&self, seed: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
+        HexGrid::new(seed.unwrap_or_default())
+    }
+
+    fn check_move(
+        &self,
+        model: &Self::SpaceModel,
+        claim: PositionClaim<R>,
+        position: &Self::Position,
+    ) -> WeaselResult<(), R> {
+        if !model.contains(position) {
+            return Err(WeaselError::PositionOutOfBounds(*position));
+        }
+        match model.occupant(position) {
+            Some(id) if !can_replace(&claim, id) => {
+                Err(WeaselError::PositionOccupied(*position, id.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn move_entity(
+        &self,
+        model: &mut Self::SpaceModel,
+        claim: PositionClaim<R>,
+        position: Option<&Self::Position>,
+        _event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        // Free the current position.
+        if let Some(entity) = claim.entity() {
+            model.free(entity.position(), entity.entity_id());
+        }
+        // Take the new one.
+        if let Some(position) = position {
+            model.insert(position, claim.entity_id().clone());
+        }
+    }
+
+    fn neighbors(
+        &self,
+        model: &Self::SpaceModel,
+        position: &Self::Position,
+    ) -> Vec<Self::Position> {
+        position
+            .neighbors()
+            .iter()
+            .filter(|hex| model.contains(hex))
+            .copied()
+            .collect()
+    }
+
+    fn estimate_cost(
+        &self,
+        _model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> PathCost {
+        from.distance(to)
+    }
+
+    /// Directions are the same as in `Hex::neighbor`.
+    fn displacement_path(
+        &self,
+        model: &Self::SpaceModel,
+        entity: &dyn Entity<R>,
+        direction: &Self::Direction,
+        distance: Distance,
+    ) -> Vec<Self::Position> {
+        let mut hex = *entity.position();
+        let path = (0..distance).map(|_| {
+            hex = hex.neighbor(*direction);
+            hex
+        });
+        until_outside(path, |hex| model.contains(hex))
+    }
+
+    fn translate_entity(
+        &self,
+        model: &Self::SpaceModel,
+        new_model: &mut Self::SpaceModel,
+        entity: &mut dyn Entity<R>,
+        event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        let position = if new_model.is_free(entity.position()) {
+            Some(*entity.position())
+        } else {
+            // Don't take hexagons that other entities are going to keep.
+            new_model.closest_free_where(entity.position(), |hex| model.occupant(hex).is_none())
+        };
+        match position {
+            Some(position) => {
+                new_model.insert(&position, entity.entity_id().clone());
+                entity.set_position(position);
+            }
+            None => {
+                // There's no room left for this entity.
+                RemoveEntity::trigger(event_queue, entity.entity_id().clone()).fire();
+            }
+        }
+    }
+}
+
+impl<R> SpatialQueries<R> for HexGridRules
+where
+    R: BattleRules<SR = Self> + 'static,
+{
+    fn distance(
+        &self,
+        _model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> Distance {
+        from.distance(to)
+    }
+
+    /// Cones span a sixth of a turn, centered on the direction from origin to target.
+    /// The origin itself is not part of the cone.\
+    /// Rectangles are parallelograms bounded by the axial coordinates of the two corners.
+    fn in_area(
+        &self,
+        _model: &Self::SpaceModel,
+        area: &Area<Self::Position>,
+        position: &Self::Position,
+    ) -> bool {
+        match area {
+            Area::Circle(center, radius) => center.distance(position) <= *radius,
+            Area::Cone(origin, target, length) => {
+                // Cartesian coordinates, with the vertical axis scaled by the square root of 3.
+                let offset = |hex: &Hex| {
+                    let (q, r, _) = hex.cube();
+                    let (origin_q, origin_r, _) = origin.cube();
+                    let (dq, dr) = (i128::from(q - origin_q), i128::from(r - origin_r));
+                    (2 * dq + dr, dr)
+                };
+                let (vx, vy) = offset(position);
+                let (dx, dy) = offset(target);
+                let dot = vx * dx + 3 * vy * dy;
+                // The angle between the two vectors must be at most 30 degrees.
+                position != origin
+                    && origin.distance(position) <= *length
+                    && dot > 0
+                    && 4 * dot * dot >= 3 * (vx * vx + 3 * vy * vy) * (dx * dx + 3 * dy * dy)
+            }
+            Area::Line(from, to) => from.line(to).contains(position),
+            Area::Rectangle(corner, opposite) => {
+                let between =
+                    |value, a: Coordinate, b: Coordinate| a.min(b) <= value && value <= a.max(b);
+                between(position.q, corner.q, opposite.q)
+                    && between(position.r, corner.r, opposite.r)
+            }
+        }
+    }
+
+    /// Line of sight is blocked by entities standing between the two hexagons.
+    fn line_of_sight(
+        &self,
+        model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> bool {
+        let line = from.line(to);
+        line.iter()
+            .skip(1)
+            .take(line.len().saturating_sub(2))
+            .all(|hex| model.occupant(hex).is_none())
+    }
+}
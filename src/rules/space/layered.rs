@@ -0,0 +1,604 @@
+//! Space rules for square grids with multiple height levels.
+
+use super::{can_replace, square_in_area, until_outside, Coordinate, GridSize, Square};
+use crate::battle::BattleRules;
+use crate::entity::{Entities, Entity, EntityId, RemoveEntity};
+use crate::error::{WeaselError, WeaselResult};
+use crate::event::{EventQueue, EventTrigger};
+use crate::metric::WriteMetrics;
+use crate::round::Rounds;
+use crate::space::{
+    Area, DisplaceEntity, Distance, PathCost, PositionClaim, SpaceRules, SpatialQueries,
+};
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter, Result};
+
+/// Type to represent the height level of a cell in a layered grid.
+pub type Layer = u32;
+
+/// How an entity moves across the layers of a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum Mobility {
+    /// The entity must stand on solid ground.
+    Ground,
+    /// The entity can stay in mid air.
+    Flying,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for Mobility {
+    fn default() -> Self {
+        Mobility::Ground
+    }
+}
+
+/// A position in a layered grid: a square, a layer and the mobility of the entity in it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct LayeredSquare {
+    square: Square,
+    layer: Layer,
+    mobility: Mobility,
+}
+
+impl LayeredSquare {
+    /// Creates a new position for an entity standing on the ground.
+    pub fn ground(square: Square, layer: Layer) -> Self {
+        Self {
+            square,
+            layer,
+            mobility: Mobility::Ground,
+        }
+    }
+
+    /// Creates a new position for a flying entity.
+    pub fn flying(square: Square, layer: Layer) -> Self {
+        Self {
+            square,
+            layer,
+            mobility: Mobility::Flying,
+        }
+    }
+
+    /// Returns the square of this position.
+    pub fn square(&self) -> Square {
+        self.square
+    }
+
+    /// Returns the layer of this position.
+    pub fn layer(&self) -> Layer {
+        self.layer
+    }
+
+    /// Returns the mobility of the entity in this position.
+    pub fn mobility(&self) -> Mobility {
+        self.mobility
+    }
+
+    /// Returns this position moved to `square` and `layer`, with the same mobility.
+    pub fn moved_to(&self, square: Square, layer: Layer) -> Self {
+        Self {
+            square,
+            layer,
+            mobility: self.mobility,
+        }
+    }
+
+    /// Returns the number of steps needed to reach `other`, moving also diagonally
+    /// and across layers.
+    pub fn distance(&self, other: &LayeredSquare) -> u32 {
+        let layers = (i64::from(self.layer) - i64::from(other.layer)).unsigned_abs() as u32;
+        self.square.distance(&other.square).max(layers)
+    }
+
+    /// Returns the cells crossed by a straight line going from this position to `other`,
+    /// both included.
+    pub fn line(&self, other: &LayeredSquare) -> Vec<(Square, Layer)> {
+        let steps = self.distance(other);
+        if steps == 0 {
+            return vec![(self.square, self.layer)];
+        }
+        // Nudge the starting point to consistently break ties on edges.
+        let start = [
+            self.square.x as f64 + 1e-6,
+            self.square.y as f64 + 1e-6,
+            self.layer as f64 + 1e-6,
+        ];
+        let delta = [
+            (other.square.x - self.square.x) as f64 / steps as f64,
+            (other.square.y - self.square.y) as f64 / steps as f64,
+            (other.layer as f64 - self.layer as f64) / steps as f64,
+        ];
+        (0..=steps)
+            .map(|step| {
+                let step = step as f64;
+                let point = |axis: usize| (start[axis] + delta[axis] * step).round();
+                (
+                    Square::new(point(0) as Coordinate, point(1) as Coordinate),
+                    point(2) as Layer,
+                )
+            })
+            .collect()
+    }
+}
+
+/// The size of a layered grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct LayeredGridSize {
+    size: GridSize,
+    layers: Layer,
+}
+
+impl LayeredGridSize {
+    /// Creates a new layered grid size.
+    pub fn new(width: u32, height: u32, layers: Layer) -> Self {
+        Self {
+            size: GridSize::new(width, height),
+            layers,
+        }
+    }
+
+    /// Returns the size of each layer.
+    pub fn size(&self) -> GridSize {
+        self.size
+    }
+
+    /// Returns the number of layers.
+    pub fn layers(&self) -> Layer {
+        self.layers
+    }
+}
+
+/// A change to the terrain of a layered grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum LayerAlteration {
+    /// Makes a cell solid, for instance to raise a cliff or build a bridge.
+    Fill(Square, Layer),
+    /// Makes a cell empty.
+    Clear(Square, Layer),
+}
+
+/// A rectangular grid of squares stacked in layers, keeping track of which cells are solid
+/// and which entity occupies each cell.
+///
+/// The bottom layer stands on solid ground. An entity stands on the ground in a cell if it's
+/// in the bottom layer or if the cell below is solid.
+pub struct LayeredGrid<R: BattleRules> {
+    size: LayeredGridSize,
+    solid: Vec<bool>,
+    cells: Vec<Option<EntityId<R>>>,
+}
+
+impl<R: BattleRules> Debug for LayeredGrid<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "LayeredGrid {{ size: {:?}, occupied: [", self.size)?;
+        for ((square, layer), id) in self.occupied() {
+            write!(
+                f,
+                "{{ square: {:?}, layer: {:?}, id: {:?} }}, ",
+                square, layer, id
+            )?;
+        }
+        write!(f, "] }}")
+    }
+}
+
+impl<R: BattleRules> LayeredGrid<R> {
+    /// Creates a new grid without solid cells nor entities.
+    pub fn new(size: LayeredGridSize) -> Self {
+        let cells =
+            (size.size.width as usize) * (size.size.height as usize) * (size.layers as usize);
+        Self {
+            size,
+            solid: vec![false; cells],
+            cells: vec![None; cells],
+        }
+    }
+
+    /// Returns the size of this grid.
+    pub fn size(&self) -> LayeredGridSize {
+        self.size
+    }
+
+    /// Returns true if the given cell is inside the grid.
+    pub fn contains(&self, square: &Square, layer: Layer) -> bool {
+        self.index(square, layer).is_some()
+    }
+
+    /// Returns true if the given cell is solid.
+    pub fn is_solid(&self, square: &Square, layer: Layer) -> bool {
+        matches!(self.index(square, layer), Some(index) if self.solid[index])
+    }
+
+    /// Returns true if an entity in the given cell would stand on the ground.
+    pub fn is_supported(&self, square: &Square, layer: Layer) -> bool {
+        layer == 0 || self.is_solid(square, layer - 1)
+    }
+
+    /// Returns the id of the entity occupying the given cell.
+    pub fn occupant(&self, square: &Square, layer: Layer) -> Option<&EntityId<R>> {
+        self.index(square, layer)
+            .and_then(|index| self.cells[index].as_ref())
+    }
+
+    /// Returns true if the given cell is inside the grid, not solid and not occupied.
+    pub fn is_free(&self, square: &Square, layer: Layer) -> bool {
+        matches!(
+            self.index(square, layer),
+            Some(index) if !self.solid[index] && self.cells[index].is_none()
+        )
+    }
+
+    /// Returns the layer on which an entity falling from the given cell would land.\
+    /// Returns `None` if the cell is outside of the grid or if it's solid.
+    pub fn landing(&self, square: &Square, layer: Layer) -> Option<Layer> {
+        if !self.contains(square, layer) || self.is_solid(square, layer) {
+            return None;
+        }
+        (0..=layer)
+            .rev()
+            .find(|layer| self.is_supported(square, *layer))
+    }
+
+    /// Returns an iterator over all occupied cells, together with their occupant.
+    pub fn occupied(&self) -> impl Iterator<Item = ((Square, Layer), &EntityId<R>)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, cell)| cell.as_ref().map(|id| (self.cell(index), id)))
+    }
+
+    fn index(&self, square: &Square, layer: Layer) -> Option<usize> {
+        let GridSize { width, height } = self.size.size;
+        if square.x < 0
+            || square.y < 0
+            || square.x as u32 >= width
+            || square.y as u32 >= height
+            || layer >= self.size.layers
+        {
+            None
+        } else {
+            Some(
+                (layer as usize * height as usize + square.y as usize) * width as usize
+                    + square.x as usize,
+            )
+        }
+    }
+
+    fn cell(&self, index: usize) -> (Square, Layer) {
+        let width = self.size.size.width as usize;
+        let height = self.size.size.height as usize;
+        let square = Square::new(
+            (index % width) as Coordinate,
+            (index / width % height) as Coordinate,
+        );
+        (square, (index / (width * height)) as Layer)
+    }
+
+    fn insert(&mut self, position: &LayeredSquare, id: EntityId<R>) {
+        if let Some(index) = self.index(&position.square, position.layer) {
+            self.cells[index] = Some(id);
+        }
+    }
+
+    /// Frees a cell, only if it's occupied by the given entity.
+    fn free(&mut self, position: &LayeredSquare, id: &EntityId<R>) {
+        if let Some(index) = self.index(&position.square, position.layer) {
+            if self.cells[index].as_ref() == Some(id) {
+                self.cells[index] = None;
+            }
+        }
+    }
+
+    /// Returns true if an entity could stand in `position`, ignoring other entities.
+    fn can_hold(&self, position: &LayeredSquare) -> bool {
+        self.contains(&position.square, position.layer)
+            && !self.is_solid(&position.square, position.layer)
+            && (position.mobility == Mobility::Flying
+                || self.is_supported(&position.square, position.layer))
+    }
+}
+
+/// Space rules for a grid of squares stacked in layers, supporting elevation, bridges
+/// and flying entities.
+///
+/// Each cell can be solid or contain at most one entity. Entities on the ground need
+/// a solid cell below them, unless they are in the bottom layer, while flying entities
+/// can occupy any empty cell. Entities can't change their mobility by moving.\
+/// The size of the grid is defined by the space seed and cells are made solid or empty
+/// with `AlterSpace`. Entities on the ground that lose their support fall, while those
+/// caught in a solid cell are removed.\
+/// When moving along a path, entities on the ground can climb up to `max_climb` layers
+/// and drop down up to `max_drop` layers at each step. Climbing costs one additional
+/// point per layer. Flying entities move freely across layers.\
+/// Forced movements never change the layer, except for entities on the ground falling
+/// down when they are pushed off a ledge.
+#[derive(Debug, Clone, Copy)]
+pub struct LayeredGridRules {
+    max_climb: Layer,
+    max_drop: Layer,
+}
+
+impl LayeredGridRules {
+    /// Creates new rules with the given limits for climbing and dropping.
+    pub fn new(max_climb: Layer, max_drop: Layer) -> Self {
+        Self {
+            max_climb,
+            max_drop,
+        }
+    }
+
+    /// Returns how many layers an entity on the ground can climb in one step.
+    pub fn max_climb(&self) -> Layer {
+        self.max_climb
+    }
+
+    /// Returns how many layers an entity on the ground can drop in one step.
+    pub fn max_drop(&self) -> Layer {
+        self.max_drop
+    }
+}
+
+impl Default for LayeredGridRules {
+    fn default() -> Self {
+        Self::new(1, 1)
+    }
+}
+
+impl<R> SpaceRules<R> for LayeredGridRules
+where
+    R: BattleRules<SR = Self> + 'static,
+{
+    type Position = LayeredSquare;
+    type SpaceSeed = LayeredGridSize;
+    type SpaceAlteration = LayerAlteration;
+    type Direction = Square;
+    type SpaceModel = LayeredGrid<R>;
+
+    fn generate_model(&self, seed: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
+        LayeredGrid::new(seed.unwrap_or_default())
+    }
+
+    fn check_move(
+        &self,
+        model: &Self::SpaceModel,
+        claim: PositionClaim<R>,
+        position: &Self::Position,
+    ) -> WeaselResult<(), R> {
+        let (square, layer) = (&position.square, position.layer);
+        if !model.contains(square, layer) {
+            return Err(WeaselError::PositionOutOfBounds(*position));
+        }
+        if model.is_solid(square, layer) {
+            return Err(WeaselError::PositionBlocked(*position));
+        }
+        let mobility_changed = matches!(
+            claim.position(),
+            Some(current) if current.mobility != position.mobility
+        );
+        if mobility_changed || !model.can_hold(position) {
+            return Err(WeaselError::PositionUnsupported(*position));
+        }
+        match model.occupant(square, layer) {
+            Some(id) if !can_replace(&claim, id) => {
+                Err(WeaselError::PositionOccupied(*position, id.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn move_entity(
+        &self,
+        model: &mut Self::SpaceModel,
+        claim: PositionClaim<R>,
+        position: Option<&Self::Position>,
+        _event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        // Free the current position.
+        if let Some(entity) = claim.entity() {
+            model.free(entity.position(), entity.entity_id());
+        }
+        // Take the new one.
+        if let Some(position) = position {
+            model.insert(position, claim.entity_id().clone());
+        }
+    }
+
+    fn neighbors(
+        &self,
+        model: &Self::SpaceModel,
+        position: &Self::Position,
+    ) -> Vec<Self::Position> {
+        let (down, up) = match position.mobility {
+            Mobility::Ground => (self.max_drop, self.max_climb),
+            Mobility::Flying => (1, 1),
+        };
+        let layers = position.layer.saturating_sub(down)..=position.layer.saturating_add(up);
+        position
+            .square
+            .neighbors()
+            .iter()
+            .flat_map(|square| layers.clone().map(move |layer| (*square, layer)))
+            .chain(layers.clone().map(|layer| (position.square, layer)))
+            .map(|(square, layer)| position.moved_to(square, layer))
+            .filter(|neighbor| neighbor != position && model.can_hold(neighbor))
+            .collect()
+    }
+
+    fn step_cost(
+        &self,
+        _model: &Self::SpaceModel,
+        _entity: &dyn Entity<R>,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> Option<PathCost> {
+        match from.mobility {
+            Mobility::Flying => Some(1),
+            Mobility::Ground => {
+                if to.layer > from.layer + self.max_climb || to.layer + self.max_drop < from.layer {
+                    None
+                } else {
+                    Some(1 + to.layer.saturating_sub(from.layer))
+                }
+            }
+        }
+    }
+
+    fn estimate_cost(
+        &self,
+        _model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> PathCost {
+        from.square.distance(&to.square)
+    }
+
+    fn displacement_path(
+        &self,
+        model: &Self::SpaceModel,
+        entity: &dyn Entity<R>,
+        direction: &Self::Direction,
+        distance: Distance,
+    ) -> Vec<Self::Position> {
+        let position = entity.position();
+        let mut layer = position.layer;
+        let path = position.square.walk(direction, distance).map(|square| {
+            if position.mobility == Mobility::Ground {
+                // Fall down from ledges.
+                layer = model.landing(&square, layer).unwrap_or(layer);
+            }
+            position.moved_to(square, layer)
+        });
+        until_outside(path, |position| {
+            model.contains(&position.square, position.layer)
+        })
+    }
+
+    fn translate_entity(
+        &self,
+        model: &Self::SpaceModel,
+        new_model: &mut Self::SpaceModel,
+        entity: &mut dyn Entity<R>,
+        event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        let current = *entity.position();
+        let available = |position: &LayeredSquare| {
+            new_model.can_hold(position)
+                && new_model.is_free(&position.square, position.layer)
+                && model.occupant(&position.square, position.layer).is_none()
+        };
+        let position =
+            if new_model.can_hold(&current) && new_model.is_free(&current.square, current.layer) {
+                Some(current)
+            } else {
+                // Don't take cells that other entities are going to keep.
+                (0..new_model.cells.len())
+                    .map(|index| {
+                        let (square, layer) = new_model.cell(index);
+                        current.moved_to(square, layer)
+                    })
+                    .filter(|position| available(position))
+                    .min_by_key(|position| position.distance(&current))
+            };
+        match position {
+            Some(position) => {
+                new_model.insert(&position, entity.entity_id().clone());
+                entity.set_position(position);
+            }
+            None => {
+                // There's no room left for this entity.
+                RemoveEntity::trigger(event_queue, entity.entity_id().clone()).fire();
+            }
+        }
+    }
+
+    fn alter_space(
+        &self,
+        entities: &Entities<R>,
+        _rounds: &Rounds<R>,
+        model: &mut Self::SpaceModel,
+        alteration: &Self::SpaceAlteration,
+        event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        let (square, layer, solid) = match alteration {
+            LayerAlteration::Fill(square, layer) => (square, *layer, true),
+            LayerAlteration::Clear(square, layer) => (square, *layer, false),
+        };
+        if let Some(index) = model.index(square, layer) {
+            model.solid[index] = solid;
+        }
+        for entity in entities.entities() {
+            let position = entity.position();
+            if position.square != *square {
+                continue;
+            }
+            if model.is_solid(&position.square, position.layer) {
+                // The entity is crushed.
+                RemoveEntity::trigger(event_queue, entity.entity_id().clone()).fire();
+            } else if !model.can_hold(position) {
+                // The entity lost its support and falls.
+                DisplaceEntity::trigger(
+                    event_queue,
+                    entity.entity_id().clone(),
+                    Square::default(),
+                    1,
+                )
+                .fire();
+            }
+        }
+    }
+}
+
+impl<R> SpatialQueries<R> for LayeredGridRules
+where
+    R: BattleRules<SR = Self> + 'static,
+{
+    fn distance(
+        &self,
+        _model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> Distance {
+        from.distance(to)
+    }
+
+    /// Circles take layers into account, while the other shapes cover all layers
+    /// of the squares they contain.
+    fn in_area(
+        &self,
+        _model: &Self::SpaceModel,
+        area: &Area<Self::Position>,
+        position: &Self::Position,
+    ) -> bool {
+        let area = match area {
+            Area::Circle(center, radius) => return center.distance(position) <= *radius,
+            Area::Cone(origin, target, length) => Area::Cone(origin.square, target.square, *length),
+            Area::Line(from, to) => Area::Line(from.square, to.square),
+            Area::Rectangle(corner, opposite) => Area::Rectangle(corner.square, opposite.square),
+        };
+        square_in_area(&area, &position.square)
+    }
+
+    /// Line of sight is blocked by solid cells and by entities between the two positions.
+    fn line_of_sight(
+        &self,
+        model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> bool {
+        let line = from.line(to);
+        line.iter()
+            .skip(1)
+            .take(line.len().saturating_sub(2))
+            .all(|(square, layer)| {
+                !model.is_solid(square, *layer) && model.occupant(square, *layer).is_none()
+            })
+    }
+}
@@ -0,0 +1,46 @@
+//! Predefined rules for space.
+
+use crate::battle::BattleRules;
+use crate::entity::EntityId;
+use crate::space::PositionClaim;
+
+mod continuous;
+mod footprint;
+mod hex;
+mod layered;
+mod square;
+
+pub use self::continuous::*;
+pub use self::footprint::*;
+pub use self::hex::*;
+pub use self::layered::*;
+pub use self::square::*;
+
+/// Type for the coordinates of a cell in a grid.
+pub type Coordinate = i32;
+
+/// Returns true if the entity behind `claim` can take a position held by `occupant`.
+fn can_replace<R: BattleRules>(claim: &PositionClaim<R>, occupant: &EntityId<R>) -> bool {
+    match claim {
+        PositionClaim::Swap(_, other) => occupant == claim.entity_id() || occupant == *other,
+        _ => occupant == claim.entity_id(),
+    }
+}
+
+/// Collects the positions of a forced movement up to the first one outside of the space,
+/// included, so that the entity can still collide with the edge.
+fn until_outside<P, I, F>(path: I, inside: F) -> Vec<P>
+where
+    I: IntoIterator<Item = P>,
+    F: Fn(&P) -> bool,
+{
+    let mut positions = Vec::new();
+    for position in path {
+        let outside = !inside(&position);
+        positions.push(position);
+        if outside {
+            break;
+        }
+    }
+    positions
+}
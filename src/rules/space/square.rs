@@ -0,0 +1,426 @@
+//! Space rules for square grids.
+
+use super::{can_replace, until_outside, Coordinate};
+use crate::battle::BattleRules;
+use crate::entity::{Entity, EntityId, RemoveEntity};
+use crate::error::{WeaselError, WeaselResult};
+use crate::event::{EventQueue, EventTrigger};
+use crate::metric::WriteMetrics;
+use crate::space::{Area, Distance, PathCost, PositionClaim, SpaceRules, SpatialQueries};
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter, Result};
+
+/// A square cell in a grid, identified by its column and row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct Square {
+    pub(crate) x: Coordinate,
+    pub(crate) y: Coordinate,
+}
+
+impl Square {
+    /// Creates a new square.
+    pub fn new(x: Coordinate, y: Coordinate) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the column of this square.
+    pub fn x(&self) -> Coordinate {
+        self.x
+    }
+
+    /// Returns the row of this square.
+    pub fn y(&self) -> Coordinate {
+        self.y
+    }
+
+    /// Returns the number of steps needed to reach `other`, moving also diagonally.
+    pub fn distance(&self, other: &Square) -> u32 {
+        let dx = (self.x - other.x).unsigned_abs();
+        let dy = (self.y - other.y).unsigned_abs();
+        dx.max(dy)
+    }
+
+    /// Returns the direction to go from this square towards `other`, as an offset
+    /// of at most one step on each axis.
+    pub fn direction_to(&self, other: &Square) -> Square {
+        Square::new((other.x - self.x).signum(), (other.y - self.y).signum())
+    }
+
+    /// Returns the squares reached by taking up to `distance` steps from this square,
+    /// moving by `direction` at each step.\
+    /// Coordinates saturate and the walk ends after the first step that doesn't move.
+    pub fn walk(&self, direction: &Square, distance: u32) -> impl Iterator<Item = Square> {
+        let (direction, mut square, mut stuck) = (*direction, *self, false);
+        (0..distance).map_while(move |_| {
+            if stuck {
+                return None;
+            }
+            let next = Square::new(
+                square.x.saturating_add(direction.x),
+                square.y.saturating_add(direction.y),
+            );
+            stuck = next == square;
+            square = next;
+            Some(next)
+        })
+    }
+
+    /// Returns the eight squares surrounding this one.
+    pub fn neighbors(&self) -> [Square; 8] {
+        let mut neighbors = [*self; 8];
+        let offsets = (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|offset| *offset != (0, 0));
+        for (neighbor, (dx, dy)) in neighbors.iter_mut().zip(offsets) {
+            *neighbor = Square::new(self.x + dx, self.y + dy);
+        }
+        neighbors
+    }
+
+    /// Returns the squares crossed by a straight line going from this square to `other`,
+    /// both included.
+    pub fn line(&self, other: &Square) -> Vec<Square> {
+        let steps = self.distance(other);
+        if steps == 0 {
+            return vec![*self];
+        }
+        // Nudge the starting point to consistently break ties on edges.
+        let (x, y) = (self.x as f64 + 1e-6, self.y as f64 + 1e-6);
+        let (dx, dy) = (
+            (other.x - self.x) as f64 / steps as f64,
+            (other.y - self.y) as f64 / steps as f64,
+        );
+        (0..=steps)
+            .map(|step| {
+                let step = step as f64;
+                Square::new(
+                    (x + dx * step).round() as Coordinate,
+                    (y + dy * step).round() as Coordinate,
+                )
+            })
+            .collect()
+    }
+}
+
+/// The size of a grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct GridSize {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+impl GridSize {
+    /// Creates a new grid size.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+/// A rectangular grid of squares, keeping track of which entity occupies each square.
+///
+/// Squares go from `(0, 0)` to `(width - 1, height - 1)`.
+pub struct SquareGrid<R: BattleRules> {
+    size: GridSize,
+    cells: Vec<Option<EntityId<R>>>,
+}
+
+impl<R: BattleRules> Debug for SquareGrid<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "SquareGrid {{ size: {:?}, occupied: [", self.size)?;
+        for (square, id) in self.occupied() {
+            write!(f, "{{ square: {:?}, id: {:?} }}, ", square, id)?;
+        }
+        write!(f, "] }}")
+    }
+}
+
+impl<R: BattleRules> SquareGrid<R> {
+    /// Creates a new empty grid.
+    pub fn new(size: GridSize) -> Self {
+        Self {
+            size,
+            cells: vec![None; (size.width as usize) * (size.height as usize)],
+        }
+    }
+
+    /// Returns the size of this grid.
+    pub fn size(&self) -> GridSize {
+        self.size
+    }
+
+    /// Returns true if the given square is inside the grid.
+    pub fn contains(&self, square: &Square) -> bool {
+        self.index(square).is_some()
+    }
+
+    /// Returns the id of the entity occupying the given square.
+    pub fn occupant(&self, square: &Square) -> Option<&EntityId<R>> {
+        self.index(square)
+            .and_then(|index| self.cells[index].as_ref())
+    }
+
+    /// Returns true if the given square is inside the grid and it's not occupied.
+    pub fn is_free(&self, square: &Square) -> bool {
+        matches!(self.index(square), Some(index) if self.cells[index].is_none())
+    }
+
+    /// Returns an iterator over all occupied squares, together with their occupant.
+    pub fn occupied(&self) -> impl Iterator<Item = (Square, &EntityId<R>)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, cell)| cell.as_ref().map(|id| (self.square(index), id)))
+    }
+
+    /// Returns the free square closest to `square`, if any.\
+    /// Ties are broken in favor of the square that comes first in row-major order.
+    pub fn closest_free(&self, square: &Square) -> Option<Square> {
+        self.closest_free_where(square, |_| true)
+    }
+
+    /// Returns the free square closest to `square` among those satisfying `filter`.
+    pub(crate) fn closest_free_where<F>(&self, square: &Square, filter: F) -> Option<Square>
+    where
+        F: Fn(&Square) -> bool,
+    {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.is_none())
+            .map(|(index, _)| self.square(index))
+            .filter(|free| filter(free))
+            .min_by_key(|free| free.distance(square))
+    }
+
+    fn index(&self, square: &Square) -> Option<usize> {
+        if square.x < 0
+            || square.y < 0
+            || square.x as u32 >= self.size.width
+            || square.y as u32 >= self.size.height
+        {
+            None
+        } else {
+            Some(square.y as usize * self.size.width as usize + square.x as usize)
+        }
+    }
+
+    fn square(&self, index: usize) -> Square {
+        let width = self.size.width as usize;
+        Square::new((index % width) as Coordinate, (index / width) as Coordinate)
+    }
+
+    pub(crate) fn insert(&mut self, square: &Square, id: EntityId<R>) {
+        if let Some(index) = self.index(square) {
+            self.cells[index] = Some(id);
+        }
+    }
+
+    /// Frees a square, only if it's occupied by the given entity.
+    pub(crate) fn free(&mut self, square: &Square, id: &EntityId<R>) {
+        if let Some(index) = self.index(square) {
+            if self.cells[index].as_ref() == Some(id) {
+                self.cells[index] = None;
+            }
+        }
+    }
+}
+
+/// Space rules for a rectangular grid of squares, in which each square
+/// can be occupied by at most one entity.
+///
+/// The size of the grid is defined by the space seed. Without a seed the grid is empty.\
+/// When the grid is resized with `ResetSpace`, entities keep their position if possible.
+/// Otherwise they are moved to the closest free square or, if the grid is full,
+/// removed from the battle.\
+/// Paths go from one square to any of its eight neighbors, each step costing one point.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SquareGridRules {}
+
+impl<R> SpaceRules<R> for SquareGridRules
+where
+    R: BattleRules<SR = Self> + 'static,
+{
+    type Position = Square;
+    type SpaceSeed = GridSize;
+    type SpaceAlteration = ();
+    type Direction = Square;
+    type SpaceModel = SquareGrid<R>;
+
+    fn generate_model(&self, seed: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
+        SquareGrid::new(seed.unwrap_or_default())
+    }
+
+    fn check_move(
+        &self,
+        model: &Self::SpaceModel,
+        claim: PositionClaim<R>,
+        position: &Self::Position,
+    ) -> WeaselResult<(), R> {
+        if !model.contains(position) {
+            return Err(WeaselError::PositionOutOfBounds(*position));
+        }
+        match model.occupant(position) {
+            Some(id) if !can_replace(&claim, id) => {
+                Err(WeaselError::PositionOccupied(*position, id.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn move_entity(
+        &self,
+        model: &mut Self::SpaceModel,
+        claim: PositionClaim<R>,
+        position: Option<&Self::Position>,
+        _event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        // Free the current position.
+        if let Some(entity) = claim.entity() {
+            model.free(entity.position(), entity.entity_id());
+        }
+        // Take the new one.
+        if let Some(position) = position {
+            model.insert(position, claim.entity_id().clone());
+        }
+    }
+
+    fn neighbors(
+        &self,
+        model: &Self::SpaceModel,
+        position: &Self::Position,
+    ) -> Vec<Self::Position> {
+        position
+            .neighbors()
+            .iter()
+            .filter(|square| model.contains(square))
+            .copied()
+            .collect()
+    }
+
+    fn estimate_cost(
+        &self,
+        _model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> PathCost {
+        from.distance(to)
+    }
+
+    /// Entities move by `direction` at each step. Use `Square::direction_to` to push or pull.
+    fn displacement_path(
+        &self,
+        model: &Self::SpaceModel,
+        entity: &dyn Entity<R>,
+        direction: &Self::Direction,
+        distance: Distance,
+    ) -> Vec<Self::Position> {
+        until_outside(entity.position().walk(direction, distance), |square| {
+            model.contains(square)
+        })
+    }
+
+    fn translate_entity(
+        &self,
+        model: &Self::SpaceModel,
+        new_model: &mut Self::SpaceModel,
+        entity: &mut dyn Entity<R>,
+        event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+        let position = if new_model.is_free(entity.position()) {
+            Some(*entity.position())
+        } else {
+            // Don't take squares that other entities are going to keep.
+            new_model
+                .closest_free_where(entity.position(), |square| model.occupant(square).is_none())
+        };
+        match position {
+            Some(position) => {
+                new_model.insert(&position, entity.entity_id().clone());
+                entity.set_position(position);
+            }
+            None => {
+                // There's no room left for this entity.
+                RemoveEntity::trigger(event_queue, entity.entity_id().clone()).fire();
+            }
+        }
+    }
+}
+
+impl<R> SpatialQueries<R> for SquareGridRules
+where
+    R: BattleRules<SR = Self> + 'static,
+{
+    fn distance(
+        &self,
+        _model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> Distance {
+        from.distance(to)
+    }
+
+    /// Cones span a quarter of a turn, centered on the direction from origin to target.
+    /// The origin itself is not part of the cone.
+    fn in_area(
+        &self,
+        _model: &Self::SpaceModel,
+        area: &Area<Self::Position>,
+        position: &Self::Position,
+    ) -> bool {
+        square_in_area(area, position)
+    }
+
+    /// Line of sight is blocked by entities standing between the two squares.
+    fn line_of_sight(
+        &self,
+        model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> bool {
+        let line = from.line(to);
+        line.iter()
+            .skip(1)
+            .take(line.len().saturating_sub(2))
+            .all(|square| model.occupant(square).is_none())
+    }
+}
+
+/// Returns true if `square` is inside `area`.
+pub(crate) fn square_in_area(area: &Area<Square>, square: &Square) -> bool {
+    match area {
+        Area::Circle(center, radius) => center.distance(square) <= *radius,
+        Area::Cone(origin, target, length) => {
+            let offset =
+                |other: &Square| (i64::from(other.x - origin.x), i64::from(other.y - origin.y));
+            let (vx, vy) = offset(square);
+            let (dx, dy) = offset(target);
+            let dot = vx * dx + vy * dy;
+            // The angle between the two vectors must be at most 45 degrees.
+            square != origin
+                && origin.distance(square) <= *length
+                && dot > 0
+                && 2 * dot * dot >= (vx * vx + vy * vy) * (dx * dx + dy * dy)
+        }
+        Area::Line(from, to) => from.line(to).contains(square),
+        Area::Rectangle(corner, opposite) => {
+            let between =
+                |value, a: Coordinate, b: Coordinate| a.min(b) <= value && value <= a.max(b);
+            between(square.x, corner.x, opposite.x) && between(square.y, corner.y, opposite.y)
+        }
+    }
+}
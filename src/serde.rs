@@ -2,20 +2,24 @@
 
 use crate::ability::ActivateAbility;
 use crate::actor::{AlterAbilities, RegenerateAbilities};
-use crate::battle::{BattleRules, EndBattle, Version};
-use crate::character::{AlterStatistics, RegenerateStatistics};
+use crate::battle::{BattleRules, EndBattle, StartBattle, Version};
+use crate::character::{AlterStatistics, RegenerateStatistics, TransmuteCharacter};
 use crate::creature::{ConvertCreature, CreateCreature, RemoveCreature};
 use crate::entropy::ResetEntropy;
 use crate::event::{
-    ClientEventPrototype, DummyEvent, Event, EventId, EventKind, EventWrapper,
-    VersionedEventWrapper,
+    ClientEventPrototype, DummyEvent, Event, EventCondition, EventId, EventKind, EventMetadata,
+    EventWrapper, VersionedEventWrapper,
 };
 use crate::fight::ApplyImpact;
 use crate::object::{CreateObject, RemoveObject};
 use crate::player::PlayerId;
 use crate::power::InvokePower;
-use crate::round::{EndRound, EndTurn, EnvironmentTurn, ResetRounds, StartTurn};
-use crate::space::{AlterSpace, MoveEntity, ResetSpace};
+use crate::round::{
+    AdvancePhase, DelayTurn, EndRound, EndTurn, EnvironmentTurn, ResetRounds, StartTurn,
+};
+use crate::space::{
+    AlterSpace, DisplaceEntity, MoveAlongPath, MoveEntity, ResetSpace, SwapEntities,
+};
 use crate::status::{AlterStatuses, ClearStatus, InflictStatus};
 use crate::team::{
     AlterPowers, ConcludeObjectives, CreateTeam, RegeneratePowers, RemoveTeam, ResetObjectives,
@@ -111,10 +115,15 @@ flat_event! {
     CreateCreature, "CreateCreature<R>: Serialize", "CreateCreature<R>: Deserialize<'de>",
     CreateObject, "CreateObject<R>: Serialize", "CreateObject<R>: Deserialize<'de>",
     MoveEntity, "MoveEntity<R>: Serialize", "MoveEntity<R>: Deserialize<'de>",
+    MoveAlongPath, "MoveAlongPath<R>: Serialize", "MoveAlongPath<R>: Deserialize<'de>",
+    DisplaceEntity, "DisplaceEntity<R>: Serialize", "DisplaceEntity<R>: Deserialize<'de>",
+    SwapEntities, "SwapEntities<R>: Serialize", "SwapEntities<R>: Deserialize<'de>",
     StartTurn, "StartTurn<R>: Serialize", "StartTurn<R>: Deserialize<'de>",
     EndTurn, "EndTurn<R>: Serialize", "EndTurn<R>: Deserialize<'de>",
     EndRound, "EndRound<R>: Serialize", "EndRound<R>: Deserialize<'de>",
     EnvironmentTurn, "EnvironmentTurn<R>: Serialize", "EnvironmentTurn<R>: Deserialize<'de>",
+    AdvancePhase, "AdvancePhase<R>: Serialize", "AdvancePhase<R>: Deserialize<'de>",
+    DelayTurn, "DelayTurn<R>: Serialize", "DelayTurn<R>: Deserialize<'de>",
     ActivateAbility, "ActivateAbility<R>: Serialize", "ActivateAbility<R>: Deserialize<'de>",
     InvokePower, "InvokePower<R>: Serialize", "InvokePower<R>: Deserialize<'de>",
     ApplyImpact, "ApplyImpact<R>: Serialize", "ApplyImpact<R>: Deserialize<'de>",
@@ -123,6 +132,7 @@ flat_event! {
     AlterAbilities, "AlterAbilities<R>: Serialize", "AlterAbilities<R>: Deserialize<'de>",
     AlterPowers, "AlterPowers<R>: Serialize", "AlterPowers<R>: Deserialize<'de>",
     RegenerateStatistics, "RegenerateStatistics<R>: Serialize", "RegenerateStatistics<R>: Deserialize<'de>",
+    TransmuteCharacter, "TransmuteCharacter<R>: Serialize", "TransmuteCharacter<R>: Deserialize<'de>",
     RegenerateAbilities, "RegenerateAbilities<R>: Serialize", "RegenerateAbilities<R>: Deserialize<'de>",
     RegeneratePowers, "RegeneratePowers<R>: Serialize", "RegeneratePowers<R>: Deserialize<'de>",
     InflictStatus, "InflictStatus<R>: Serialize", "InflictStatus<R>: Deserialize<'de>",
@@ -138,6 +148,7 @@ flat_event! {
     ResetObjectives, "ResetObjectives<R>: Serialize", "ResetObjectives<R>: Deserialize<'de>",
     ResetRounds, "ResetRounds<R>: Serialize", "ResetRounds<R>: Deserialize<'de>",
     ResetSpace, "ResetSpace<R>: Serialize", "ResetSpace<R>: Deserialize<'de>",
+    StartBattle, "StartBattle<R>: Serialize", "StartBattle<R>: Deserialize<'de>",
     EndBattle, "EndBattle<R>: Serialize", "EndBattle<R>: Deserialize<'de>",
 }
 
@@ -159,6 +170,8 @@ pub struct FlatVersionedEvent<R: BattleRules> {
         deserialize = "Version<R>: Deserialize<'de>"
     ))]
     version: Version<R>,
+
+    metadata: EventMetadata,
 }
 
 impl<R: BattleRules> FlatVersionedEvent<R> {
@@ -181,6 +194,11 @@ impl<R: BattleRules> FlatVersionedEvent<R> {
     pub fn version(&self) -> &Version<R> {
         &self.version
     }
+
+    /// Returns the metadata of this event.
+    pub fn metadata(&self) -> &EventMetadata {
+        &self.metadata
+    }
 }
 
 impl<R: BattleRules + 'static> From<VersionedEventWrapper<R>> for FlatVersionedEvent<R> {
@@ -190,6 +208,7 @@ impl<R: BattleRules + 'static> From<VersionedEventWrapper<R>> for FlatVersionedE
             origin: event.wrapper().origin(),
             event: FlatEvent::flattened(event.wrapper.event),
             version: event.version,
+            metadata: event.wrapper.metadata,
         }
     }
 }
@@ -197,7 +216,7 @@ impl<R: BattleRules + 'static> From<VersionedEventWrapper<R>> for FlatVersionedE
 impl<R: BattleRules + 'static> From<FlatVersionedEvent<R>> for VersionedEventWrapper<R> {
     fn from(event: FlatVersionedEvent<R>) -> Self {
         Self::new(
-            EventWrapper::new(event.id, event.origin, event.event.boxed()),
+            EventWrapper::new(event.id, event.origin, event.event.boxed(), event.metadata),
             event.version,
         )
     }
@@ -222,6 +241,14 @@ pub struct FlatClientEvent<R: BattleRules> {
     version: Version<R>,
 
     player: Option<PlayerId>,
+
+    #[serde(bound(
+        serialize = "EventCondition<R>: Serialize",
+        deserialize = "EventCondition<R>: Deserialize<'de>"
+    ))]
+    condition: Option<EventCondition<R>>,
+
+    tags: Vec<String>,
 }
 
 impl<R: BattleRules> FlatClientEvent<R> {
@@ -244,6 +271,16 @@ impl<R: BattleRules> FlatClientEvent<R> {
     pub fn player(&self) -> Option<PlayerId> {
         self.player
     }
+
+    /// Returns the condition that must be satisfied for this event to be valid.
+    pub fn condition(&self) -> &Option<EventCondition<R>> {
+        &self.condition
+    }
+
+    /// Returns the user defined tags of this event.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
 }
 
 impl<R: BattleRules + 'static> From<ClientEventPrototype<R>> for FlatClientEvent<R> {
@@ -254,6 +291,8 @@ impl<R: BattleRules + 'static> From<ClientEventPrototype<R>> for FlatClientEvent
             event: FlatEvent::flattened(event.event),
             version: event.version,
             player,
+            condition: event.condition,
+            tags: event.tags,
         }
     }
 }
@@ -265,6 +304,8 @@ impl<R: BattleRules + 'static> From<FlatClientEvent<R>> for ClientEventPrototype
             event.event.boxed(),
             event.version,
             event.player,
+            event.condition,
+            event.tags,
         )
     }
 }
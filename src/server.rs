@@ -1,14 +1,24 @@
 //! A battle server.
 
 use crate::battle::{Battle, BattleController, BattleRules, EventCallback};
+use crate::clock::{Clock, Timestamp};
 use crate::error::{WeaselError, WeaselResult};
 use crate::event::{
-    ClientEventPrototype, EventProcessor, EventPrototype, EventQueue, EventReceiver, EventRights,
-    EventServer, EventWrapper, MultiClientSink, MultiClientSinkHandle, MultiClientSinkHandleMut,
-    VersionedEventWrapper,
+    ClientEventPrototype, EventId, EventKind, EventProcessor, EventPrototype, EventQueue,
+    EventReceiver, EventRights, EventServer, EventTrigger, EventWrapper, MultiClientSink,
+    MultiClientSinkHandle, MultiClientSinkHandleMut, VersionedEventWrapper,
 };
 use crate::player::{PlayerId, RightsHandle, RightsHandleMut};
+use crate::round::{EndTurn, RoundsRules, TurnState};
 use crate::team::TeamId;
+use crate::util::Id;
+use indexmap::{IndexMap, IndexSet};
+
+/// Default maximum depth of a cascade of derived events.
+pub const DEFAULT_MAX_CASCADE_DEPTH: usize = 64;
+
+/// Default maximum number of events derived from a single root event.
+pub const DEFAULT_MAX_DERIVED_EVENTS: usize = 4096;
 
 /// The server is the main object used to orchestrate a battle.
 ///
@@ -21,6 +31,19 @@ pub struct Server<R: BattleRules> {
     pub(crate) battle: Battle<R>,
     client_sinks: MultiClientSink<R>,
     authentication: bool,
+    clock: Option<Box<dyn Clock>>,
+    max_cascade_depth: usize,
+    max_derived_events: usize,
+    /// Ids of the events whose derived events are being processed, starting from the root.
+    cascade_chain: Vec<EventId>,
+    /// Number of events derived from the current root event.
+    derived_events: usize,
+    /// Set when the current cascade exceeded its limits.
+    cascade_overflow: bool,
+    planning: Option<Planning<R>>,
+    turn_time_limit: Option<Timestamp>,
+    /// Time at which the current turn expires.
+    turn_deadline: Option<Timestamp>,
 }
 
 impl<R: BattleRules + 'static> Server<R> {
@@ -29,6 +52,10 @@ impl<R: BattleRules + 'static> Server<R> {
         ServerBuilder {
             battle,
             authentication: false,
+            clock: None,
+            max_cascade_depth: DEFAULT_MAX_CASCADE_DEPTH,
+            max_derived_events: DEFAULT_MAX_DERIVED_EVENTS,
+            turn_time_limit: None,
         }
     }
 
@@ -37,6 +64,128 @@ impl<R: BattleRules + 'static> Server<R> {
         self.authentication
     }
 
+    /// Returns the clock used to timestamp events, if any.
+    pub fn clock(&self) -> Option<&dyn Clock> {
+        self.clock.as_deref()
+    }
+
+    /// Sets the clock used to timestamp events.
+    pub fn set_clock(&mut self, clock: Option<Box<dyn Clock>>) {
+        self.clock = clock;
+    }
+
+    /// Returns the current time according to the server's clock.
+    fn now(&self) -> Option<Timestamp> {
+        self.clock.as_ref().map(|clock| clock.now())
+    }
+
+    /// Returns the maximum depth of a cascade of derived events.
+    pub fn max_cascade_depth(&self) -> usize {
+        self.max_cascade_depth
+    }
+
+    /// Returns the maximum number of events that can be derived from a single root event.
+    pub fn max_derived_events(&self) -> usize {
+        self.max_derived_events
+    }
+
+    /// Returns the maximum duration of a turn, in milliseconds.
+    pub fn turn_time_limit(&self) -> Option<Timestamp> {
+        self.turn_time_limit
+    }
+
+    /// Sets the maximum duration of a turn, in milliseconds.\
+    /// The new limit is applied starting from the next turn.
+    pub fn set_turn_time_limit(&mut self, limit: Option<Timestamp>) {
+        self.turn_time_limit = limit;
+    }
+
+    /// Returns the time at which the current turn expires, if any.
+    pub fn turn_deadline(&self) -> Option<Timestamp> {
+        self.turn_deadline
+    }
+
+    /// Enforces all deadlines at time `now`.
+    ///
+    /// An expired planning phase is resolved, as in `expire_planning`.\
+    /// If the current turn exceeded the turn time limit, the server fires the events
+    /// provided by `RoundsRules::on_turn_expired` or, if there are none, an `EndTurn`.
+    /// These events are recorded in the history and sent to client sinks like any other.
+    ///
+    /// The deadline of a turn is computed from the timestamp of its `StartTurn` event.
+    /// Without a clock, the deadline is computed on the first tick after the start of the turn.
+    pub fn tick(&mut self, now: Timestamp) -> WeaselResult<(), R> {
+        let mut errors = Vec::new();
+        if let Err(error) = self.expire_planning(now) {
+            errors.push(error);
+        }
+        if let Err(error) = self.expire_turn(now) {
+            errors.push(error);
+        }
+        match errors.len() {
+            1 => Err(errors.swap_remove(0)),
+            x if x > 1 => Err(WeaselError::MultiError(errors)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Concludes the current turn if its deadline expired at time `now`.
+    fn expire_turn(&mut self, now: Timestamp) -> WeaselResult<(), R> {
+        let actors = match self.battle.rounds().state() {
+            TurnState::Started(actors) => actors.clone(),
+            TurnState::Ready => return Ok(()),
+        };
+        let limit = match self.turn_time_limit {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+        let deadline = *self
+            .turn_deadline
+            .get_or_insert_with(|| now.saturating_add(limit));
+        if deadline > now {
+            return Ok(());
+        }
+        self.turn_deadline = None;
+        // Collect the fallback events from the rules.
+        let mut event_queue = Some(EventQueue::<R>::new());
+        let rounds = self.battle.rounds();
+        rounds.rules().on_turn_expired(
+            self.battle.entities(),
+            rounds.model(),
+            &actors,
+            &mut event_queue,
+        );
+        let mut event_queue = event_queue.unwrap_or_default();
+        if event_queue.is_empty() {
+            EndTurn::trigger(&mut event_queue).fire();
+        }
+        let mut errors = Vec::new();
+        for prototype in event_queue {
+            if let Err(error) = self.process(prototype) {
+                errors.push(error);
+            }
+        }
+        match errors.len() {
+            1 => Err(errors.swap_remove(0)),
+            x if x > 1 => Err(WeaselError::MultiError(errors)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Updates the deadline of the current turn, after `event` has been applied.
+    fn track_turn(&mut self, event: &EventWrapper<R>) {
+        if let TurnState::Ready = self.battle.rounds().state() {
+            self.turn_deadline = None;
+        } else if event.kind() == EventKind::StartTurn {
+            self.turn_deadline = self.turn_time_limit.and_then(|limit| {
+                event
+                    .metadata()
+                    .timestamp()
+                    .map(|timestamp| timestamp.saturating_add(limit))
+            });
+        }
+    }
+
     /// Returns a handle to access the players' rights to control one or more teams.
     pub fn rights(&self) -> RightsHandle<R> {
         self.battle.rights()
@@ -57,27 +206,180 @@ impl<R: BattleRules + 'static> Server<R> {
         MultiClientSinkHandleMut::new(&mut self.client_sinks, &self.battle)
     }
 
+    /// Returns the planning phase in progress, if any.
+    pub fn planning(&self) -> Option<&Planning<R>> {
+        self.planning.as_ref()
+    }
+
+    /// Starts a planning phase, in which every team in the battle must submit a batch
+    /// of orders through `submit_orders`.\
+    /// Client events can't be processed until the planning phase is over.
+    ///
+    /// Orders are buffered by the server without being applied nor sent to client sinks.
+    /// They are resolved once every team has submitted its batch, or when the planning
+    /// phase is explicitly resolved or expired.
+    pub fn start_planning(&mut self, deadline: Option<Timestamp>) -> WeaselResult<(), R> {
+        if self.planning.is_some() {
+            return Err(WeaselError::PlanningInProgress);
+        }
+        let teams = self
+            .battle
+            .entities()
+            .teams()
+            .map(|team| team.id().clone())
+            .collect();
+        self.planning = Some(Planning {
+            teams,
+            batches: IndexMap::new(),
+            deadline,
+        });
+        Ok(())
+    }
+
+    /// Submits the batch of orders of a team for the current planning phase.
+    ///
+    /// Orders are verified only when they are resolved, since they might depend
+    /// on the outcome of previous orders.\
+    /// The planning phase is resolved as soon as the last team submits its batch.
+    pub fn submit_orders(
+        &mut self,
+        team_id: TeamId<R>,
+        orders: Vec<ClientEventPrototype<R>>,
+    ) -> WeaselResult<(), R> {
+        let planning = self
+            .planning
+            .as_ref()
+            .ok_or(WeaselError::NoPlanningInProgress)?;
+        if !planning.teams.contains(&team_id) {
+            return Err(WeaselError::TeamNotFound(team_id));
+        }
+        if planning.batches.contains_key(&team_id) {
+            return Err(WeaselError::OrdersAlreadySubmitted(team_id));
+        }
+        // Verify that all orders come from a player in control of the team.
+        if self.authentication {
+            for order in &orders {
+                if let Some(player) = order.player() {
+                    self.check_rights(player, &team_id)?;
+                } else {
+                    return Err(WeaselError::MissingAuthentication);
+                }
+            }
+        }
+        let planning = self.planning.as_mut().unwrap();
+        planning.batches.insert(team_id, orders);
+        if planning.complete() {
+            self.resolve_planning()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Resolves the planning phase in progress if its deadline expired at time `now`.
+    /// The orders of teams who didn't submit a batch in time are considered empty.
+    pub fn expire_planning(&mut self, now: Timestamp) -> WeaselResult<(), R> {
+        match self
+            .planning
+            .as_ref()
+            .and_then(|planning| planning.deadline)
+        {
+            Some(deadline) if deadline <= now => self.resolve_planning(),
+            _ => Ok(()),
+        }
+    }
+
+    /// Ends the planning phase in progress and resolves all submitted orders,
+    /// one batch after the other in the order defined by `RoundsRules::order_batches`.
+    pub fn resolve_planning(&mut self) -> WeaselResult<(), R> {
+        let mut planning = self
+            .planning
+            .take()
+            .ok_or(WeaselError::NoPlanningInProgress)?;
+        let teams: Vec<_> = planning.batches.keys().cloned().collect();
+        let rounds = self.battle.rounds();
+        let order = rounds
+            .rules()
+            .order_batches(rounds.model(), self.battle.entities(), &teams);
+        let mut errors = Vec::new();
+        for team_id in order {
+            let orders = planning.batches.swap_remove(&team_id).unwrap_or_default();
+            for order in orders {
+                if let Err(error) = self.resolve_order(&team_id, order) {
+                    errors.push(error);
+                }
+            }
+        }
+        match errors.len() {
+            1 => Err(errors.swap_remove(0)),
+            x if x > 1 => Err(WeaselError::MultiError(errors)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Processes an order belonging to the batch of the given team.
+    fn resolve_order(
+        &mut self,
+        team_id: &TeamId<R>,
+        order: ClientEventPrototype<R>,
+    ) -> WeaselResult<(), R> {
+        self.battle.verify_client(&order)?;
+        // Verify that the order affects only the team who submitted it.
+        let foreign_team = match order.rights(&self.battle) {
+            EventRights::Team(id) if id != team_id => Some(id.clone()),
+            EventRights::Teams(ids) => ids.into_iter().find(|id| *id != team_id).cloned(),
+            _ => None,
+        };
+        if let Some(id) = foreign_team {
+            return Err(WeaselError::AuthenticationError(order.player(), id));
+        }
+        self.process_client(order)
+    }
+
     /// Applies an event. The event must be valid.
     fn apply_event(&mut self, event: EventWrapper<R>) -> WeaselResult<(), R> {
+        // Reset the cascade's bookkeeping for each new root event.
+        if self.cascade_chain.is_empty() {
+            self.derived_events = 0;
+            self.cascade_overflow = false;
+        }
         let mut event_queue = Some(EventQueue::<R>::new());
         // Apply the event on the battle.
         self.battle.apply(&event, &mut event_queue);
+        self.track_turn(&event);
         // Send the event to all client sinks.
         self.client_sinks
             .send_all(&event.clone().version(self.battle.rules().version().clone()));
         // Recursively process derived events.
         let mut errors = Vec::new();
         if let Some(event_queue) = event_queue {
+            self.cascade_chain.push(event.id());
             for mut prototype in event_queue {
+                // Stop processing derived events once the cascade overflowed.
+                if self.cascade_overflow {
+                    break;
+                }
                 // Set origin id in derived event, only if it wasn't set explicitly.
                 if prototype.origin().is_none() {
                     prototype.set_origin(Some(event.id()));
                 }
+                // Check the cascade's limits.
+                self.derived_events += 1;
+                if self.cascade_chain.len() > self.max_cascade_depth
+                    || self.derived_events > self.max_derived_events
+                {
+                    self.cascade_overflow = true;
+                    errors.push(WeaselError::EventCascadeOverflow(
+                        self.cascade_chain.clone(),
+                        prototype.event().clone(),
+                    ));
+                    break;
+                }
                 let result = self.process(prototype);
                 if let Err(error) = result {
                     errors.push(error);
                 }
             }
+            self.cascade_chain.pop();
         }
         // If there is an error, return it.
         // In the case of multiple errors, wrap them into a multi error.
@@ -124,7 +426,7 @@ impl<R: BattleRules + 'static> EventProcessor<R> for Server<R> {
             .verify_prototype(&event)
             .map_err(|e| WeaselError::InvalidEvent(event.event().clone(), e.into()))?;
         // Promote verified event.
-        let event = self.battle.promote(event);
+        let event = self.battle.promote(event, self.now(), None);
         // Apply it.
         self.apply_event(event)
     }
@@ -132,6 +434,10 @@ impl<R: BattleRules + 'static> EventProcessor<R> for Server<R> {
 
 impl<R: BattleRules + 'static> EventServer<R> for Server<R> {
     fn process_client(&mut self, event: ClientEventPrototype<R>) -> WeaselResult<(), R> {
+        // Client events must be submitted as orders during a planning phase.
+        if self.planning.is_some() {
+            return Err(WeaselError::PlanningInProgress);
+        }
         // Verify this event.
         self.battle.verify_client(&event)?;
         // Verify event's rights.
@@ -166,7 +472,8 @@ impl<R: BattleRules + 'static> EventServer<R> for Server<R> {
             EventRights::None => {}
         }
         // Promote verified event.
-        let event = self.battle.promote(event.prototype());
+        let player = event.player();
+        let event = self.battle.promote(event.prototype(), self.now(), player);
         // Apply it.
         self.apply_event(event)
     }
@@ -178,6 +485,7 @@ impl<R: BattleRules + 'static> EventReceiver<R> for Server<R> {
         self.battle.verify_wrapper(&event)?;
         // Apply the event on the battle.
         self.battle.apply(&event.wrapper(), &mut None);
+        self.track_turn(event.wrapper());
         // Send the event to all client sinks.
         self.client_sinks.send_all(&event);
         Ok(())
@@ -188,6 +496,10 @@ impl<R: BattleRules + 'static> EventReceiver<R> for Server<R> {
 pub struct ServerBuilder<R: BattleRules> {
     battle: Battle<R>,
     authentication: bool,
+    clock: Option<Box<dyn Clock>>,
+    max_cascade_depth: usize,
+    max_derived_events: usize,
+    turn_time_limit: Option<Timestamp>,
 }
 
 impl<R: BattleRules> ServerBuilder<R> {
@@ -198,12 +510,84 @@ impl<R: BattleRules> ServerBuilder<R> {
         self
     }
 
+    /// Sets a clock to timestamp all events promoted by the server.
+    pub fn clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Sets the maximum depth of a cascade of derived events.
+    /// Derived events beyond this depth are rejected with an `EventCascadeOverflow` error.
+    pub fn max_cascade_depth(mut self, depth: usize) -> Self {
+        self.max_cascade_depth = depth;
+        self
+    }
+
+    /// Sets the maximum number of events that can be derived from a single root event.
+    /// Derived events beyond this budget are rejected with an `EventCascadeOverflow` error.
+    pub fn max_derived_events(mut self, count: usize) -> Self {
+        self.max_derived_events = count;
+        self
+    }
+
+    /// Sets the maximum duration of a turn, in milliseconds.
+    /// Expired turns are concluded by `Server::tick`.
+    pub fn turn_time_limit(mut self, limit: Timestamp) -> Self {
+        self.turn_time_limit = Some(limit);
+        self
+    }
+
     /// Creates a new server.
     pub fn build(self) -> Server<R> {
         Server {
             battle: self.battle,
             client_sinks: MultiClientSink::new(),
             authentication: self.authentication,
+            clock: self.clock,
+            max_cascade_depth: self.max_cascade_depth,
+            max_derived_events: self.max_derived_events,
+            cascade_chain: Vec::new(),
+            derived_events: 0,
+            cascade_overflow: false,
+            planning: None,
+            turn_time_limit: self.turn_time_limit,
+            turn_deadline: None,
         }
     }
 }
+
+/// A planning phase, during which teams submit their orders simultaneously.
+pub struct Planning<R: BattleRules> {
+    teams: IndexSet<TeamId<R>>,
+    batches: IndexMap<TeamId<R>, Vec<ClientEventPrototype<R>>>,
+    deadline: Option<Timestamp>,
+}
+
+impl<R: BattleRules> Planning<R> {
+    /// Returns the time at which the planning phase expires.
+    pub fn deadline(&self) -> Option<Timestamp> {
+        self.deadline
+    }
+
+    /// Returns an iterator over the ids of all teams taking part in the planning phase.
+    pub fn teams(&self) -> impl Iterator<Item = &TeamId<R>> {
+        self.teams.iter()
+    }
+
+    /// Returns true if the given team already submitted its orders.
+    pub fn has_submitted(&self, team_id: &TeamId<R>) -> bool {
+        self.batches.contains_key(team_id)
+    }
+
+    /// Returns an iterator over the ids of the teams who didn't submit their orders yet.
+    pub fn pending(&self) -> impl Iterator<Item = &TeamId<R>> {
+        self.teams
+            .iter()
+            .filter(move |team_id| !self.batches.contains_key(*team_id))
+    }
+
+    /// Returns true if every team submitted its orders.
+    fn complete(&self) -> bool {
+        self.pending().next().is_none()
+    }
+}
@@ -1,15 +1,25 @@
 //! Module for the spatial dimension.
 
-use crate::battle::{Battle, BattleRules};
+use crate::battle::{Battle, BattlePhase, BattleRules};
 use crate::entity::{Entities, Entity, EntityId};
 use crate::error::{WeaselError, WeaselResult};
-use crate::event::{Event, EventKind, EventProcessor, EventQueue, EventTrigger};
+use crate::event::{Event, EventKind, EventProcessor, EventQueue, EventRights, EventTrigger};
 use crate::metric::WriteMetrics;
-use crate::round::Rounds;
+use crate::round::{end_turn_if_exhausted, Budget, Rounds, RoundsRules};
+use crate::team::TeamId;
 #[cfg(feature = "serialization")]
 use serde::{Deserialize, Serialize};
 use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::fmt::{Debug, Formatter, Result};
+use std::hash::Hash;
+
+/// Type to represent the cost of moving through space.
+pub type PathCost = u32;
+
+/// Type to represent the distance between two positions.
+pub type Distance = u32;
 
 /// This object takes care of everything related to space and movement in the battle.\
 /// It verifies the consistency of every entity's position.
@@ -41,10 +51,113 @@ impl<R: BattleRules> Space<R> {
         &mut self,
         claim: PositionClaim<R>,
         position: Option<&Position<R>>,
+        event_queue: &mut Option<EventQueue<R>>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.rules
+            .move_entity(&mut self.model, claim, position, event_queue, metrics);
+    }
+
+    /// See [on_step](trait.SpaceRules.html#method.on_step).
+    pub(crate) fn on_step(
+        &self,
+        entity: &dyn Entity<R>,
+        from: &Position<R>,
+        to: &Position<R>,
+        event_queue: &mut Option<EventQueue<R>>,
         metrics: &mut WriteMetrics<R>,
     ) {
         self.rules
-            .move_entity(&mut self.model, claim, position, metrics);
+            .on_step(&self.model, entity, from, to, event_queue, metrics);
+    }
+
+    /// See [on_turn_end](trait.SpaceRules.html#method.on_turn_end).
+    pub(crate) fn on_turn_end(
+        &self,
+        entity: &dyn Entity<R>,
+        event_queue: &mut Option<EventQueue<R>>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.rules
+            .on_turn_end(&self.model, entity, event_queue, metrics);
+    }
+
+    /// Verifies that creatures of the given team can be deployed in `position`.
+    pub(crate) fn check_deployment(
+        &self,
+        team_id: &TeamId<R>,
+        position: &Position<R>,
+    ) -> WeaselResult<(), R> {
+        if self
+            .rules
+            .in_deployment_zone(&self.model, team_id, position)
+        {
+            Ok(())
+        } else {
+            Err(WeaselError::OutsideDeploymentZone(
+                team_id.clone(),
+                position.clone(),
+            ))
+        }
+    }
+
+    /// Invokes the zone hooks for the entity with the given id, which has just moved
+    /// from `from` into its current position.\
+    /// Returns `ZoneReaction::Interrupt` if any hook asks to interrupt the movement.
+    pub(crate) fn cross_zones(
+        &self,
+        entities: &Entities<R>,
+        id: &EntityId<R>,
+        from: &Position<R>,
+        event_queue: &mut Option<EventQueue<R>>,
+        metrics: &mut WriteMetrics<R>,
+    ) -> ZoneReaction {
+        let entity = entities
+            .entity(id)
+            .unwrap_or_else(|| panic!("constraint violated: entity {:?} not found", id));
+        let mut reaction = ZoneReaction::Continue;
+        for owner in entities.entities().filter(|owner| owner.entity_id() != id) {
+            let was_inside = self
+                .rules
+                .in_zone(&self.model, entities, owner, entity, from);
+            let is_inside =
+                self.rules
+                    .in_zone(&self.model, entities, owner, entity, entity.position());
+            let result = match (was_inside, is_inside) {
+                (false, true) => {
+                    self.rules
+                        .on_zone_enter(&self.model, owner, entity, from, event_queue, metrics)
+                }
+                (true, false) => {
+                    self.rules
+                        .on_zone_leave(&self.model, owner, entity, from, event_queue, metrics)
+                }
+                _ => continue,
+            };
+            if result == ZoneReaction::Interrupt {
+                reaction = ZoneReaction::Interrupt;
+            }
+        }
+        reaction
+    }
+
+    /// See [on_collision](trait.SpaceRules.html#method.on_collision).
+    pub(crate) fn on_collision(
+        &self,
+        entity: &dyn Entity<R>,
+        obstacle: &Position<R>,
+        remaining: Distance,
+        event_queue: &mut Option<EventQueue<R>>,
+        metrics: &mut WriteMetrics<R>,
+    ) {
+        self.rules.on_collision(
+            &self.model,
+            entity,
+            obstacle,
+            remaining,
+            event_queue,
+            metrics,
+        );
     }
 
     /// Returns the space model.
@@ -58,6 +171,21 @@ impl<R: BattleRules> Space<R> {
         &mut self.model
     }
 
+    /// Returns the total cost for `entity` to walk along `path`, starting from its
+    /// current position.
+    ///
+    /// Returns `None` if any step of the path is impassable.
+    pub fn path_cost(&self, entity: &dyn Entity<R>, path: &[Position<R>]) -> Option<PathCost> {
+        let mut from = entity.position();
+        let mut cost: PathCost = 0;
+        for to in path {
+            let step = self.rules.step_cost(&self.model, entity, from, to)?;
+            cost = cost.saturating_add(step);
+            from = to;
+        }
+        Some(cost)
+    }
+
     /// Returns this space's rules.
     pub fn rules(&self) -> &R::SR {
         &self.rules
@@ -69,6 +197,141 @@ impl<R: BattleRules> Space<R> {
     }
 }
 
+impl<R: BattleRules> Space<R>
+where
+    Position<R>: Hash,
+{
+    /// Finds the cheapest path for `entity` to reach `destination`, using the A* algorithm.
+    ///
+    /// Steps are generated with the space rules' `neighbors`, weighted with `step_cost`
+    /// and validated with `check_move`. The search is guided by `estimate_cost`.\
+    /// The returned path doesn't include the entity's current position and it ends with
+    /// `destination`. Returns `None` if `destination` can't be reached.
+    pub fn find_path(
+        &self,
+        entity: &dyn Entity<R>,
+        destination: &Position<R>,
+    ) -> Option<Vec<Position<R>>> {
+        let start = entity.position().clone();
+        // All positions discovered so far.
+        let mut nodes = vec![start.clone()];
+        // For each position: its index, the cost to reach it and the index of its predecessor.
+        let mut visited: HashMap<Position<R>, (usize, PathCost, Option<usize>)> = HashMap::new();
+        visited.insert(start, (0, 0, None));
+        // Sequence numbers make the search deterministic when estimates are equal.
+        let mut sequence = 0;
+        let mut frontier = BinaryHeap::new();
+        frontier.push(Reverse((0, sequence, 0, 0)));
+        while let Some(Reverse((_, _, cost, index))) = frontier.pop() {
+            let current = nodes[index].clone();
+            if visited[&current].1 < cost {
+                // A cheaper way to this position was already found.
+                continue;
+            }
+            if current == *destination {
+                // Walk back to the starting position.
+                let mut path = Vec::new();
+                let mut step = Some(index);
+                while let Some(index) = step {
+                    path.push(nodes[index].clone());
+                    step = visited[&nodes[index]].2;
+                }
+                path.pop();
+                path.reverse();
+                return Some(path);
+            }
+            for next in self.rules.neighbors(&self.model, &current) {
+                let step_cost = match self.rules.step_cost(&self.model, entity, &current, &next) {
+                    Some(step_cost) => step_cost,
+                    None => continue,
+                };
+                let next_cost = cost.saturating_add(step_cost);
+                let next_index = match visited.get(&next) {
+                    Some((_, known_cost, _)) if *known_cost <= next_cost => continue,
+                    Some((next_index, _, _)) => *next_index,
+                    None => {
+                        if self
+                            .check_move(PositionClaim::Movement(entity), &next)
+                            .is_err()
+                        {
+                            continue;
+                        }
+                        nodes.push(next.clone());
+                        nodes.len() - 1
+                    }
+                };
+                visited.insert(next.clone(), (next_index, next_cost, Some(index)));
+                let estimate = next_cost.saturating_add(self.rules.estimate_cost(
+                    &self.model,
+                    &next,
+                    destination,
+                ));
+                sequence += 1;
+                frontier.push(Reverse((estimate, sequence, next_cost, next_index)));
+            }
+        }
+        None
+    }
+}
+
+impl<R: BattleRules> Space<R>
+where
+    R::SR: SpatialQueries<R>,
+{
+    /// See [distance](trait.SpatialQueries.html#method.distance).
+    pub fn distance(&self, from: &Position<R>, to: &Position<R>) -> Distance {
+        self.rules.distance(&self.model, from, to)
+    }
+
+    /// See [in_area](trait.SpatialQueries.html#method.in_area).
+    pub fn in_area(&self, area: &Area<Position<R>>, position: &Position<R>) -> bool {
+        self.rules.in_area(&self.model, area, position)
+    }
+
+    /// See [line_of_sight](trait.SpatialQueries.html#method.line_of_sight).
+    pub fn line_of_sight(&self, from: &Position<R>, to: &Position<R>) -> bool {
+        self.rules.line_of_sight(&self.model, from, to)
+    }
+
+    /// Returns the ids of all entities within `radius` from `center`.
+    pub fn entities_within<'a>(
+        &'a self,
+        entities: &'a Entities<R>,
+        center: &Position<R>,
+        radius: Distance,
+    ) -> impl Iterator<Item = &'a EntityId<R>> {
+        self.entities_in_area(entities, Area::Circle(center.clone(), radius))
+    }
+
+    /// Returns the ids of all entities inside `area`.
+    pub fn entities_in_area<'a>(
+        &'a self,
+        entities: &'a Entities<R>,
+        area: Area<Position<R>>,
+    ) -> impl Iterator<Item = &'a EntityId<R>> {
+        entities
+            .entities()
+            .filter(move |entity| self.in_area(&area, entity.position()))
+            .map(|entity| entity.entity_id())
+    }
+
+    /// Returns the ids of all entities in line of sight from `position`.\
+    /// Entities standing in `position` are not included.
+    pub fn visible_entities<'a>(
+        &'a self,
+        entities: &'a Entities<R>,
+        position: &Position<R>,
+    ) -> impl Iterator<Item = &'a EntityId<R>> {
+        let position = position.clone();
+        entities
+            .entities()
+            .filter(move |entity| {
+                *entity.position() != position && self.line_of_sight(&position, entity.position())
+            })
+            .map(|entity| entity.entity_id())
+    }
+}
+
 /// Rules to govern the space dimension in a game.
 ///
 /// This rules are used to determine if an entity can occupy a given position and to keep a model
@@ -95,6 +358,13 @@ pub trait SpaceRules<R: BattleRules> {
     /// See [SpaceAlteration](type.SpaceAlteration.html).
     type SpaceAlteration: Clone + Debug + Send + Serialize + for<'a> Deserialize<'a>;
 
+    #[cfg(not(feature = "serialization"))]
+    /// See [Direction](type.Direction.html).
+    type Direction: Clone + Debug + Send;
+    #[cfg(feature = "serialization")]
+    /// See [Direction](type.Direction.html).
+    type Direction: Clone + Debug + Send + Serialize + for<'a> Deserialize<'a>;
+
     /// See [SpaceModel](type.SpaceModel.html).
     type SpaceModel;
 
@@ -121,6 +391,8 @@ pub trait SpaceRules<R: BattleRules> {
     /// unless it is `None`.
     /// An empty position means that the entity is disappearing from the battle.\
     /// The claim tells in which context the entity is trying to acquire the position.\
+    /// Side effects of leaving the old position or entering the new one, such as hazards
+    /// or traps, should be applied by registering events inside `event_queue`.
     ///
     /// The provided implementation does nothing.
     fn move_entity(
@@ -128,6 +400,190 @@ pub trait SpaceRules<R: BattleRules> {
         _model: &mut Self::SpaceModel,
         _claim: PositionClaim<R>,
         _position: Option<&Self::Position>,
+        _event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+    }
+
+    /// Returns the positions that can be reached with a single step from `position`.
+    ///
+    /// Neighbors are used to find paths and to validate movements along a path.
+    ///
+    /// The provided implementation returns no positions.
+    fn neighbors(
+        &self,
+        _model: &Self::SpaceModel,
+        _position: &Self::Position,
+    ) -> Vec<Self::Position> {
+        Vec::new()
+    }
+
+    /// Returns the cost for `entity` to step from `from` to the neighboring position `to`.
+    ///
+    /// `None` means that the step is impassable.
+    ///
+    /// The provided implementation returns a cost of one for every step.
+    fn step_cost(
+        &self,
+        _model: &Self::SpaceModel,
+        _entity: &dyn Entity<R>,
+        _from: &Self::Position,
+        _to: &Self::Position,
+    ) -> Option<PathCost> {
+        Some(1)
+    }
+
+    /// Returns an estimate of the cost to go from `from` to `to`.
+    ///
+    /// The estimate guides the search of paths and it must never be greater than the
+    /// actual cost, otherwise the paths found might not be the cheapest ones.
+    ///
+    /// The provided implementation returns zero.
+    fn estimate_cost(
+        &self,
+        _model: &Self::SpaceModel,
+        _from: &Self::Position,
+        _to: &Self::Position,
+    ) -> PathCost {
+        0
+    }
+
+    /// Invoked each time an entity moving along a path steps from `from` into `to`.
+    ///
+    /// Side effects of crossing intermediate positions should be applied by registering
+    /// events inside `event_queue`.
+    ///
+    /// The provided implementation does nothing.
+    fn on_step(
+        &self,
+        _model: &Self::SpaceModel,
+        _entity: &dyn Entity<R>,
+        _from: &Self::Position,
+        _to: &Self::Position,
+        _event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+    }
+
+    /// Invoked when an actor ends its turn, standing in its current position.
+    ///
+    /// Effects of the position on the actor, such as hazards, should be applied by
+    /// registering events inside `event_queue`.
+    ///
+    /// The provided implementation does nothing.
+    fn on_turn_end(
+        &self,
+        _model: &Self::SpaceModel,
+        _entity: &dyn Entity<R>,
+        _event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) {
+    }
+
+    /// Returns true if creatures of the team `team_id` can be deployed in `position`.
+    ///
+    /// Deployment zones are enforced only during the `Deployment` phase of the battle,
+    /// when creatures are created or moved with `MoveEntity`.
+    ///
+    /// The provided implementation returns true.
+    fn in_deployment_zone(
+        &self,
+        _model: &Self::SpaceModel,
+        _team_id: &TeamId<R>,
+        _position: &Self::Position,
+    ) -> bool {
+        true
+    }
+
+    /// Returns true if `position` is inside the zone of control of `owner`, for `entity`.
+    ///
+    /// Zones are checked each time an entity moves on its own, with `MoveEntity` or
+    /// `MoveAlongPath`. Forced movements and swaps don't trigger zones.
+    ///
+    /// The provided implementation returns false.
+    fn in_zone(
+        &self,
+        _model: &Self::SpaceModel,
+        _entities: &Entities<R>,
+        _owner: &dyn Entity<R>,
+        _entity: &dyn Entity<R>,
+        _position: &Self::Position,
+    ) -> bool {
+        false
+    }
+
+    /// Invoked when `entity` moves from `from` into the zone of control of `owner`.
+    ///
+    /// Reactions, such as an opportunity attack, should be applied by registering
+    /// events inside `event_queue`. Return `ZoneReaction::Interrupt` to stop the movement
+    /// before the entity takes its next step.
+    ///
+    /// The provided implementation does nothing and lets the movement continue.
+    fn on_zone_enter(
+        &self,
+        _model: &Self::SpaceModel,
+        _owner: &dyn Entity<R>,
+        _entity: &dyn Entity<R>,
+        _from: &Self::Position,
+        _event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) -> ZoneReaction {
+        ZoneReaction::Continue
+    }
+
+    /// Invoked when `entity` moves out of the zone of control of `owner`, leaving `from`.
+    ///
+    /// Reactions, such as an opportunity attack, should be applied by registering
+    /// events inside `event_queue`. Return `ZoneReaction::Interrupt` to stop the movement
+    /// before the entity takes its next step.
+    ///
+    /// The provided implementation does nothing and lets the movement continue.
+    fn on_zone_leave(
+        &self,
+        _model: &Self::SpaceModel,
+        _owner: &dyn Entity<R>,
+        _entity: &dyn Entity<R>,
+        _from: &Self::Position,
+        _event_queue: &mut Option<EventQueue<R>>,
+        _metrics: &mut WriteMetrics<R>,
+    ) -> ZoneReaction {
+        ZoneReaction::Continue
+    }
+
+    /// Returns the positions that `entity` would cross, one step at a time, if it were
+    /// forced to move `distance` steps towards `direction`.
+    ///
+    /// Positions are validated one by one with `check_move`. The movement stops at the first
+    /// invalid position, so the returned positions may include obstacles or positions
+    /// outside of the battlefield.\
+    /// `distance` can be arbitrarily large: implementations should stop the path
+    /// once it leaves the battlefield.
+    ///
+    /// The provided implementation returns no positions.
+    fn displacement_path(
+        &self,
+        _model: &Self::SpaceModel,
+        _entity: &dyn Entity<R>,
+        _direction: &Self::Direction,
+        _distance: Distance,
+    ) -> Vec<Self::Position> {
+        Vec::new()
+    }
+
+    /// Invoked when a forced movement of `entity` is stopped before reaching `obstacle`.
+    /// `remaining` is the number of steps left to travel.
+    ///
+    /// Consequences of the collision, such as damage, should be applied by registering
+    /// events inside `event_queue`.
+    ///
+    /// The provided implementation does nothing.
+    fn on_collision(
+        &self,
+        _model: &Self::SpaceModel,
+        _entity: &dyn Entity<R>,
+        _obstacle: &Self::Position,
+        _remaining: Distance,
+        _event_queue: &mut Option<EventQueue<R>>,
         _metrics: &mut WriteMetrics<R>,
     ) {
     }
@@ -165,12 +621,79 @@ pub trait SpaceRules<R: BattleRules> {
     }
 }
 
+/// Outcome of an entity entering or leaving a zone of control.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoneReaction {
+    /// The movement goes on.
+    Continue,
+    /// The movement stops in the current position. Remaining steps are not taken.
+    Interrupt,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for ZoneReaction {
+    fn default() -> Self {
+        ZoneReaction::Continue
+    }
+}
+
+/// A shape covering a portion of space.
+///
+/// The exact meaning of each shape is defined by the space rules implementing `SpatialQueries`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub enum Area<P> {
+    /// All positions within the given distance from a center.
+    Circle(P, Distance),
+    /// Positions within the given distance from an origin, spreading towards a target.
+    Cone(P, P, Distance),
+    /// Positions crossed by a straight line between two positions.
+    Line(P, P),
+    /// Positions inside the rectangle defined by two opposite corners.
+    Rectangle(P, P),
+}
+
+/// Geometric queries over a space model.
+///
+/// Space rules implementing this trait enable the spatial queries of `Space`, such as
+/// finding all entities inside an area or checking line of sight.
+pub trait SpatialQueries<R: BattleRules>: SpaceRules<R> {
+    /// Returns the distance between two positions.
+    fn distance(
+        &self,
+        model: &Self::SpaceModel,
+        from: &Self::Position,
+        to: &Self::Position,
+    ) -> Distance;
+
+    /// Returns true if `position` is inside `area`.
+    fn in_area(
+        &self,
+        model: &Self::SpaceModel,
+        area: &Area<Self::Position>,
+        position: &Self::Position,
+    ) -> bool;
+
+    /// Returns true if `to` can be seen from `from`.
+    ///
+    /// The provided implementation always returns true.
+    fn line_of_sight(
+        &self,
+        _model: &Self::SpaceModel,
+        _from: &Self::Position,
+        _to: &Self::Position,
+    ) -> bool {
+        true
+    }
+}
+
 /// Type to represent an object's position.
 ///
 /// Position's meaning changes depending on your definition of space.\
 /// Remember that positions should contain all information to fully represent what does
 /// it mean to occupy a *piece* of the battlefield. For instance, if your entities occupy
-/// an area, both the area's location and dimension must be encapsulated in this type.
+/// an area, both the area's location and dimension must be encapsulated in this type.\
+/// `rules::space::FootprintGridRules` provides such positions for grids of squares.
 pub type Position<R> = <<R as BattleRules>::SR as SpaceRules<R>>::Position;
 
 /// Type to represent a space seed.
@@ -191,26 +714,56 @@ pub type SpaceModel<R> = <<R as BattleRules>::SR as SpaceRules<R>>::SpaceModel;
 /// implemented in the space rules `alter_space` method.
 pub type SpaceAlteration<R> = <<R as BattleRules>::SR as SpaceRules<R>>::SpaceAlteration;
 
+/// Type to represent a direction in space.
+///
+/// Directions are used to displace entities with forced movements, for instance
+/// to push an entity away from the attacker.
+pub type Direction<R> = <<R as BattleRules>::SR as SpaceRules<R>>::Direction;
+
 /// Represents an entity's claim to a given position.
 pub enum PositionClaim<'a, R: BattleRules> {
     /// The entity is spawning.
     Spawn(&'a EntityId<R>),
     /// The entity wants to change its position.
     Movement(&'a dyn Entity<R>),
+    /// The entity is forced to change its position.
+    Displacement(&'a dyn Entity<R>),
+    /// The entity is exchanging its position with another entity, whose id is given.\
+    /// The other entity still occupies the position when the claim is made.
+    Swap(&'a dyn Entity<R>, &'a EntityId<R>),
 }
 
-impl<R: BattleRules> PositionClaim<'_, R> {
+impl<'a, R: BattleRules> PositionClaim<'a, R> {
     /// Returns the id of the entity behind this claim.
     pub fn entity_id(&self) -> &EntityId<R> {
         match self {
             Self::Spawn(id) => id,
-            Self::Movement(entity) => entity.entity_id(),
+            Self::Movement(entity) | Self::Displacement(entity) | Self::Swap(entity, _) => {
+                entity.entity_id()
+            }
+        }
+    }
+
+    /// Returns the current position of the entity behind this claim, unless it is spawning.
+    pub fn position(&self) -> Option<&'a Position<R>> {
+        self.entity().map(|entity| entity.position())
+    }
+
+    /// Returns the entity behind this claim, unless it is spawning.
+    pub fn entity(&self) -> Option<&'a dyn Entity<R>> {
+        match self {
+            Self::Spawn(_) => None,
+            Self::Movement(entity) | Self::Displacement(entity) | Self::Swap(entity, _) => {
+                Some(*entity)
+            }
         }
     }
 }
 
 /// An event to move an entity from its position to a new one.
 ///
+/// During the deployment phase, teams can fire this event to position their own creatures.
+///
 /// # Examples
 /// ```
 /// use weasel::{
@@ -285,6 +838,22 @@ impl<R: BattleRules> MoveEntity<R> {
     }
 }
 
+impl<R: BattleRules + 'static> MoveEntity<R> {
+    /// Returns the cost of this movement, if the entity is an actor in its turn.
+    fn movement_cost(&self, battle: &Battle<R>) -> Option<Budget> {
+        if !battle.rounds().is_acting(&self.id) {
+            return None;
+        }
+        let actor = battle.entities().actor(&self.id)?;
+        let rounds = battle.rounds();
+        Some(
+            rounds
+                .rules()
+                .movement_cost(rounds.model(), actor, &self.position),
+        )
+    }
+}
+
 impl<R: BattleRules> Debug for MoveEntity<R> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(
@@ -311,6 +880,10 @@ impl<R: BattleRules + 'static> Event<R> for MoveEntity<R> {
             .entities()
             .entity(&self.id)
             .ok_or_else(|| WeaselError::EntityNotFound(self.id.clone()))?;
+        // Incapacitated actors can't move on their own.
+        if matches!(battle.entities().actor(&self.id), Some(actor) if actor.is_incapacitated()) {
+            return Err(WeaselError::CharacterIncapacitated(self.id.clone()));
+        }
         // Check position.
         battle
             .space()
@@ -321,10 +894,27 @@ impl<R: BattleRules + 'static> Event<R> for MoveEntity<R> {
                     self.position.clone(),
                     Box::new(err),
                 )
-            })
+            })?;
+        // Creatures must stay in their deployment zone until the battle starts.
+        if battle.phase() == BattlePhase::Deployment {
+            if let Some(actor) = battle.entities().actor(&self.id) {
+                battle
+                    .space()
+                    .check_deployment(actor.team_id(), &self.position)?;
+            }
+        }
+        // Verify if the actor can pay for the movement.
+        if let Some(cost) = self.movement_cost(battle) {
+            battle.rounds().check_cost(&self.id, &cost)?;
+        }
+        Ok(())
     }
 
-    fn apply(&self, battle: &mut Battle<R>, _: &mut Option<EventQueue<R>>) {
+    fn apply(&self, battle: &mut Battle<R>, event_queue: &mut Option<EventQueue<R>>) {
+        // Pay for the movement.
+        if let Some(cost) = self.movement_cost(battle) {
+            battle.state.rounds.spend(&self.id, &cost);
+        }
         // Find the entity.
         let entity = battle
             .state
@@ -335,10 +925,20 @@ impl<R: BattleRules + 'static> Event<R> for MoveEntity<R> {
         battle.state.space.move_entity(
             PositionClaim::Movement(entity),
             Some(&self.position),
+            event_queue,
             &mut battle.metrics.write_handle(),
         );
         // Update the entity.
+        let from = entity.position().clone();
         entity.set_position(self.position.clone());
+        battle.state.space.cross_zones(
+            &battle.state.entities,
+            &self.id,
+            &from,
+            event_queue,
+            &mut battle.metrics.write_handle(),
+        );
+        end_turn_if_exhausted(battle, event_queue);
     }
 
     fn kind(&self) -> EventKind {
@@ -352,6 +952,16 @@ impl<R: BattleRules + 'static> Event<R> for MoveEntity<R> {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn rights<'a>(&'a self, battle: &'a Battle<R>) -> EventRights<'a, R> {
+        // During deployment teams can position their own creatures.
+        if battle.phase() == BattlePhase::Deployment {
+            if let Some(actor) = battle.entities().actor(&self.id) {
+                return EventRights::Team(actor.team_id());
+            }
+        }
+        EventRights::Server
+    }
 }
 
 /// Trigger to build and fire a `DummyEvent` event.
@@ -383,6 +993,720 @@ where
     }
 }
 
+/// An event to move an entity step by step along a path.
+///
+/// The path doesn't include the entity's current position. Each step must lead to a
+/// neighboring position that is passable and that the entity can occupy.\
+/// If the entity is an actor in its turn, it must be able to pay for the whole path,
+/// but it pays only for the steps it takes before a zone of control interrupts
+/// the movement.
+///
+/// # Examples
+/// ```
+/// use weasel::{
+///     battle_rules, battle_rules_with_space, rules::empty::*, rules::space::*, Battle,
+///     BattleController, BattleRules, CreateCreature, CreateTeam, EntityId, EventKind,
+///     EventTrigger, MoveAlongPath, ResetSpace, Server,
+/// };
+///
+/// battle_rules_with_space! { SquareGridRules }
+///
+/// let battle = Battle::builder(CustomRules::new()).build();
+/// let mut server = Server::builder(battle).build();
+/// ResetSpace::trigger(&mut server)
+///     .seed(GridSize::new(3, 3))
+///     .fire()
+///     .unwrap();
+///
+/// let team_id = 1;
+/// CreateTeam::trigger(&mut server, team_id).fire().unwrap();
+/// let creature_id = 1;
+/// CreateCreature::trigger(&mut server, creature_id, team_id, Square::new(0, 0))
+///     .fire()
+///     .unwrap();
+///
+/// let id = EntityId::Creature(creature_id);
+/// let entity = server.battle().entities().entity(&id).unwrap();
+/// let path = server
+///     .battle()
+///     .space()
+///     .find_path(entity, &Square::new(2, 2))
+///     .unwrap();
+/// MoveAlongPath::trigger(&mut server, id, path).fire().unwrap();
+/// assert_eq!(
+///     server.battle().history().events().iter().last().unwrap().kind(),
+///     EventKind::MoveAlongPath
+/// );
+/// ```
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct MoveAlongPath<R: BattleRules> {
+    #[cfg_attr(
+        feature = "serialization",
+        serde(bound(
+            serialize = "EntityId<R>: Serialize",
+            deserialize = "EntityId<R>: Deserialize<'de>"
+        ))
+    )]
+    id: EntityId<R>,
+
+    #[cfg_attr(
+        feature = "serialization",
+        serde(bound(
+            serialize = "Position<R>: Serialize",
+            deserialize = "Position<R>: Deserialize<'de>"
+        ))
+    )]
+    path: Vec<Position<R>>,
+}
+
+impl<R: BattleRules> MoveAlongPath<R> {
+    /// Returns a trigger for this event.
+    pub fn trigger<P: EventProcessor<R>>(
+        processor: &mut P,
+        id: EntityId<R>,
+        path: Vec<Position<R>>,
+    ) -> MoveAlongPathTrigger<'_, R, P> {
+        MoveAlongPathTrigger {
+            processor,
+            id,
+            path,
+        }
+    }
+
+    /// Returns the entity id.
+    pub fn id(&self) -> &EntityId<R> {
+        &self.id
+    }
+
+    /// Returns the positions that the entity will cross, the last one being its destination.
+    pub fn path(&self) -> &[Position<R>] {
+        &self.path
+    }
+}
+
+impl<R: BattleRules + 'static> MoveAlongPath<R> {
+    /// Returns the cost of each step of the path, starting from the entity's
+    /// current position.
+    fn step_costs(&self, battle: &Battle<R>) -> Option<Vec<PathCost>> {
+        let entity = battle.entities().entity(&self.id)?;
+        let space = battle.space();
+        let mut from = entity.position();
+        self.path
+            .iter()
+            .map(|to| {
+                let cost = space.rules().step_cost(space.model(), entity, from, to);
+                from = to;
+                cost
+            })
+            .collect()
+    }
+
+    /// Returns the cost of walking the first `steps` steps of the path,
+    /// if the entity is an actor in its turn.
+    fn movement_cost(
+        &self,
+        battle: &Battle<R>,
+        step_costs: &[PathCost],
+        steps: usize,
+    ) -> Option<Budget> {
+        if !battle.rounds().is_acting(&self.id) {
+            return None;
+        }
+        let actor = battle.entities().actor(&self.id)?;
+        let cost = step_costs
+            .iter()
+            .take(steps)
+            .fold(0, |total: PathCost, cost| total.saturating_add(*cost));
+        let rounds = battle.rounds();
+        Some(
+            rounds
+                .rules()
+                .path_cost(rounds.model(), actor, &self.path[..steps], cost),
+        )
+    }
+}
+
+impl<R: BattleRules> Debug for MoveAlongPath<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "MoveAlongPath {{ id: {:?}, path: {:?} }}",
+            self.id, self.path
+        )
+    }
+}
+
+impl<R: BattleRules> Clone for MoveAlongPath<R> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            path: self.path.clone(),
+        }
+    }
+}
+
+impl<R: BattleRules + 'static> Event<R> for MoveAlongPath<R> {
+    fn verify(&self, battle: &Battle<R>) -> WeaselResult<(), R> {
+        // Find the entity.
+        let entity = battle
+            .entities()
+            .entity(&self.id)
+            .ok_or_else(|| WeaselError::EntityNotFound(self.id.clone()))?;
+        // Incapacitated actors can't move on their own.
+        if matches!(battle.entities().actor(&self.id), Some(actor) if actor.is_incapacitated()) {
+            return Err(WeaselError::CharacterIncapacitated(self.id.clone()));
+        }
+        if self.path.is_empty() {
+            return Err(WeaselError::EmptyPath);
+        }
+        // Check each step.
+        let space = battle.space();
+        let mut from = entity.position();
+        for to in &self.path {
+            let adjacent = space.rules().neighbors(space.model(), from).contains(to);
+            if !adjacent
+                || space
+                    .rules()
+                    .step_cost(space.model(), entity, from, to)
+                    .is_none()
+            {
+                return Err(WeaselError::ImpassableStep(from.clone(), to.clone()));
+            }
+            space
+                .check_move(PositionClaim::Movement(entity), to)
+                .map_err(|err| {
+                    WeaselError::PositionError(Some(from.clone()), to.clone(), Box::new(err))
+                })?;
+            from = to;
+        }
+        // Verify if the actor can pay for the whole movement.
+        let cost = self
+            .step_costs(battle)
+            .and_then(|step_costs| self.movement_cost(battle, &step_costs, self.path.len()));
+        if let Some(cost) = cost {
+            battle.rounds().check_cost(&self.id, &cost)?;
+        }
+        Ok(())
+    }
+
+    fn apply(&self, battle: &mut Battle<R>, event_queue: &mut Option<EventQueue<R>>) {
+        // Compute the cost of each step before the entity starts moving.
+        let step_costs = self
+            .step_costs(battle)
+            .unwrap_or_else(|| panic!("constraint violated: path {:?} is impassable", self.path));
+        // Walk the path one step at a time.
+        let mut steps = 0;
+        for to in &self.path {
+            let entity = battle
+                .state
+                .entities
+                .entity_mut(&self.id)
+                .unwrap_or_else(|| panic!("constraint violated: entity {:?} not found", self.id));
+            let from = entity.position().clone();
+            battle.state.space.move_entity(
+                PositionClaim::Movement(entity),
+                Some(to),
+                event_queue,
+                &mut battle.metrics.write_handle(),
+            );
+            entity.set_position(to.clone());
+            battle.state.space.on_step(
+                entity,
+                &from,
+                to,
+                event_queue,
+                &mut battle.metrics.write_handle(),
+            );
+            // Zones of control may interrupt the movement.
+            let reaction = battle.state.space.cross_zones(
+                &battle.state.entities,
+                &self.id,
+                &from,
+                event_queue,
+                &mut battle.metrics.write_handle(),
+            );
+            steps += 1;
+            if reaction == ZoneReaction::Interrupt {
+                break;
+            }
+        }
+        // Pay only for the steps actually taken.
+        if let Some(cost) = self.movement_cost(battle, &step_costs, steps) {
+            battle.state.rounds.spend(&self.id, &cost);
+        }
+        end_turn_if_exhausted(battle, event_queue);
+    }
+
+    fn kind(&self) -> EventKind {
+        EventKind::MoveAlongPath
+    }
+
+    fn box_clone(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Trigger to build and fire a `MoveAlongPath` event.
+pub struct MoveAlongPathTrigger<'a, R, P>
+where
+    R: BattleRules,
+    P: EventProcessor<R>,
+{
+    processor: &'a mut P,
+    id: EntityId<R>,
+    path: Vec<Position<R>>,
+}
+
+impl<'a, R, P> EventTrigger<'a, R, P> for MoveAlongPathTrigger<'a, R, P>
+where
+    R: BattleRules + 'static,
+    P: EventProcessor<R>,
+{
+    fn processor(&'a mut self) -> &'a mut P {
+        self.processor
+    }
+
+    /// Returns a `MoveAlongPath` event.
+    fn event(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(MoveAlongPath {
+            id: self.id.clone(),
+            path: self.path.clone(),
+        })
+    }
+}
+
+/// An event to force an entity to move for a given distance towards a direction,
+/// for instance when it's pushed or pulled.
+///
+/// The positions crossed by the entity are decided by the space rules' `displacement_path`.
+/// If an obstacle stops the entity early, `on_collision` is invoked.\
+/// Unlike `MoveEntity`, forced movements never cost points to actors.
+///
+/// # Examples
+/// ```
+/// use weasel::{
+///     battle_rules, battle_rules_with_space, rules::empty::*, rules::space::*, Battle,
+///     BattleController, BattleRules, CreateCreature, CreateTeam, DisplaceEntity, EntityId,
+///     EventTrigger, ResetSpace, Server,
+/// };
+///
+/// battle_rules_with_space! { SquareGridRules }
+///
+/// let battle = Battle::builder(CustomRules::new()).build();
+/// let mut server = Server::builder(battle).build();
+/// ResetSpace::trigger(&mut server)
+///     .seed(GridSize::new(3, 1))
+///     .fire()
+///     .unwrap();
+///
+/// let team_id = 1;
+/// CreateTeam::trigger(&mut server, team_id).fire().unwrap();
+/// let creature_id = 1;
+/// CreateCreature::trigger(&mut server, creature_id, team_id, Square::new(0, 0))
+///     .fire()
+///     .unwrap();
+///
+/// // Push the creature to the right. It stops at the edge of the grid.
+/// let id = EntityId::Creature(creature_id);
+/// DisplaceEntity::trigger(&mut server, id, Square::new(1, 0), 5)
+///     .fire()
+///     .unwrap();
+/// assert_eq!(
+///     server.battle().entities().entity(&id).unwrap().position(),
+///     &Square::new(2, 0)
+/// );
+/// ```
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct DisplaceEntity<R: BattleRules> {
+    #[cfg_attr(
+        feature = "serialization",
+        serde(bound(
+            serialize = "EntityId<R>: Serialize",
+            deserialize = "EntityId<R>: Deserialize<'de>"
+        ))
+    )]
+    id: EntityId<R>,
+
+    #[cfg_attr(
+        feature = "serialization",
+        serde(bound(
+            serialize = "Direction<R>: Serialize",
+            deserialize = "Direction<R>: Deserialize<'de>"
+        ))
+    )]
+    direction: Direction<R>,
+
+    distance: Distance,
+}
+
+impl<R: BattleRules> DisplaceEntity<R> {
+    /// Returns a trigger for this event.
+    pub fn trigger<P: EventProcessor<R>>(
+        processor: &mut P,
+        id: EntityId<R>,
+        direction: Direction<R>,
+        distance: Distance,
+    ) -> DisplaceEntityTrigger<'_, R, P> {
+        DisplaceEntityTrigger {
+            processor,
+            id,
+            direction,
+            distance,
+        }
+    }
+
+    /// Returns the entity id.
+    pub fn id(&self) -> &EntityId<R> {
+        &self.id
+    }
+
+    /// Returns the direction of the movement.
+    pub fn direction(&self) -> &Direction<R> {
+        &self.direction
+    }
+
+    /// Returns the maximum distance that the entity will travel.
+    pub fn distance(&self) -> Distance {
+        self.distance
+    }
+}
+
+impl<R: BattleRules> Debug for DisplaceEntity<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "DisplaceEntity {{ id: {:?}, direction: {:?}, distance: {:?} }}",
+            self.id, self.direction, self.distance
+        )
+    }
+}
+
+impl<R: BattleRules> Clone for DisplaceEntity<R> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            direction: self.direction.clone(),
+            distance: self.distance,
+        }
+    }
+}
+
+impl<R: BattleRules + 'static> Event<R> for DisplaceEntity<R> {
+    fn verify(&self, battle: &Battle<R>) -> WeaselResult<(), R> {
+        // Check if the entity exists.
+        if battle.entities().entity(&self.id).is_none() {
+            return Err(WeaselError::EntityNotFound(self.id.clone()));
+        }
+        Ok(())
+    }
+
+    fn apply(&self, battle: &mut Battle<R>, event_queue: &mut Option<EventQueue<R>>) {
+        // Find the entity.
+        let entity = battle
+            .state
+            .entities
+            .entity_mut(&self.id)
+            .unwrap_or_else(|| panic!("constraint violated: entity {:?} not found", self.id));
+        let space = &mut battle.state.space;
+        let path =
+            space
+                .rules
+                .displacement_path(&space.model, entity, &self.direction, self.distance);
+        let mut remaining = self.distance;
+        // Move one step at a time, until an obstacle is met.
+        for to in path {
+            if space
+                .check_move(PositionClaim::Displacement(entity), &to)
+                .is_err()
+            {
+                space.on_collision(
+                    entity,
+                    &to,
+                    remaining,
+                    event_queue,
+                    &mut battle.metrics.write_handle(),
+                );
+                break;
+            }
+            space.move_entity(
+                PositionClaim::Displacement(entity),
+                Some(&to),
+                event_queue,
+                &mut battle.metrics.write_handle(),
+            );
+            entity.set_position(to);
+            remaining = remaining.saturating_sub(1);
+        }
+    }
+
+    fn kind(&self) -> EventKind {
+        EventKind::DisplaceEntity
+    }
+
+    fn box_clone(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Trigger to build and fire a `DisplaceEntity` event.
+pub struct DisplaceEntityTrigger<'a, R, P>
+where
+    R: BattleRules,
+    P: EventProcessor<R>,
+{
+    processor: &'a mut P,
+    id: EntityId<R>,
+    direction: Direction<R>,
+    distance: Distance,
+}
+
+impl<'a, R, P> EventTrigger<'a, R, P> for DisplaceEntityTrigger<'a, R, P>
+where
+    R: BattleRules + 'static,
+    P: EventProcessor<R>,
+{
+    fn processor(&'a mut self) -> &'a mut P {
+        self.processor
+    }
+
+    /// Returns a `DisplaceEntity` event.
+    fn event(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(DisplaceEntity {
+            id: self.id.clone(),
+            direction: self.direction.clone(),
+            distance: self.distance,
+        })
+    }
+}
+
+/// An event to exchange the positions of two entities.
+///
+/// The swap is atomic: both entities must be able to take the other one's position,
+/// otherwise neither of them moves.
+///
+/// # Examples
+/// ```
+/// use weasel::{
+///     battle_rules, rules::empty::*, Battle, BattleController, BattleRules, CreateCreature,
+///     CreateTeam, EntityId, EventKind, EventTrigger, Server, SwapEntities,
+/// };
+///
+/// battle_rules! {}
+///
+/// let battle = Battle::builder(CustomRules::new()).build();
+/// let mut server = Server::builder(battle).build();
+///
+/// let team_id = 1;
+/// CreateTeam::trigger(&mut server, team_id).fire().unwrap();
+/// CreateCreature::trigger(&mut server, 1, team_id, ()).fire().unwrap();
+/// CreateCreature::trigger(&mut server, 2, team_id, ()).fire().unwrap();
+///
+/// SwapEntities::trigger(&mut server, EntityId::Creature(1), EntityId::Creature(2))
+///     .fire()
+///     .unwrap();
+/// assert_eq!(
+///     server.battle().history().events().iter().last().unwrap().kind(),
+///     EventKind::SwapEntities
+/// );
+/// ```
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct SwapEntities<R: BattleRules> {
+    #[cfg_attr(
+        feature = "serialization",
+        serde(bound(
+            serialize = "EntityId<R>: Serialize",
+            deserialize = "EntityId<R>: Deserialize<'de>"
+        ))
+    )]
+    first: EntityId<R>,
+
+    #[cfg_attr(
+        feature = "serialization",
+        serde(bound(
+            serialize = "EntityId<R>: Serialize",
+            deserialize = "EntityId<R>: Deserialize<'de>"
+        ))
+    )]
+    second: EntityId<R>,
+}
+
+impl<R: BattleRules> SwapEntities<R> {
+    /// Returns a trigger for this event.
+    pub fn trigger<P: EventProcessor<R>>(
+        processor: &mut P,
+        first: EntityId<R>,
+        second: EntityId<R>,
+    ) -> SwapEntitiesTrigger<'_, R, P> {
+        SwapEntitiesTrigger {
+            processor,
+            first,
+            second,
+        }
+    }
+
+    /// Returns the id of the first entity.
+    pub fn first(&self) -> &EntityId<R> {
+        &self.first
+    }
+
+    /// Returns the id of the second entity.
+    pub fn second(&self) -> &EntityId<R> {
+        &self.second
+    }
+
+    /// Moves `id` into `position`, in exchange with `other`.
+    fn swap(
+        &self,
+        battle: &mut Battle<R>,
+        id: &EntityId<R>,
+        other: &EntityId<R>,
+        position: Position<R>,
+        event_queue: &mut Option<EventQueue<R>>,
+    ) {
+        let entity = battle
+            .state
+            .entities
+            .entity_mut(id)
+            .unwrap_or_else(|| panic!("constraint violated: entity {:?} not found", id));
+        battle.state.space.move_entity(
+            PositionClaim::Swap(entity, other),
+            Some(&position),
+            event_queue,
+            &mut battle.metrics.write_handle(),
+        );
+        entity.set_position(position);
+    }
+}
+
+impl<R: BattleRules> Debug for SwapEntities<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "SwapEntities {{ first: {:?}, second: {:?} }}",
+            self.first, self.second
+        )
+    }
+}
+
+impl<R: BattleRules> Clone for SwapEntities<R> {
+    fn clone(&self) -> Self {
+        Self {
+            first: self.first.clone(),
+            second: self.second.clone(),
+        }
+    }
+}
+
+impl<R: BattleRules + 'static> Event<R> for SwapEntities<R> {
+    fn verify(&self, battle: &Battle<R>) -> WeaselResult<(), R> {
+        if self.first == self.second {
+            return Err(WeaselError::SelfSwap(self.first.clone()));
+        }
+        // Find the entities.
+        let first = battle
+            .entities()
+            .entity(&self.first)
+            .ok_or_else(|| WeaselError::EntityNotFound(self.first.clone()))?;
+        let second = battle
+            .entities()
+            .entity(&self.second)
+            .ok_or_else(|| WeaselError::EntityNotFound(self.second.clone()))?;
+        // Check both positions.
+        for (entity, other) in [(first, second), (second, first)].iter() {
+            battle
+                .space()
+                .check_move(
+                    PositionClaim::Swap(*entity, other.entity_id()),
+                    other.position(),
+                )
+                .map_err(|err| {
+                    WeaselError::PositionError(
+                        Some(entity.position().clone()),
+                        other.position().clone(),
+                        Box::new(err),
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
+    fn apply(&self, battle: &mut Battle<R>, event_queue: &mut Option<EventQueue<R>>) {
+        let position = |id: &EntityId<R>| {
+            battle
+                .entities()
+                .entity(id)
+                .unwrap_or_else(|| panic!("constraint violated: entity {:?} not found", id))
+                .position()
+                .clone()
+        };
+        let first_position = position(&self.first);
+        let second_position = position(&self.second);
+        self.swap(
+            battle,
+            &self.first,
+            &self.second,
+            second_position,
+            event_queue,
+        );
+        self.swap(
+            battle,
+            &self.second,
+            &self.first,
+            first_position,
+            event_queue,
+        );
+    }
+
+    fn kind(&self) -> EventKind {
+        EventKind::SwapEntities
+    }
+
+    fn box_clone(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Trigger to build and fire a `SwapEntities` event.
+pub struct SwapEntitiesTrigger<'a, R, P>
+where
+    R: BattleRules,
+    P: EventProcessor<R>,
+{
+    processor: &'a mut P,
+    first: EntityId<R>,
+    second: EntityId<R>,
+}
+
+impl<'a, R, P> EventTrigger<'a, R, P> for SwapEntitiesTrigger<'a, R, P>
+where
+    R: BattleRules + 'static,
+    P: EventProcessor<R>,
+{
+    fn processor(&'a mut self) -> &'a mut P {
+        self.processor
+    }
+
+    /// Returns a `SwapEntities` event.
+    fn event(&self) -> Box<dyn Event<R> + Send> {
+        Box::new(SwapEntities {
+            first: self.first.clone(),
+            second: self.second.clone(),
+        })
+    }
+}
+
 /// Event to reset the space model.
 ///
 /// # Examples
@@ -1,7 +1,9 @@
 //! Teams of entities.
 
 use crate::battle::{Battle, BattleRules, BattleState};
+use crate::character::Character;
 use crate::creature::{Creature, CreatureId};
+use crate::entity::Entities;
 use crate::entropy::Entropy;
 use crate::error::{WeaselError, WeaselResult};
 use crate::event::{Event, EventKind, EventProcessor, EventQueue, EventTrigger};
@@ -44,6 +46,16 @@ impl<R: BattleRules> Team<R> {
         Box::new(self.creatures.iter())
     }
 
+    /// Returns an iterator over the creatures of this team which are not incapacitated.
+    pub fn active_creatures<'a>(
+        &'a self,
+        entities: &'a Entities<R>,
+    ) -> impl Iterator<Item = &'a CreatureId<R>> + 'a {
+        self.creatures.iter().filter(move |id| {
+            matches!(entities.creature(id), Some(creature) if !creature.is_incapacitated())
+        })
+    }
+
     pub(crate) fn creatures_mut(&mut self) -> &mut Vec<CreatureId<R>> {
         &mut self.creatures
     }
@@ -227,7 +239,9 @@ pub trait TeamRules<R: BattleRules> {
     }
 
     /// Checks if the team has completed its objectives.
-    /// This check is called after every event.
+    /// This check is called after every event.\
+    /// Incapacitated creatures remain in the battle; see `Team::active_creatures`
+    /// to consider only those still able to fight.
     ///
     /// The provided implementation does not return any conclusion.\
     /// If you set team `Conclusion` manually, you may avoid implementing this method.
@@ -1,13 +1,13 @@
 //! User defined extension for battle rules functionalities.
 
-use crate::battle::BattleRules;
+use crate::battle::{BattleRules, BattleState};
 #[cfg(feature = "serialization")]
 use crate::error::{WeaselError, WeaselResult};
 #[cfg(feature = "serialization")]
 use crate::event::Event;
 #[cfg(feature = "serialization")]
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
 /// Numerical identifier to distinguish user events.
@@ -20,11 +20,40 @@ pub trait UserRules<R: BattleRules> {
     #[cfg(feature = "serialization")]
     /// See [UserEventPackage](type.UserEventPackage.html).
     type UserEventPackage: UserEventPacker<R>;
+
+    #[cfg(not(feature = "serialization"))]
+    /// See [UserCondition](type.UserCondition.html).
+    type UserCondition: Clone + Debug + Send;
+    #[cfg(feature = "serialization")]
+    /// See [UserCondition](type.UserCondition.html).
+    type UserCondition: Clone + Debug + Send + Serialize + for<'a> Deserialize<'a>;
+
+    #[cfg(not(feature = "serialization"))]
+    /// See [UserError](type.UserError.html).
+    type UserError: Clone + Debug + Display + PartialEq + Send;
+    #[cfg(feature = "serialization")]
+    /// See [UserError](type.UserError.html).
+    type UserError: Clone + Debug + Display + PartialEq + Send + Serialize + for<'a> Deserialize<'a>;
+
+    /// Evaluates an user defined condition against the current state of the battle.
+    ///
+    /// The provided implementation always returns true.
+    fn check_condition(&self, _state: &BattleState<R>, _condition: &Self::UserCondition) -> bool {
+        true
+    }
 }
 
 /// Id of user defined metrics.
 pub type UserMetricId<R> = <<R as BattleRules>::UR as UserRules<R>>::UserMetricId;
 
+/// Type to describe domain specific errors returned by the rules.\
+/// It is carried by `WeaselError::UserError`. Use `String` for simple textual errors.
+pub type UserError<R> = <<R as BattleRules>::UR as UserRules<R>>::UserError;
+
+/// Type to describe user defined conditions for event prototypes.\
+/// Use `()` if you don't need custom conditions.
+pub type UserCondition<R> = <<R as BattleRules>::UR as UserRules<R>>::UserCondition;
+
 #[cfg(feature = "serialization")]
 /// Type containing the data to serialize and deserialize all defined user events.\
 /// Use `()` if you didn't define any user event.
@@ -1,3 +1,5 @@
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
 use weasel::ability::ActivateAbility;
 use weasel::actor::{Action, ActorRules};
 use weasel::battle::{Battle, BattleController, BattleRules, BattleState};
@@ -7,6 +9,7 @@ use weasel::event::{DummyEvent, EventKind, EventQueue, EventRights, EventServer,
 use weasel::metric::WriteMetrics;
 use weasel::player::PlayerId;
 use weasel::rules::empty::EmptyAbility;
+use weasel::user::UserRules;
 use weasel::{
     battle_rules, battle_rules_with_actor, rules::empty::*, Server, WeaselError, WeaselResult,
 };
@@ -186,3 +189,113 @@ fn ability_rights() {
     // Check that now he can activate the ability.
     assert_eq!(server.process_client(event).err(), None);
 }
+
+#[test]
+fn user_error_in_activable() {
+    // Domain specific errors.
+    #[derive(Clone, Debug, PartialEq)]
+    #[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+    enum ManaError {
+        NotEnoughMana(u32),
+    }
+
+    impl std::fmt::Display for ManaError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::NotEnoughMana(mana) => write!(f, "not enough mana: {}", mana),
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct ManaUserRules {}
+
+    impl UserRules<CustomRules> for ManaUserRules {
+        type UserMetricId = u32;
+        #[cfg(feature = "serialization")]
+        type UserEventPackage = ();
+        type UserCondition = ();
+        type UserError = ManaError;
+    }
+
+    #[derive(Default)]
+    struct ManaActorRules {}
+
+    impl ActorRules<CustomRules> for ManaActorRules {
+        type Ability = EmptyAbility;
+        type AbilitiesSeed = ();
+        type Activation = u32;
+        type AbilitiesAlteration = ();
+
+        fn generate_abilities(
+            &self,
+            _: &Option<Self::AbilitiesSeed>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Box<dyn Iterator<Item = Self::Ability>> {
+            let v = vec![EmptyAbility { id: ABILITY_ID }];
+            Box::new(v.into_iter())
+        }
+
+        fn activable(
+            &self,
+            _state: &BattleState<CustomRules>,
+            action: Action<CustomRules>,
+        ) -> WeaselResult<(), CustomRules> {
+            // Each ability costs 5 mana.
+            match action.activation {
+                Some(mana) if *mana >= 5 => Ok(()),
+                Some(mana) => Err(WeaselError::UserError(ManaError::NotEnoughMana(*mana))),
+                None => Err(WeaselError::UserError(ManaError::NotEnoughMana(0))),
+            }
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        EmptyCharacterRules,
+        ManaActorRules,
+        EmptyFightRules,
+        ManaUserRules,
+        EmptySpaceRules,
+        EmptyRoundsRules,
+        EmptyEntropyRules
+    }
+
+    let entity_id = EntityId::Creature(CREATURE_1_ID);
+    // Create a server with a creature.
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, ());
+    util::start_turn(&mut server, &entity_id);
+    // Check that the user error is nested inside `AbilityNotActivable`.
+    let error = ActivateAbility::trigger(&mut server, entity_id, ABILITY_ID)
+        .activation(2)
+        .fire()
+        .err()
+        .map(|e| e.unfold());
+    assert_eq!(
+        error,
+        Some(WeaselError::AbilityNotActivable(
+            entity_id,
+            ABILITY_ID,
+            Box::new(WeaselError::UserError(ManaError::NotEnoughMana(2)))
+        ))
+    );
+    assert_eq!(
+        error.as_ref().and_then(|e| e.user_error()),
+        Some(&ManaError::NotEnoughMana(2))
+    );
+    // User errors are displayed with their own formatting.
+    if let Some(WeaselError::AbilityNotActivable(_, _, inner)) = error {
+        assert_eq!(inner.to_string(), "user error: not enough mana: 2");
+    }
+    // Activate the ability with enough mana.
+    assert_eq!(
+        ActivateAbility::trigger(&mut server, entity_id, ABILITY_ID)
+            .activation(5)
+            .fire()
+            .err(),
+        None
+    );
+}
@@ -89,3 +89,184 @@ fn end_battle_during_events() {
     );
     assert_eq!(server.battle().phase(), BattlePhase::Ended);
 }
+
+#[test]
+fn deployment() {
+    use weasel::battle::{Battle, StartBattle};
+    use weasel::creature::CreateCreature;
+    use weasel::entity::Entity;
+    use weasel::metric::ReadMetrics;
+    use weasel::round::EnvironmentTurn;
+    use weasel::server::Server;
+    use weasel::space::{MoveEntity, PositionClaim, SpaceRules};
+    use weasel::team::{Conclusion, Team, TeamId, TeamRules};
+    use weasel::WeaselResult;
+
+    const TEAM_2_ID: u32 = 2;
+    const TEAM_3_ID: u32 = 3;
+    const CREATURE_2_ID: u32 = 2;
+    // Positions from 0 to `FRONT` are reserved to the first team, the others to the second.
+    const FRONT: u32 = 4;
+
+    #[derive(Default)]
+    struct DeploymentSpaceRules {}
+
+    impl SpaceRules<CustomRules> for DeploymentSpaceRules {
+        type Position = u32;
+        type SpaceSeed = ();
+        type SpaceModel = ();
+        type SpaceAlteration = ();
+        type Direction = ();
+
+        fn generate_model(&self, _: &Option<Self::SpaceSeed>) -> Self::SpaceModel {}
+
+        fn check_move(
+            &self,
+            _model: &Self::SpaceModel,
+            _claim: PositionClaim<CustomRules>,
+            _position: &Self::Position,
+        ) -> WeaselResult<(), CustomRules> {
+            Ok(())
+        }
+
+        fn in_deployment_zone(
+            &self,
+            _model: &Self::SpaceModel,
+            team_id: &TeamId<CustomRules>,
+            position: &Self::Position,
+        ) -> bool {
+            (*team_id == TEAM_1_ID) == (*position <= FRONT)
+        }
+    }
+
+    #[derive(Default)]
+    struct DeploymentTeamRules {}
+
+    impl TeamRules<CustomRules> for DeploymentTeamRules {
+        type Id = u32;
+        type Power = EmptyPower;
+        type PowersSeed = ();
+        type Invocation = ();
+        type PowersAlteration = ();
+        type ObjectivesSeed = ();
+        type Objectives = ();
+
+        fn check_objectives_on_event(
+            &self,
+            _state: &BattleState<CustomRules>,
+            team: &Team<CustomRules>,
+            _metrics: &ReadMetrics<CustomRules>,
+        ) -> Option<Conclusion> {
+            // A team without creatures is defeated.
+            if team.creatures().next().is_none() {
+                Some(Conclusion::Defeat)
+            } else {
+                None
+            }
+        }
+    }
+
+    battle_rules! {
+        DeploymentTeamRules,
+        EmptyCharacterRules,
+        EmptyActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        DeploymentSpaceRules,
+        EmptyRoundsRules,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let battle = Battle::builder(CustomRules::new()).deployment().build();
+    let mut server = Server::builder(battle).build();
+    assert_eq!(server.battle().phase(), BattlePhase::Deployment);
+    util::team(&mut server, TEAM_1_ID);
+    util::team(&mut server, TEAM_2_ID);
+    util::team(&mut server, TEAM_3_ID);
+    // Objectives are not checked during deployment.
+    assert!(server
+        .battle()
+        .entities()
+        .teams()
+        .all(|team| team.conclusion().is_none()));
+    // Creatures must be placed inside their team's zone.
+    assert_eq!(
+        CreateCreature::trigger(&mut server, CREATURE_1_ID, TEAM_1_ID, FRONT + 1)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::OutsideDeploymentZone(TEAM_1_ID, FRONT + 1))
+    );
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, FRONT);
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, FRONT + 1)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::OutsideDeploymentZone(TEAM_1_ID, FRONT + 1))
+    );
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, 0)
+            .fire()
+            .err(),
+        None
+    );
+    // Turns can't start.
+    assert_eq!(
+        StartTurn::trigger(&mut server, entity_1_id)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::BattleNotStarted)
+    );
+    assert_eq!(
+        EnvironmentTurn::trigger(&mut server)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::BattleNotStarted)
+    );
+    // Teams can deploy secretly and simultaneously during a planning phase.
+    util::creature(&mut server, CREATURE_2_ID, TEAM_2_ID, FRONT + 1);
+    let deploy = |server: &mut Server<CustomRules>, id, position| {
+        MoveEntity::trigger(server, EntityId::Creature(id), position)
+            .prototype()
+            .client_prototype(0, None)
+    };
+    let position = |server: &Server<CustomRules>, id| {
+        *server.battle().entities().creature(&id).unwrap().position()
+    };
+    assert_eq!(server.start_planning(None).err(), None);
+    let orders = vec![deploy(&mut server, CREATURE_1_ID, 2)];
+    assert_eq!(server.submit_orders(TEAM_1_ID, orders).err(), None);
+    assert_eq!(position(&server, CREATURE_1_ID), 0);
+    assert_eq!(server.submit_orders(TEAM_3_ID, Vec::new()).err(), None);
+    let orders = vec![deploy(&mut server, CREATURE_2_ID, FRONT + 3)];
+    assert_eq!(server.submit_orders(TEAM_2_ID, orders).err(), None);
+    assert_eq!(position(&server, CREATURE_1_ID), 2);
+    assert_eq!(position(&server, CREATURE_2_ID), FRONT + 3);
+    // Start the battle.
+    assert_eq!(StartBattle::trigger(&mut server).fire().err(), None);
+    assert_eq!(server.battle().phase(), BattlePhase::Started);
+    let conclusion = |server: &Server<CustomRules>, id| {
+        server.battle().entities().team(&id).unwrap().conclusion()
+    };
+    assert_eq!(conclusion(&server, TEAM_1_ID), None);
+    assert_eq!(conclusion(&server, TEAM_3_ID), Some(Conclusion::Defeat));
+    assert_eq!(
+        StartBattle::trigger(&mut server)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::BattleAlreadyStarted)
+    );
+    // Deployment zones no longer apply.
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, FRONT + 2)
+            .fire()
+            .err(),
+        None
+    );
+    util::start_turn(&mut server, &entity_1_id);
+}
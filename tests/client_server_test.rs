@@ -3,8 +3,9 @@ use std::sync::{Arc, Mutex};
 use weasel::battle::{Battle, BattleController, BattleRules};
 use weasel::entity::EntityId;
 use weasel::event::{
-    ClientEventPrototype, ClientSink, DummyEvent, EventKind, EventReceiver, EventServer, EventSink,
-    EventSinkId, EventTrigger, ServerSink, VersionedEventWrapper,
+    ClientEventPrototype, ClientSink, Conditional, DummyEvent, EventCondition, EventKind,
+    EventReceiver, EventServer, EventSink, EventSinkId, EventTrigger, ServerSink,
+    VersionedEventWrapper,
 };
 use weasel::player::PlayerId;
 use weasel::round::StartTurn;
@@ -545,6 +546,74 @@ fn server_only_events() {
     );
 }
 
+#[test]
+fn replicated_conditions() {
+    // Create a client and a server.
+    let server = Arc::new(Mutex::new(util::server(CustomRules::new())));
+    let mut server_sink = TestServerSink::new(SERVER_1_ID, server.clone());
+    let client = Arc::new(Mutex::new(util::client(
+        CustomRules::new(),
+        server_sink.clone(),
+    )));
+    let mut client_sink = TestClientSink::new(CLIENT_1_ID, client.clone());
+    add_sink!(server, client_sink);
+    // Verify that client checks the condition.
+    assert_eq!(
+        Conditional::with_event_condition(
+            DummyEvent::trigger(&mut *client.lock().unwrap()),
+            EventCondition::TeamExists(TEAM_1_ID),
+        )
+        .fire()
+        .err(),
+        Some(WeaselError::ConditionUnsatisfied)
+    );
+    // Verify that the condition is carried over to the server.
+    let event = Conditional::with_event_condition(
+        DummyEvent::trigger(&mut *client.lock().unwrap()),
+        EventCondition::TeamExists(TEAM_1_ID),
+    )
+    .prototype()
+    .client_prototype(0, None);
+    assert!(event.condition().is_some());
+    assert_eq!(
+        server_sink.send(&event).err(),
+        Some(WeaselError::ConditionUnsatisfied)
+    );
+    // Satisfy the condition on the server only.
+    util::team(&mut *server.lock().unwrap(), TEAM_1_ID);
+    assert_eq!(server_sink.send(&event).err(), None);
+    assert_eq!(client_sink.receive().err(), None);
+    assert_eq!(events!(server).len(), 2);
+    assert_eq!(events!(client).len(), 2);
+    // Check combinations of conditions.
+    let condition = EventCondition::All(vec![
+        EventCondition::TurnReady,
+        EventCondition::Not(Box::new(EventCondition::EntityExists(ENTITY_1_ID))),
+    ]);
+    assert_eq!(
+        Conditional::with_event_condition(
+            DummyEvent::trigger(&mut *client.lock().unwrap()),
+            condition
+        )
+        .fire()
+        .err(),
+        None
+    );
+    let condition = EventCondition::Any(vec![
+        EventCondition::Acting(ENTITY_1_ID),
+        EventCondition::EntityExists(ENTITY_1_ID),
+    ]);
+    assert_eq!(
+        Conditional::with_event_condition(
+            DummyEvent::trigger(&mut *client.lock().unwrap()),
+            condition
+        )
+        .fire()
+        .err(),
+        Some(WeaselError::ConditionUnsatisfied)
+    );
+}
+
 #[cfg(feature = "serialization")]
 #[test]
 fn client_server_serde() {
@@ -634,3 +703,318 @@ fn client_server_serde() {
         ]
     );
 }
+
+#[cfg(feature = "serialization")]
+#[test]
+fn client_event_condition_serde() {
+    use weasel::serde::FlatClientEvent;
+
+    // Create a server.
+    let server = Arc::new(Mutex::new(util::server(CustomRules::new())));
+    let mut server_sink = TestServerSink::new(SERVER_1_ID, server.clone());
+    // Serialize a client event with a condition.
+    let event = Conditional::with_event_condition(
+        DummyEvent::trigger(&mut *server.lock().unwrap()),
+        EventCondition::TeamExists(TEAM_1_ID),
+    )
+    .prototype()
+    .client_prototype(0, None);
+    let json = serde_json::to_string(&FlatClientEvent::from(event)).unwrap();
+    // Deserialize it and check that the condition is still enforced.
+    let flat: FlatClientEvent<CustomRules> = serde_json::from_str(&json).unwrap();
+    assert!(flat.condition().is_some());
+    let event: ClientEventPrototype<CustomRules> = flat.into();
+    assert_eq!(
+        server_sink.send(&event).err(),
+        Some(WeaselError::ConditionUnsatisfied)
+    );
+    util::team(&mut *server.lock().unwrap(), TEAM_1_ID);
+    assert_eq!(server_sink.send(&event).err(), None);
+}
+
+#[test]
+fn planning_phase() {
+    use weasel::ability::ActivateAbility;
+    use weasel::actor::ActorRules;
+    use weasel::entity::Entities;
+    use weasel::entropy::Entropy;
+    use weasel::metric::WriteMetrics;
+    use weasel::round::RoundsRules;
+    use weasel::team::TeamId;
+
+    const TEAM_2_ID: u32 = 2;
+    const CREATURE_2_ID: u32 = 2;
+    const ABILITY_ID: u32 = 1;
+
+    #[derive(Default)]
+    struct CustomActorRules {}
+
+    impl ActorRules<CustomRules> for CustomActorRules {
+        type Ability = EmptyAbility;
+        type AbilitiesSeed = ();
+        type Activation = ();
+        type AbilitiesAlteration = ();
+
+        fn generate_abilities(
+            &self,
+            _: &Option<Self::AbilitiesSeed>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Box<dyn Iterator<Item = Self::Ability>> {
+            let v = vec![EmptyAbility { id: ABILITY_ID }];
+            Box::new(v.into_iter())
+        }
+    }
+
+    #[derive(Default)]
+    struct ReverseRoundsRules {}
+
+    impl RoundsRules<CustomRules> for ReverseRoundsRules {
+        type RoundsSeed = ();
+        type RoundsModel = ();
+        type Phase = ();
+
+        fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {}
+
+        fn order_batches(
+            &self,
+            _model: &Self::RoundsModel,
+            _entities: &Entities<CustomRules>,
+            teams: &[TeamId<CustomRules>],
+        ) -> Vec<TeamId<CustomRules>> {
+            // Resolve the orders of the last team first.
+            teams.iter().rev().cloned().collect()
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        EmptyCharacterRules,
+        CustomActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        EmptySpaceRules,
+        ReverseRoundsRules,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    // Create a server with two teams.
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::team(&mut server, TEAM_2_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, ());
+    util::creature(&mut server, CREATURE_2_ID, TEAM_2_ID, ());
+    assert_eq!(
+        StartTurn::trigger_with_actors(&mut server, vec![entity_1_id, entity_2_id])
+            .fire()
+            .err(),
+        None
+    );
+    let order = |server: &mut Server<CustomRules>, id| {
+        ActivateAbility::trigger(server, id, ABILITY_ID)
+            .prototype()
+            .client_prototype(0, None)
+    };
+    // Start the planning phase.
+    assert_eq!(server.start_planning(Some(100)).err(), None);
+    assert_eq!(
+        server.start_planning(None).err(),
+        Some(WeaselError::PlanningInProgress)
+    );
+    // Client events can't be processed during planning.
+    let event = order(&mut server, entity_1_id);
+    assert_eq!(
+        server.process_client(event).err(),
+        Some(WeaselError::PlanningInProgress)
+    );
+    // Submit the orders of the first team. They are buffered.
+    let history_len = server.battle().history().len() as usize;
+    let orders = vec![order(&mut server, entity_1_id)];
+    assert_eq!(server.submit_orders(TEAM_1_ID, orders).err(), None);
+    assert_eq!(server.battle().history().len() as usize, history_len);
+    let planning = server.planning().unwrap();
+    assert!(planning.has_submitted(&TEAM_1_ID));
+    assert_eq!(planning.pending().collect::<Vec<_>>(), vec![&TEAM_2_ID]);
+    assert_eq!(
+        server.submit_orders(TEAM_1_ID, Vec::new()).err(),
+        Some(WeaselError::OrdersAlreadySubmitted(TEAM_1_ID))
+    );
+    // The planning doesn't expire before its deadline.
+    assert_eq!(server.expire_planning(50).err(), None);
+    assert!(server.planning().is_some());
+    // Orders are resolved as soon as every team has submitted them.
+    let orders = vec![order(&mut server, entity_2_id)];
+    assert_eq!(server.submit_orders(TEAM_2_ID, orders).err(), None);
+    assert!(server.planning().is_none());
+    let events = server.battle().history().events();
+    assert_eq!(events.len(), history_len + 2);
+    let moved: Vec<_> = events[history_len..]
+        .iter()
+        .map(|event| {
+            assert_eq!(event.kind(), EventKind::ActivateAbility);
+            event
+                .as_any()
+                .downcast_ref::<ActivateAbility<CustomRules>>()
+                .unwrap()
+                .entity_id()
+        })
+        .collect();
+    assert_eq!(moved, vec![&entity_2_id, &entity_1_id]);
+    // Teams can't give orders to other teams' entities.
+    assert_eq!(server.start_planning(Some(100)).err(), None);
+    let orders = vec![order(&mut server, entity_2_id)];
+    assert_eq!(server.submit_orders(TEAM_1_ID, orders).err(), None);
+    assert_eq!(
+        server.expire_planning(100).err(),
+        Some(WeaselError::AuthenticationError(None, TEAM_2_ID))
+    );
+    assert!(server.planning().is_none());
+    assert_eq!(server.battle().history().len() as usize, history_len + 2);
+}
+
+#[test]
+fn turn_deadlines() {
+    use indexmap::IndexSet;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use weasel::ability::ActivateAbility;
+    use weasel::actor::ActorRules;
+    use weasel::clock::{Clock, Timestamp};
+    use weasel::entity::Entities;
+    use weasel::entropy::Entropy;
+    use weasel::event::EventQueue;
+    use weasel::metric::WriteMetrics;
+    use weasel::round::{EndTurn, RoundsRules, TurnState};
+
+    const CREATURE_2_ID: u32 = 2;
+    const ABILITY_ID: u32 = 1;
+    const TIME_LIMIT: Timestamp = 100;
+
+    /// A clock whose time is set manually.
+    #[derive(Clone, Default)]
+    struct ManualClock {
+        time: Arc<AtomicU64>,
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Timestamp {
+            self.time.load(Ordering::SeqCst)
+        }
+    }
+
+    #[derive(Default)]
+    struct CustomActorRules {}
+
+    impl ActorRules<CustomRules> for CustomActorRules {
+        type Ability = EmptyAbility;
+        type AbilitiesSeed = ();
+        type Activation = ();
+        type AbilitiesAlteration = ();
+
+        fn generate_abilities(
+            &self,
+            _: &Option<Self::AbilitiesSeed>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Box<dyn Iterator<Item = Self::Ability>> {
+            let v = vec![EmptyAbility { id: ABILITY_ID }];
+            Box::new(v.into_iter())
+        }
+    }
+
+    #[derive(Default)]
+    struct FallbackRoundsRules {}
+
+    impl RoundsRules<CustomRules> for FallbackRoundsRules {
+        type RoundsSeed = ();
+        type RoundsModel = ();
+        type Phase = ();
+
+        fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {}
+
+        fn on_turn_expired(
+            &self,
+            _entities: &Entities<CustomRules>,
+            _model: &Self::RoundsModel,
+            actors: &IndexSet<EntityId<CustomRules>>,
+            event_queue: &mut Option<EventQueue<CustomRules>>,
+        ) {
+            // The second creature activates an ability before ending its turn.
+            let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+            if actors.contains(&entity_2_id) {
+                ActivateAbility::trigger(event_queue, entity_2_id, ABILITY_ID).fire();
+                EndTurn::trigger(event_queue).fire();
+            }
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        EmptyCharacterRules,
+        CustomActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        EmptySpaceRules,
+        FallbackRoundsRules,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    // Create a server with a clock and a time limit for turns.
+    let clock = ManualClock::default();
+    let battle = Battle::builder(CustomRules::new()).build();
+    let server = Server::builder(battle)
+        .clock(Box::new(clock.clone()))
+        .turn_time_limit(TIME_LIMIT)
+        .build();
+    let server = Arc::new(Mutex::new(server));
+    let server_sink = TestServerSink::new(SERVER_1_ID, server.clone());
+    // Connect a client to the server.
+    let client = Arc::new(Mutex::new(util::client(CustomRules::new(), server_sink)));
+    let mut client_sink = TestClientSink::new(CLIENT_1_ID, client.clone());
+    add_sink!(server, client_sink);
+    let shared_server = server;
+    let mut server = shared_server.lock().unwrap();
+    util::team(&mut *server, TEAM_1_ID);
+    util::creature(&mut *server, CREATURE_1_ID, TEAM_1_ID, ());
+    util::creature(&mut *server, CREATURE_2_ID, TEAM_1_ID, ());
+    // The deadline is computed from the start of the turn.
+    clock.time.store(10, Ordering::SeqCst);
+    util::start_turn(&mut *server, &entity_1_id);
+    assert_eq!(server.turn_deadline(), Some(10 + TIME_LIMIT));
+    assert_eq!(server.tick(50).err(), None);
+    assert!(server.battle().rounds().state().has_actor(&entity_1_id));
+    // Expired turns are ended by default.
+    assert_eq!(server.tick(10 + TIME_LIMIT).err(), None);
+    assert_eq!(*server.battle().rounds().state(), TurnState::Ready);
+    assert_eq!(server.turn_deadline(), None);
+    let last_kind =
+        |server: &Server<CustomRules>| server.battle().history().events().last().unwrap().kind();
+    assert_eq!(last_kind(&server), EventKind::EndTurn);
+    // Rules can provide a fallback action.
+    clock.time.store(200, Ordering::SeqCst);
+    util::start_turn(&mut *server, &entity_2_id);
+    let history_len = server.battle().history().len() as usize;
+    assert_eq!(server.tick(200 + TIME_LIMIT).err(), None);
+    assert_eq!(*server.battle().rounds().state(), TurnState::Ready);
+    let kinds: Vec<_> = server.battle().history().events()[history_len..]
+        .iter()
+        .map(|event| event.kind())
+        .collect();
+    assert_eq!(kinds, vec![EventKind::ActivateAbility, EventKind::EndTurn]);
+    // Without a clock, the deadline starts at the first tick.
+    server.set_clock(None);
+    util::start_turn(&mut *server, &entity_1_id);
+    assert_eq!(server.turn_deadline(), None);
+    assert_eq!(server.tick(400).err(), None);
+    assert_eq!(server.turn_deadline(), Some(400 + TIME_LIMIT));
+    assert_eq!(server.tick(400 + TIME_LIMIT).err(), None);
+    assert_eq!(last_kind(&server), EventKind::EndTurn);
+    // Events fired on deadlines are sent to clients.
+    drop(server);
+    assert_eq!(client_sink.receive().err(), None);
+    assert_eq!(events!(client).len(), events!(shared_server).len());
+    assert_eq!(events!(client).last().unwrap().kind(), EventKind::EndTurn);
+}
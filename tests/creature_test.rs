@@ -1,20 +1,22 @@
 use std::cell::RefCell;
 use std::collections::HashSet;
-use weasel::ability::AbilityId;
+use weasel::ability::{AbilityId, ActivateAbility};
 use weasel::actor::{Actor, ActorRules, RegenerateAbilities};
 use weasel::battle::{BattleController, BattleRules, BattleState};
 use weasel::character::{
     AlterStatistics, Character, CharacterRules, RegenerateStatistics, StatisticId,
+    TransmuteCharacter,
 };
 use weasel::creature::{CreateCreature, RemoveCreature};
 use weasel::entity::{EntityId, RemoveEntity, Transmutation};
 use weasel::entropy::Entropy;
 use weasel::event::{EventQueue, EventTrigger};
-use weasel::metric::{system::*, WriteMetrics};
-use weasel::round::{RoundsRules, TurnState};
+use weasel::metric::{system::*, ReadMetrics, WriteMetrics};
+use weasel::round::{RoundsRules, StartTurn, TurnState};
 use weasel::rules::empty::{EmptyAbility, EmptyStat};
 use weasel::rules::{ability::SimpleAbility, statistic::SimpleStatistic};
-use weasel::space::{PositionClaim, SpaceRules};
+use weasel::space::{MoveEntity, PositionClaim, SpaceRules};
+use weasel::team::{Conclusion, Team, TeamRules};
 use weasel::user::UserMetricId;
 use weasel::{
     battle_rules, battle_rules_with_actor, battle_rules_with_character, rules::empty::*,
@@ -398,6 +400,7 @@ fn remove_creature() {
         // Collection to keep track of how many times on_actor_removed is called
         // and with what entity_id.
         type RoundsModel = Vec<EntityId<CustomRules>>;
+        type Phase = ();
 
         fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {
             Vec::new()
@@ -422,6 +425,7 @@ fn remove_creature() {
         type SpaceSeed = ();
         type SpaceModel = HashSet<Self::Position>;
         type SpaceAlteration = ();
+        type Direction = ();
 
         fn generate_model(&self, _: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
             HashSet::new()
@@ -445,6 +449,7 @@ fn remove_creature() {
             model: &mut Self::SpaceModel,
             claim: PositionClaim<CustomRules>,
             position: Option<&Self::Position>,
+            _event_queue: &mut Option<EventQueue<CustomRules>>,
             _metrics: &mut WriteMetrics<CustomRules>,
         ) {
             if let Some(position) = position {
@@ -571,6 +576,199 @@ fn remove_creature_on_alter() {
     assert!(entities.creature(&CREATURE_1_ID).is_none());
 }
 
+#[test]
+fn incapacitate_and_revive() {
+    #[derive(Default)]
+    struct CustomCharacterRules {}
+
+    impl CharacterRules<CustomRules> for CustomCharacterRules {
+        type CreatureId = u32;
+        type ObjectId = ();
+        type Statistic = SimpleStatistic<u32, i32>;
+        type StatisticsSeed = ();
+        type StatisticsAlteration = i32;
+        type Status = EmptyStatus;
+        type StatusesAlteration = ();
+
+        fn generate_statistics(
+            &self,
+            _seed: &Option<Self::StatisticsSeed>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Box<dyn Iterator<Item = Self::Statistic>> {
+            Box::new(std::iter::once(SimpleStatistic::new(HEALTH_ID, 10)))
+        }
+
+        fn alter_statistics(
+            &self,
+            character: &mut dyn Character<CustomRules>,
+            alteration: &Self::StatisticsAlteration,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Option<Transmutation> {
+            let health = character.statistic_mut(&HEALTH_ID).unwrap();
+            health.add(*alteration);
+            if health.value() > 0 {
+                Some(Transmutation::REVIVAL)
+            } else {
+                Some(Transmutation::INCAPACITATION)
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct CustomTeamRules {}
+
+    impl TeamRules<CustomRules> for CustomTeamRules {
+        type Id = u32;
+        type Power = EmptyPower;
+        type PowersSeed = ();
+        type Invocation = ();
+        type PowersAlteration = ();
+        type ObjectivesSeed = ();
+        type Objectives = ();
+
+        fn check_objectives_on_event(
+            &self,
+            state: &BattleState<CustomRules>,
+            team: &Team<CustomRules>,
+            _metrics: &ReadMetrics<CustomRules>,
+        ) -> Option<Conclusion> {
+            // A team without active creatures is defeated.
+            if team.active_creatures(state.entities()).next().is_some() {
+                None
+            } else {
+                Some(Conclusion::Defeat)
+            }
+        }
+    }
+
+    battle_rules! {
+        CustomTeamRules,
+        CustomCharacterRules,
+        EmptyActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        EmptySpaceRules,
+        EmptyRoundsRules,
+        EmptyEntropyRules
+    }
+
+    const HEALTH_ID: u32 = 1;
+    const ENTITY_1_ID: EntityId<CustomRules> = EntityId::Creature(CREATURE_1_ID);
+    // Create a battle with two teams.
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::team(&mut server, TEAM_5_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, ());
+    util::creature(&mut server, CREATURE_5_ID, TEAM_5_ID, ());
+    // Knock out the creature during its turn.
+    util::start_turn(&mut server, &ENTITY_1_ID);
+    assert_eq!(
+        AlterStatistics::trigger(&mut server, ENTITY_1_ID, -10)
+            .fire()
+            .err(),
+        None
+    );
+    let entities = server.battle().entities();
+    assert!(entities
+        .creature(&CREATURE_1_ID)
+        .unwrap()
+        .is_incapacitated());
+    assert_eq!(entities.incapacitated_creatures().count(), 1);
+    assert_eq!(entities.active_creatures().count(), 1);
+    // The turn of an incapacitated creature ends immediately.
+    assert_eq!(*server.battle().rounds().state(), TurnState::Ready);
+    assert_eq!(server.battle().rounds().completed_turns(), 1);
+    // Incapacitated creatures can't act, move nor start a new turn.
+    assert_eq!(
+        ActivateAbility::trigger(&mut server, ENTITY_1_ID, 0)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::ActorNotReady(ENTITY_1_ID))
+    );
+    assert_eq!(
+        MoveEntity::trigger(&mut server, ENTITY_1_ID, ())
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::CharacterIncapacitated(ENTITY_1_ID))
+    );
+    assert_eq!(
+        StartTurn::trigger(&mut server, ENTITY_1_ID)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::ActorNotEligible(ENTITY_1_ID))
+    );
+    // Objectives see the team as defeated.
+    let entities = server.battle().entities();
+    let team = entities.team(&TEAM_1_ID).unwrap();
+    assert_eq!(team.conclusion(), Some(Conclusion::Defeat));
+    assert_eq!(team.creatures().count(), 1);
+    assert_eq!(team.active_creatures(entities).count(), 0);
+    // A creature can't be incapacitated twice.
+    assert_eq!(
+        TransmuteCharacter::trigger(&mut server, ENTITY_1_ID, Transmutation::INCAPACITATION)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::CharacterIncapacitated(ENTITY_1_ID))
+    );
+    // Further damage doesn't alter the creature's state.
+    assert_eq!(
+        AlterStatistics::trigger(&mut server, ENTITY_1_ID, -5)
+            .fire()
+            .err(),
+        None
+    );
+    // Revive the creature through an alteration.
+    assert_eq!(
+        AlterStatistics::trigger(&mut server, ENTITY_1_ID, 5)
+            .fire()
+            .err(),
+        None
+    );
+    let creature = server.battle().entities().creature(&CREATURE_1_ID).unwrap();
+    assert!(!creature.is_incapacitated());
+    assert_eq!(creature.statistic(&HEALTH_ID).unwrap().value(), 5);
+    util::start_turn(&mut server, &ENTITY_1_ID);
+    util::end_turn(&mut server);
+    // Only incapacitated creatures can be revived.
+    assert_eq!(
+        TransmuteCharacter::trigger(&mut server, ENTITY_1_ID, Transmutation::REVIVAL)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::CharacterNotIncapacitated(ENTITY_1_ID))
+    );
+    // Explicit transmutations work as well.
+    assert_eq!(
+        TransmuteCharacter::trigger(&mut server, ENTITY_1_ID, Transmutation::INCAPACITATION)
+            .fire()
+            .err(),
+        None
+    );
+    assert!(server
+        .battle()
+        .entities()
+        .creature(&CREATURE_1_ID)
+        .unwrap()
+        .is_incapacitated());
+    assert_eq!(
+        TransmuteCharacter::trigger(&mut server, ENTITY_1_ID, Transmutation::REMOVAL)
+            .fire()
+            .err(),
+        None
+    );
+    assert!(server
+        .battle()
+        .entities()
+        .creature(&CREATURE_1_ID)
+        .is_none());
+}
+
 #[test]
 fn character_existence_callbacks() {
     #[derive(Default)]
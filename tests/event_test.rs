@@ -4,24 +4,29 @@ use std::any::Any;
 use std::marker::PhantomData;
 use weasel::ability::ActivateAbility;
 use weasel::actor::{Action, Actor, ActorRules, AlterAbilities, RegenerateAbilities};
-use weasel::battle::{Battle, BattleController, BattleRules, BattleState, EndBattle};
-use weasel::character::{AlterStatistics, RegenerateStatistics};
+use weasel::battle::{Battle, BattleController, BattleRules, BattleState, EndBattle, StartBattle};
+use weasel::character::{AlterStatistics, RegenerateStatistics, TransmuteCharacter};
 use weasel::creature::{ConvertCreature, CreateCreature, RemoveCreature};
-use weasel::entity::EntityId;
+use weasel::entity::{EntityId, Transmutation};
 use weasel::entropy::{Entropy, EntropyModel, ResetEntropy};
 use weasel::event::{
-    ClientEventPrototype, Conditional, DefaultOutput, DummyEvent, Event, EventKind, EventProcessor,
-    EventQueue, EventSink, EventSinkId, EventTrigger, ServerSink,
+    ClientEventPrototype, Conditional, DefaultOutput, DummyEvent, Event, EventCondition, EventKind,
+    EventProcessor, EventQueue, EventSink, EventSinkId, EventTrigger, EventWrapper, ServerSink,
 };
 use weasel::fight::ApplyImpact;
 use weasel::metric::WriteMetrics;
 use weasel::object::{CreateObject, RemoveObject};
 use weasel::power::InvokePower;
-use weasel::round::{EndRound, EndTurn, EnvironmentTurn, ResetRounds, RoundsModel, StartTurn};
+use weasel::round::{
+    AdvancePhase, DelayTurn, EndRound, EndTurn, EnvironmentTurn, ResetRounds, RoundsModel,
+    StartTurn,
+};
 use weasel::rules::ability::SimpleAbility;
 #[cfg(feature = "serialization")]
 use weasel::serde::FlatEvent;
-use weasel::space::{AlterSpace, MoveEntity, ResetSpace, SpaceModel};
+use weasel::space::{
+    AlterSpace, DisplaceEntity, MoveAlongPath, MoveEntity, ResetSpace, SpaceModel, SwapEntities,
+};
 use weasel::status::{AlterStatuses, ClearStatus, InflictStatus};
 use weasel::team::{
     AlterPowers, ConcludeObjectives, Conclusion, CreateTeam, RegeneratePowers, Relation,
@@ -31,7 +36,7 @@ use weasel::team::{
 use weasel::user::UserEventPacker;
 use weasel::user::{UserMetricId, UserRules};
 use weasel::{battle_rules, battle_rules_with_actor, battle_rules_with_user, rules::empty::*};
-use weasel::{WeaselError, WeaselResult};
+use weasel::{Server, WeaselError, WeaselResult};
 
 #[cfg(feature = "serialization")]
 mod helper;
@@ -242,6 +247,51 @@ fn conditional() {
     assert_eq!(events[events.len() - 1].kind(), EventKind::DummyEvent);
 }
 
+#[test]
+fn user_condition() {
+    // Define custom user rules with a condition on the number of teams.
+    #[derive(Default)]
+    struct CustomUserRules {}
+
+    impl UserRules<CustomRules> for CustomUserRules {
+        type UserMetricId = u32;
+        #[cfg(feature = "serialization")]
+        type UserEventPackage = ();
+        type UserCondition = usize;
+        type UserError = String;
+
+        fn check_condition(&self, state: &BattleState<CustomRules>, condition: &usize) -> bool {
+            state.entities().teams().count() >= *condition
+        }
+    }
+
+    battle_rules_with_user! { CustomUserRules }
+    // Create a server.
+    let mut server = util::server(CustomRules::new());
+    // Fire an event with an user condition.
+    assert_eq!(
+        Conditional::with_event_condition(
+            DummyEvent::trigger(&mut server),
+            EventCondition::User(1)
+        )
+        .fire()
+        .err()
+        .map(|e| e.unfold()),
+        Some(WeaselError::ConditionUnsatisfied)
+    );
+    // Satisfy the condition and fire again.
+    util::team(&mut server, TEAM_1_ID);
+    assert_eq!(
+        Conditional::with_event_condition(
+            DummyEvent::trigger(&mut server),
+            EventCondition::User(1)
+        )
+        .fire()
+        .err(),
+        None
+    );
+}
+
 macro_rules! user_event_check {
     ($server: expr, $data: expr) => {{
         let event = &$server.battle().history().events()[0];
@@ -264,6 +314,8 @@ fn user_event() {
         type UserMetricId = u32;
         #[cfg(feature = "serialization")]
         type UserEventPackage = ();
+        type UserCondition = ();
+        type UserError = String;
     }
 
     battle_rules_with_user! { CustomUserRules }
@@ -330,6 +382,8 @@ fn user_event_serde() {
     impl UserRules<CustomRules> for CustomUserRules {
         type UserMetricId = u32;
         type UserEventPackage = Package;
+        type UserCondition = ();
+        type UserError = String;
     }
 
     battle_rules_with_user! { CustomUserRules }
@@ -366,10 +420,15 @@ macro_rules! events_vec {
         events.push(CreateCreature::trigger(&mut (), TEAM_1_ID, CREATURE_1_ID, ()).event());
         events.push(CreateObject::trigger(&mut (), OBJECT_1_ID, ()).event());
         events.push(MoveEntity::trigger(&mut (), ENTITY_1_ID, ()).event());
+        events.push(MoveAlongPath::trigger(&mut (), ENTITY_1_ID, vec![()]).event());
+        events.push(DisplaceEntity::trigger(&mut (), ENTITY_1_ID, (), 1).event());
+        events.push(SwapEntities::trigger(&mut (), ENTITY_1_ID, ENTITY_1_ID).event());
         events.push(StartTurn::trigger(&mut (), ENTITY_1_ID).event());
         events.push(EndTurn::trigger(&mut ()).event());
         events.push(EndRound::trigger(&mut ()).event());
         events.push(EnvironmentTurn::trigger(&mut ()).event());
+        events.push(AdvancePhase::trigger(&mut ()).event());
+        events.push(DelayTurn::trigger(&mut ()).event());
         events.push(ActivateAbility::trigger(&mut (), ENTITY_1_ID, ABILITY_1_ID).event());
         events.push(InvokePower::trigger(&mut (), TEAM_1_ID, POWER_1_ID).event());
         events.push(ApplyImpact::trigger(&mut (), ()).event());
@@ -378,6 +437,9 @@ macro_rules! events_vec {
         events.push(AlterAbilities::trigger(&mut (), ENTITY_1_ID, ()).event());
         events.push(AlterPowers::trigger(&mut (), TEAM_1_ID, ()).event());
         events.push(RegenerateStatistics::trigger(&mut (), ENTITY_1_ID.clone()).event());
+        events.push(
+            TransmuteCharacter::trigger(&mut (), ENTITY_1_ID, Transmutation::REVIVAL).event(),
+        );
         events.push(RegenerateAbilities::trigger(&mut (), ENTITY_1_ID.clone()).event());
         events.push(RegeneratePowers::trigger(&mut (), TEAM_1_ID.clone()).event());
         events.push(InflictStatus::trigger(&mut (), ENTITY_1_ID.clone(), STATUS_1_ID).event());
@@ -395,6 +457,7 @@ macro_rules! events_vec {
         events.push(ResetObjectives::trigger(&mut (), TEAM_1_ID).event());
         events.push(ResetRounds::trigger(&mut ()).event());
         events.push(ResetSpace::trigger(&mut ()).event());
+        events.push(StartBattle::trigger(&mut ()).event());
         events.push(EndBattle::trigger(&mut ()).event());
         events
     }};
@@ -469,3 +532,49 @@ fn event_processor_generic() {
     assert!(fire_event(&mut server).is_ok());
     assert!(fire_event(&mut client).is_ok());
 }
+
+#[test]
+fn cascade_limits() {
+    battle_rules! {}
+
+    // Each dummy event derives two more dummy events, endlessly.
+    let cb = |event: &EventWrapper<CustomRules>,
+              _: &BattleState<CustomRules>,
+              event_queue: &mut Option<EventQueue<CustomRules>>| {
+        if let EventKind::DummyEvent = event.kind() {
+            DummyEvent::trigger(event_queue).fire();
+            DummyEvent::trigger(event_queue).fire();
+        }
+    };
+    // Check that the cascade depth is limited.
+    let battle = Battle::builder(CustomRules::new())
+        .event_callback(Box::new(cb))
+        .build();
+    let mut server = Server::builder(battle).max_cascade_depth(5).build();
+    assert_eq!(
+        DummyEvent::trigger(&mut server).fire().err(),
+        Some(WeaselError::EventCascadeOverflow(
+            vec![0, 1, 2, 3, 4, 5],
+            DummyEvent::trigger(&mut ()).event()
+        ))
+    );
+    assert_eq!(server.battle().history().len(), 6);
+    // Check that the total number of derived events is limited.
+    let battle = Battle::builder(CustomRules::new())
+        .event_callback(Box::new(cb))
+        .build();
+    let mut server = Server::builder(battle).max_derived_events(10).build();
+    let result = DummyEvent::trigger(&mut server).fire();
+    assert!(matches!(
+        result,
+        Err(WeaselError::EventCascadeOverflow(_, _))
+    ));
+    assert_eq!(server.battle().history().len(), 11);
+    // Limits are enforced separately for each root event.
+    let result = DummyEvent::trigger(&mut server).fire();
+    assert!(matches!(
+        result,
+        Err(WeaselError::EventCascadeOverflow(_, _))
+    ));
+    assert_eq!(server.battle().history().len(), 22);
+}
@@ -1,8 +1,14 @@
 use std::convert::TryInto;
-use weasel::battle::{BattleController, BattleRules};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use weasel::battle::{Battle, BattleController, BattleRules};
+use weasel::clock::{Clock, Timestamp};
+use weasel::entity::EntityId;
 use weasel::entropy::ResetEntropy;
-use weasel::event::{EventId, EventKind, EventTrigger};
-use weasel::round::EndTurn;
+use weasel::event::{EventId, EventKind, EventServer, EventTrigger, EventWrapper, Tagged};
+use weasel::player::PlayerId;
+use weasel::round::{EndTurn, StartTurn};
+use weasel::server::Server;
 use weasel::{battle_rules, rules::empty::*};
 
 const TEAM_1_ID: u32 = 1;
@@ -30,3 +36,65 @@ fn timeline_populated() {
     assert_eq!(events[2].kind(), EventKind::ResetEntropy);
     assert_eq!(events[2].id(), len - 1);
 }
+
+/// A clock whose time is set manually.
+#[derive(Clone, Default)]
+struct ManualClock {
+    time: Arc<AtomicU64>,
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Timestamp {
+        self.time.load(Ordering::SeqCst)
+    }
+}
+
+#[test]
+fn metadata_queries() {
+    const PLAYER_1_ID: PlayerId = 1;
+    const TAG: &str = "tag";
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    // Create a server with a clock.
+    let clock = ManualClock::default();
+    let battle = Battle::builder(CustomRules::new()).build();
+    let mut server = Server::builder(battle)
+        .clock(Box::new(clock.clone()))
+        .build();
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, ());
+    // Fire an event on behalf of a player.
+    clock.time.store(10, Ordering::SeqCst);
+    let prototype = StartTurn::trigger(&mut (), entity_1_id)
+        .prototype()
+        .client_prototype(0, Some(PLAYER_1_ID));
+    assert_eq!(server.process_client(prototype).err(), None);
+    // Fire a tagged event.
+    clock.time.store(20, Ordering::SeqCst);
+    assert_eq!(
+        Tagged::new(EndTurn::trigger(&mut server), TAG.to_string())
+            .fire()
+            .err(),
+        None
+    );
+    util::dummy(&mut server);
+    // Check the metadata.
+    let history = server.battle().history();
+    let metadata = history.event(2).unwrap().metadata();
+    assert_eq!(metadata.timestamp(), Some(10));
+    assert_eq!(metadata.player(), Some(PLAYER_1_ID));
+    assert_eq!(metadata.completed_turns(), 0);
+    let metadata = history.event(4).unwrap().metadata();
+    assert_eq!(metadata.timestamp(), Some(20));
+    assert_eq!(metadata.player(), None);
+    assert_eq!(metadata.completed_turns(), 1);
+    // Check the queries.
+    let ids = |events: Vec<&EventWrapper<CustomRules>>| -> Vec<EventId> {
+        events.iter().map(|event| event.id()).collect()
+    };
+    assert_eq!(ids(history.by_player(PLAYER_1_ID).collect()), vec![2]);
+    assert_eq!(ids(history.tagged(TAG).collect()), vec![3]);
+    assert_eq!(ids(history.in_turn(0).collect()), vec![0, 1, 2, 3]);
+    assert_eq!(ids(history.in_round(0).collect()), vec![0, 1, 2, 3, 4]);
+    assert_eq!(ids(history.between(5..15).collect()), vec![2]);
+    assert_eq!(ids(history.between(0..100).collect()).len(), 5);
+}
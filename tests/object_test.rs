@@ -223,6 +223,7 @@ fn remove_object() {
         type SpaceSeed = ();
         type SpaceModel = HashSet<Self::Position>;
         type SpaceAlteration = ();
+        type Direction = ();
 
         fn generate_model(&self, _: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
             HashSet::new()
@@ -246,6 +247,7 @@ fn remove_object() {
             model: &mut Self::SpaceModel,
             claim: PositionClaim<CustomRules>,
             position: Option<&Self::Position>,
+            _event_queue: &mut Option<EventQueue<CustomRules>>,
             _metrics: &mut WriteMetrics<CustomRules>,
         ) {
             if let Some(position) = position {
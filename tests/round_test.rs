@@ -1,15 +1,20 @@
 use indexmap::indexset;
 #[cfg(feature = "serialization")]
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 use weasel::actor::Actor;
-use weasel::battle::{Battle, BattleController, BattleRules};
-use weasel::entity::{Entities, EntityId};
+use weasel::battle::{Battle, BattleController, BattleRules, BattleState};
+use weasel::character::TransmuteCharacter;
+use weasel::creature::RemoveCreature;
+use weasel::entity::{Entities, EntityId, Transmutation};
 use weasel::entropy::Entropy;
-use weasel::event::{EventProcessor, EventRights, EventServer, EventTrigger};
+use weasel::event::{
+    EventKind, EventProcessor, EventQueue, EventRights, EventServer, EventTrigger,
+};
 use weasel::metric::WriteMetrics;
 use weasel::player::PlayerId;
 use weasel::round::{
-    EndRound, EndTurn, EnvironmentTurn, ResetRounds, RoundsRules, StartTurn, TurnState,
+    DelayTurn, EndRound, EndTurn, EnvironmentTurn, ResetRounds, RoundsRules, StartTurn, TurnState,
 };
 use weasel::server::Server;
 use weasel::space::Space;
@@ -43,6 +48,7 @@ struct CustomRoundsRules {}
 impl RoundsRules<CustomRules> for CustomRoundsRules {
     type RoundsSeed = Model;
     type RoundsModel = Model;
+    type Phase = ();
 
     fn generate_model(&self, seed: &Option<Self::RoundsSeed>) -> Self::RoundsModel {
         match seed {
@@ -377,3 +383,1162 @@ fn end_round() {
     assert_eq!(EndRound::trigger(&mut server).fire().err(), None);
     assert_eq!(server.battle().rounds().completed_rounds(), 1);
 }
+
+#[test]
+fn automatic_round_end() {
+    #[derive(Default)]
+    struct RoundsModel {
+        round_ends: u32,
+        round_starts: u32,
+    }
+
+    #[derive(Default)]
+    struct AutoRoundsRules {}
+
+    impl RoundsRules<CustomRules> for AutoRoundsRules {
+        type RoundsSeed = ();
+        type RoundsModel = RoundsModel;
+        type Phase = ();
+
+        fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {
+            RoundsModel::default()
+        }
+
+        fn on_round_end(
+            &self,
+            _entities: &Entities<CustomRules>,
+            _space: &Space<CustomRules>,
+            model: &mut Self::RoundsModel,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            model.round_ends += 1;
+        }
+
+        fn on_round_start(
+            &self,
+            _entities: &Entities<CustomRules>,
+            _space: &Space<CustomRules>,
+            model: &mut Self::RoundsModel,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            model.round_starts += 1;
+        }
+
+        fn auto_end_round(&self, _model: &Self::RoundsModel) -> bool {
+            true
+        }
+    }
+
+    battle_rules_with_rounds! { AutoRoundsRules }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    // Initialize the battle.
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, ());
+    util::creature(&mut server, CREATURE_2_ID, TEAM_1_ID, ());
+    // The round doesn't end until every actor has acted.
+    util::start_turn(&mut server, &entity_1_id);
+    util::end_turn(&mut server);
+    assert!(server.battle().rounds().has_acted(&entity_1_id));
+    assert!(!server.battle().rounds().has_acted(&entity_2_id));
+    assert_eq!(server.battle().rounds().completed_rounds(), 0);
+    util::start_turn(&mut server, &entity_1_id);
+    util::end_turn(&mut server);
+    assert_eq!(server.battle().rounds().completed_rounds(), 0);
+    // Let the second actor act.
+    util::start_turn(&mut server, &entity_2_id);
+    util::end_turn(&mut server);
+    let rounds = server.battle().rounds();
+    assert_eq!(rounds.completed_rounds(), 1);
+    assert_eq!(rounds.acted().count(), 0);
+    assert_eq!(rounds.model().round_ends, 1);
+    assert_eq!(rounds.model().round_starts, 1);
+    assert_eq!(
+        server.battle().history().events().last().unwrap().kind(),
+        EventKind::EndRound
+    );
+}
+
+#[test]
+fn automatic_turn_advance() {
+    #[derive(Default)]
+    struct RoundsModel {
+        order: Vec<EntityId<CustomRules>>,
+        next: usize,
+        ends: u32,
+    }
+
+    #[derive(Default)]
+    struct AutoRoundsRules {}
+
+    impl RoundsRules<CustomRules> for AutoRoundsRules {
+        type RoundsSeed = ();
+        type RoundsModel = RoundsModel;
+        type Phase = ();
+
+        fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {
+            RoundsModel::default()
+        }
+
+        fn on_end(
+            &self,
+            _entities: &Entities<CustomRules>,
+            _space: &Space<CustomRules>,
+            model: &mut Self::RoundsModel,
+            _actor: &dyn Actor<CustomRules>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            model.next = (model.next + 1) % model.order.len();
+            model.ends += 1;
+        }
+
+        fn next_actors(
+            &self,
+            model: &Self::RoundsModel,
+            _entities: &Entities<CustomRules>,
+        ) -> Vec<EntityId<CustomRules>> {
+            // Actors are idle after the third turn.
+            if model.ends >= 3 {
+                Vec::new()
+            } else {
+                vec![model.order[model.next]]
+            }
+        }
+
+        fn auto_advance_turns(&self, _model: &Self::RoundsModel) -> bool {
+            true
+        }
+
+        fn auto_end_round(&self, _model: &Self::RoundsModel) -> bool {
+            true
+        }
+
+        fn on_actor_added(
+            &self,
+            model: &mut Self::RoundsModel,
+            actor: &dyn Actor<CustomRules>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            model.order.push(*actor.entity_id());
+        }
+    }
+
+    battle_rules_with_rounds! { AutoRoundsRules }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    // Initialize the battle.
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, ());
+    util::creature(&mut server, CREATURE_2_ID, TEAM_1_ID, ());
+    assert_eq!(
+        server
+            .battle()
+            .rounds()
+            .next_actors(server.battle().entities()),
+        vec![entity_1_id]
+    );
+    // The first turn must be started manually.
+    util::start_turn(&mut server, &entity_1_id);
+    // Ending a turn starts the next one.
+    util::end_turn(&mut server);
+    assert!(server.battle().rounds().is_acting(&entity_2_id));
+    // Ending the last turn of the round starts a new round and a new turn.
+    util::end_turn(&mut server);
+    assert_eq!(server.battle().rounds().completed_rounds(), 1);
+    assert!(server.battle().rounds().is_acting(&entity_1_id));
+    let kinds: Vec<_> = server
+        .battle()
+        .history()
+        .events()
+        .iter()
+        .rev()
+        .take(3)
+        .map(|event| event.kind())
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            EventKind::StartTurn,
+            EventKind::EndRound,
+            EventKind::EndTurn
+        ]
+    );
+    // When no actor is ready the environment takes a turn.
+    util::end_turn(&mut server);
+    assert_eq!(*server.battle().rounds().state(), TurnState::Ready);
+    assert_eq!(
+        server.battle().history().events().last().unwrap().kind(),
+        EventKind::EnvironmentTurn
+    );
+}
+
+#[test]
+fn initiative_rules() {
+    use weasel::character::CharacterRules;
+    use weasel::creature::CreateCreature;
+    use weasel::rules::round::{InitiativeRules, SpeedSource};
+    use weasel::rules::statistic::SimpleStatistic;
+
+    const SPEED: u32 = 0;
+
+    #[derive(Default)]
+    struct CustomCharacterRules {}
+
+    impl CharacterRules<CustomRules> for CustomCharacterRules {
+        type CreatureId = u32;
+        type ObjectId = ();
+        type Statistic = SimpleStatistic<u32, u32>;
+        type StatisticsSeed = u32;
+        type StatisticsAlteration = ();
+        type Status = EmptyStatus;
+        type StatusesAlteration = ();
+
+        fn generate_statistics(
+            &self,
+            seed: &Option<Self::StatisticsSeed>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Box<dyn Iterator<Item = Self::Statistic>> {
+            let v = vec![SimpleStatistic::new(SPEED, seed.unwrap())];
+            Box::new(v.into_iter())
+        }
+    }
+
+    #[derive(Default)]
+    struct Speed {}
+
+    impl SpeedSource<CustomRules> for Speed {
+        type Speed = u32;
+
+        fn speed(&self, actor: &dyn Actor<CustomRules>) -> Self::Speed {
+            actor.statistic(&SPEED).unwrap().value()
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        CustomCharacterRules,
+        EmptyActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        EmptySpaceRules,
+        InitiativeRules<Speed>,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    let entity_3_id = EntityId::Creature(CREATURE_3_ID);
+    // Initialize the battle.
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    for (id, speed) in &[
+        (CREATURE_1_ID, 10),
+        (CREATURE_2_ID, 20),
+        (CREATURE_3_ID, 20),
+    ] {
+        assert_eq!(
+            CreateCreature::trigger(&mut server, *id, TEAM_1_ID, ())
+                .statistics_seed(*speed)
+                .fire()
+                .err(),
+            None
+        );
+    }
+    // Ties are broken in favor of the first actor.
+    let order = |server: &Server<CustomRules>| -> Vec<_> {
+        server.battle().rounds().model().order().cloned().collect()
+    };
+    assert_eq!(order(&server), vec![entity_2_id, entity_3_id, entity_1_id]);
+    assert_eq!(
+        server
+            .battle()
+            .rounds()
+            .next_actors(server.battle().entities()),
+        vec![entity_2_id]
+    );
+    // Only the actor at the top of the queue is eligible.
+    assert_eq!(
+        StartTurn::trigger(&mut server, entity_1_id)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::ActorNotEligible(entity_1_id))
+    );
+    // Initiative grows with speed and it's reset after each turn.
+    util::start_turn(&mut server, &entity_2_id);
+    util::end_turn(&mut server);
+    assert_eq!(order(&server), vec![entity_3_id, entity_1_id, entity_2_id]);
+    assert_eq!(
+        server.battle().rounds().model().score(&entity_3_id),
+        Some(40)
+    );
+    util::start_turn(&mut server, &entity_3_id);
+    util::end_turn(&mut server);
+    assert_eq!(order(&server), vec![entity_1_id, entity_2_id, entity_3_id]);
+    // Actors who delay their turn let the next one act first.
+    util::start_turn(&mut server, &entity_1_id);
+    assert_eq!(DelayTurn::trigger(&mut server).fire().err(), None);
+    assert_eq!(order(&server), vec![entity_2_id, entity_1_id, entity_3_id]);
+    util::start_turn(&mut server, &entity_2_id);
+    util::end_turn(&mut server);
+    assert_eq!(order(&server), vec![entity_1_id, entity_3_id, entity_2_id]);
+    // Removed actors leave the queue.
+    util::start_turn(&mut server, &entity_1_id);
+    util::end_turn(&mut server);
+    assert_eq!(
+        RemoveCreature::trigger(&mut server, CREATURE_2_ID)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(order(&server), vec![entity_3_id, entity_1_id]);
+    // Incapacitated actors leave the queue and rejoin it once revived.
+    assert_eq!(
+        TransmuteCharacter::trigger(&mut server, entity_3_id, Transmutation::INCAPACITATION)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(order(&server), vec![entity_1_id]);
+    assert_eq!(
+        server
+            .battle()
+            .rounds()
+            .next_actors(server.battle().entities()),
+        vec![entity_1_id]
+    );
+    assert_eq!(
+        StartTurn::trigger(&mut server, entity_3_id)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::ActorNotEligible(entity_3_id))
+    );
+    assert_eq!(
+        TransmuteCharacter::trigger(&mut server, entity_3_id, Transmutation::REVIVAL)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(order(&server), vec![entity_3_id, entity_1_id]);
+}
+
+#[test]
+fn timeline_rules() {
+    use weasel::character::CharacterRules;
+    use weasel::creature::CreateCreature;
+    use weasel::round::ResetRounds;
+    use weasel::rules::round::{SpeedSource, TimelineRules, TimelineSource};
+    use weasel::rules::statistic::SimpleStatistic;
+
+    const SPEED: u32 = 0;
+
+    #[derive(Default)]
+    struct CustomCharacterRules {}
+
+    impl CharacterRules<CustomRules> for CustomCharacterRules {
+        type CreatureId = u32;
+        type ObjectId = ();
+        type Statistic = SimpleStatistic<u32, u32>;
+        type StatisticsSeed = u32;
+        type StatisticsAlteration = ();
+        type Status = EmptyStatus;
+        type StatusesAlteration = ();
+
+        fn generate_statistics(
+            &self,
+            seed: &Option<Self::StatisticsSeed>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Box<dyn Iterator<Item = Self::Statistic>> {
+            let v = vec![SimpleStatistic::new(SPEED, seed.unwrap())];
+            Box::new(v.into_iter())
+        }
+    }
+
+    #[derive(Default)]
+    struct Readiness {}
+
+    impl SpeedSource<CustomRules> for Readiness {
+        type Speed = u32;
+
+        fn speed(&self, actor: &dyn Actor<CustomRules>) -> Self::Speed {
+            actor.statistic(&SPEED).unwrap().value()
+        }
+    }
+
+    impl TimelineSource<CustomRules> for Readiness {
+        fn threshold(&self) -> Self::Speed {
+            100
+        }
+
+        fn recovery(&self, actor: &dyn Actor<CustomRules>) -> Self::Speed {
+            // Creature 2 always performs heavy actions.
+            if *actor.entity_id() == EntityId::Creature(CREATURE_2_ID) {
+                75
+            } else {
+                0
+            }
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        CustomCharacterRules,
+        EmptyActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        EmptySpaceRules,
+        TimelineRules<Readiness>,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    // Initialize the battle.
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    for (id, speed) in &[(CREATURE_1_ID, 10), (CREATURE_2_ID, 25)] {
+        assert_eq!(
+            CreateCreature::trigger(&mut server, *id, TEAM_1_ID, ())
+                .statistics_seed(*speed)
+                .fire()
+                .err(),
+            None
+        );
+    }
+    // Faster actors fill their gauge more often.
+    let timeline = server.battle().rounds().model();
+    assert_eq!(
+        timeline.predict(4),
+        vec![entity_2_id, entity_2_id, entity_1_id, entity_2_id]
+    );
+    assert_eq!(
+        StartTurn::trigger(&mut server, entity_1_id)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::ActorNotEligible(entity_1_id))
+    );
+    // Heavy actions push back the actor on the timeline.
+    util::start_turn(&mut server, &entity_2_id);
+    util::end_turn(&mut server);
+    let timeline = server.battle().rounds().model();
+    assert_eq!(timeline.gauge(&entity_1_id), Some(40));
+    assert_eq!(timeline.gauge(&entity_2_id), Some(0));
+    assert_eq!(timeline.next(), Some(entity_1_id));
+    // The timeline can be restored from a seed.
+    let seed = timeline.clone();
+    let prediction = timeline.predict(10);
+    util::start_turn(&mut server, &entity_1_id);
+    util::end_turn(&mut server);
+    assert_eq!(
+        ResetRounds::trigger(&mut server).seed(seed).fire().err(),
+        None
+    );
+    assert_eq!(server.battle().rounds().model().predict(10), prediction);
+    // Incapacitated actors are taken off the timeline until revived.
+    let next = server.battle().rounds().model().next().unwrap();
+    let other = if next == entity_1_id {
+        entity_2_id
+    } else {
+        entity_1_id
+    };
+    assert_eq!(
+        TransmuteCharacter::trigger(&mut server, next, Transmutation::INCAPACITATION)
+            .fire()
+            .err(),
+        None
+    );
+    let timeline = server.battle().rounds().model();
+    assert_eq!(timeline.gauge(&next), None);
+    assert_eq!(timeline.predict(2), vec![other, other]);
+    assert_eq!(
+        server
+            .battle()
+            .rounds()
+            .next_actors(server.battle().entities()),
+        vec![other]
+    );
+    util::start_turn(&mut server, &other);
+    util::end_turn(&mut server);
+    assert_eq!(
+        TransmuteCharacter::trigger(&mut server, next, Transmutation::REVIVAL)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(server.battle().rounds().model().gauge(&next), Some(0));
+}
+
+#[test]
+fn timeline_large_values() {
+    use weasel::character::CharacterRules;
+    use weasel::creature::CreateCreature;
+    use weasel::rules::round::{SpeedSource, TimelineRules, TimelineSource};
+    use weasel::rules::statistic::SimpleStatistic;
+
+    const SPEED: u32 = 0;
+
+    #[derive(Default)]
+    struct CustomCharacterRules {}
+
+    impl CharacterRules<CustomRules> for CustomCharacterRules {
+        type CreatureId = u32;
+        type ObjectId = ();
+        type Statistic = SimpleStatistic<u32, u64>;
+        type StatisticsSeed = u64;
+        type StatisticsAlteration = ();
+        type Status = EmptyStatus;
+        type StatusesAlteration = ();
+
+        fn generate_statistics(
+            &self,
+            seed: &Option<Self::StatisticsSeed>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Box<dyn Iterator<Item = Self::Statistic>> {
+            let v = vec![SimpleStatistic::new(SPEED, seed.unwrap())];
+            Box::new(v.into_iter())
+        }
+    }
+
+    #[derive(Default)]
+    struct Readiness {}
+
+    impl SpeedSource<CustomRules> for Readiness {
+        type Speed = u64;
+
+        fn speed(&self, actor: &dyn Actor<CustomRules>) -> Self::Speed {
+            actor.statistic(&SPEED).unwrap().value()
+        }
+    }
+
+    impl TimelineSource<CustomRules> for Readiness {
+        fn threshold(&self) -> Self::Speed {
+            u64::MAX / 2
+        }
+
+        fn recovery(&self, _actor: &dyn Actor<CustomRules>) -> Self::Speed {
+            u64::MAX
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        CustomCharacterRules,
+        EmptyActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        EmptySpaceRules,
+        TimelineRules<Readiness>,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    // Initialize the battle with slow actors and a huge threshold.
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    for (id, speed) in &[(CREATURE_1_ID, 1), (CREATURE_2_ID, 3)] {
+        assert_eq!(
+            CreateCreature::trigger(&mut server, *id, TEAM_1_ID, ())
+                .statistics_seed(*speed)
+                .fire()
+                .err(),
+            None
+        );
+    }
+    // Time advances in a single step and gauges saturate instead of overflowing.
+    let timeline = server.battle().rounds().model();
+    assert_eq!(timeline.next(), Some(entity_2_id));
+    util::start_turn(&mut server, &entity_2_id);
+    util::end_turn(&mut server);
+    let timeline = server.battle().rounds().model();
+    assert_eq!(timeline.gauge(&entity_1_id), Some(u64::MAX / 6 + 1));
+    assert_eq!(timeline.next(), Some(entity_1_id));
+    util::start_turn(&mut server, &entity_1_id);
+    util::end_turn(&mut server);
+    let timeline = server.battle().rounds().model();
+    assert_eq!(timeline.next(), Some(entity_2_id));
+    assert_eq!(timeline.predict(3).len(), 3);
+}
+
+#[test]
+fn action_budget() {
+    use weasel::ability::ActivateAbility;
+    use weasel::actor::{Action, ActorRules};
+    use weasel::round::Budget;
+    use weasel::space::{MoveEntity, Position};
+
+    const ABILITY_ID: u32 = 1;
+
+    #[derive(Default)]
+    struct CustomActorRules {}
+
+    impl ActorRules<CustomRules> for CustomActorRules {
+        type Ability = EmptyAbility;
+        type AbilitiesSeed = ();
+        type Activation = ();
+        type AbilitiesAlteration = ();
+
+        fn generate_abilities(
+            &self,
+            _: &Option<Self::AbilitiesSeed>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Box<dyn Iterator<Item = Self::Ability>> {
+            let v = vec![EmptyAbility { id: ABILITY_ID }];
+            Box::new(v.into_iter())
+        }
+    }
+
+    #[derive(Default)]
+    struct BudgetRoundsRules {}
+
+    impl RoundsRules<CustomRules> for BudgetRoundsRules {
+        type RoundsSeed = ();
+        type RoundsModel = ();
+        type Phase = ();
+
+        fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {}
+
+        fn budget(
+            &self,
+            _model: &Self::RoundsModel,
+            _actor: &dyn Actor<CustomRules>,
+        ) -> Option<Budget> {
+            Some(Budget::new(2, 1))
+        }
+
+        fn activation_cost(
+            &self,
+            _model: &Self::RoundsModel,
+            _action: Action<CustomRules>,
+        ) -> Budget {
+            Budget::new(1, 0)
+        }
+
+        fn movement_cost(
+            &self,
+            _model: &Self::RoundsModel,
+            _actor: &dyn Actor<CustomRules>,
+            _position: &Position<CustomRules>,
+        ) -> Budget {
+            Budget::new(0, 1)
+        }
+
+        fn auto_end_turn(&self, _model: &Self::RoundsModel) -> bool {
+            true
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        EmptyCharacterRules,
+        CustomActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        EmptySpaceRules,
+        BudgetRoundsRules,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    // Initialize the battle.
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, ());
+    assert_eq!(server.battle().rounds().budget(&entity_1_id), None);
+    // The budget is filled at the start of the turn.
+    util::start_turn(&mut server, &entity_1_id);
+    assert_eq!(
+        server.battle().rounds().budget(&entity_1_id),
+        Some(&Budget::new(2, 1))
+    );
+    // Movement consumes movement points.
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, ())
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(
+        server.battle().rounds().budget(&entity_1_id),
+        Some(&Budget::new(2, 0))
+    );
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, ())
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::InsufficientActionPoints(entity_1_id))
+    );
+    // Abilities consume action points.
+    assert_eq!(
+        ActivateAbility::trigger(&mut server, entity_1_id, ABILITY_ID)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(
+        server.battle().rounds().budget(&entity_1_id),
+        Some(&Budget::new(1, 0))
+    );
+    assert_eq!(
+        *server.battle().rounds().state(),
+        TurnState::Started(indexset![entity_1_id])
+    );
+    // The turn ends automatically once the budget is exhausted.
+    assert_eq!(
+        ActivateAbility::trigger(&mut server, entity_1_id, ABILITY_ID)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(*server.battle().rounds().state(), TurnState::Ready);
+    assert_eq!(server.battle().rounds().budget(&entity_1_id), None);
+    assert_eq!(server.battle().rounds().completed_turns(), 1);
+}
+
+#[test]
+fn turn_phases() {
+    use weasel::ability::ActivateAbility;
+    use weasel::actor::ActorRules;
+    use weasel::character::{Character, CharacterRules};
+    use weasel::round::AdvancePhase;
+    use weasel::rules::status::SimpleStatus;
+    use weasel::space::MoveEntity;
+    use weasel::status::{InflictStatus, Potency, Status, StatusId};
+
+    const ABILITY_ID: u32 = 1;
+    const STATUS_ID: u32 = 1;
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    #[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+    enum Phase {
+        Upkeep,
+        Movement,
+        Action,
+    }
+
+    #[derive(Default)]
+    struct CustomCharacterRules {}
+
+    impl CharacterRules<CustomRules> for CustomCharacterRules {
+        type CreatureId = u32;
+        type ObjectId = u32;
+        type Statistic = EmptyStat;
+        type StatisticsSeed = ();
+        type StatisticsAlteration = ();
+        type Status = SimpleStatus<u32, u32>;
+        type StatusesAlteration = ();
+
+        fn generate_status(
+            &self,
+            _character: &dyn Character<CustomRules>,
+            status_id: &StatusId<CustomRules>,
+            _potency: &Option<Potency<CustomRules>>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Option<Status<CustomRules>> {
+            Some(SimpleStatus::new(*status_id, 0, None))
+        }
+    }
+
+    #[derive(Default)]
+    struct CustomActorRules {}
+
+    impl ActorRules<CustomRules> for CustomActorRules {
+        type Ability = EmptyAbility;
+        type AbilitiesSeed = ();
+        type Activation = ();
+        type AbilitiesAlteration = ();
+
+        fn generate_abilities(
+            &self,
+            _: &Option<Self::AbilitiesSeed>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Box<dyn Iterator<Item = Self::Ability>> {
+            let v = vec![EmptyAbility { id: ABILITY_ID }];
+            Box::new(v.into_iter())
+        }
+    }
+
+    #[derive(Default)]
+    struct PhasedRoundsRules {}
+
+    impl RoundsRules<CustomRules> for PhasedRoundsRules {
+        type RoundsSeed = ();
+        type RoundsModel = Vec<Phase>;
+        type Phase = Phase;
+
+        fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {
+            Vec::new()
+        }
+
+        fn phases(&self, _model: &Self::RoundsModel) -> Vec<Self::Phase> {
+            vec![Phase::Upkeep, Phase::Movement, Phase::Action]
+        }
+
+        fn on_phase(
+            &self,
+            _entities: &Entities<CustomRules>,
+            _space: &Space<CustomRules>,
+            model: &mut Self::RoundsModel,
+            phase: &Self::Phase,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            model.push(*phase);
+        }
+
+        fn statuses_phase(&self, _model: &Self::RoundsModel) -> Option<Self::Phase> {
+            Some(Phase::Action)
+        }
+
+        fn phase_allows(
+            &self,
+            _model: &Self::RoundsModel,
+            phase: &Self::Phase,
+            kind: EventKind,
+        ) -> bool {
+            match phase {
+                Phase::Upkeep => false,
+                Phase::Movement => kind == EventKind::MoveEntity,
+                Phase::Action => kind != EventKind::MoveEntity,
+            }
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        CustomCharacterRules,
+        CustomActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        EmptySpaceRules,
+        PhasedRoundsRules,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let duration = |server: &Server<CustomRules>| {
+        server
+            .battle()
+            .entities()
+            .creature(&CREATURE_1_ID)
+            .unwrap()
+            .status(&STATUS_ID)
+            .unwrap()
+            .duration()
+    };
+    // Initialize the battle.
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, ());
+    assert_eq!(
+        InflictStatus::trigger(&mut server, entity_1_id, STATUS_ID)
+            .fire()
+            .err(),
+        None
+    );
+    // Phases can't be advanced outside of a turn.
+    assert_eq!(server.battle().rounds().phase(), None);
+    assert_eq!(
+        AdvancePhase::trigger(&mut server)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::NoTurnInProgress)
+    );
+    // The turn starts in its first phase, where no action is allowed.
+    util::start_turn(&mut server, &entity_1_id);
+    assert_eq!(server.battle().rounds().phase(), Some(&Phase::Upkeep));
+    assert_eq!(server.battle().rounds().model(), &vec![Phase::Upkeep]);
+    assert_eq!(duration(&server), 0);
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, ())
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::NotAllowedInPhase(EventKind::MoveEntity))
+    );
+    // Only movement is allowed in the second phase.
+    assert_eq!(AdvancePhase::trigger(&mut server).fire().err(), None);
+    assert_eq!(server.battle().rounds().phase(), Some(&Phase::Movement));
+    assert_eq!(
+        ActivateAbility::trigger(&mut server, entity_1_id, ABILITY_ID)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::NotAllowedInPhase(EventKind::ActivateAbility))
+    );
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, ())
+            .fire()
+            .err(),
+        None
+    );
+    // Statuses are updated when entering the action phase.
+    assert_eq!(duration(&server), 0);
+    assert_eq!(AdvancePhase::trigger(&mut server).fire().err(), None);
+    assert_eq!(server.battle().rounds().phase(), Some(&Phase::Action));
+    assert_eq!(duration(&server), 1);
+    assert_eq!(
+        ActivateAbility::trigger(&mut server, entity_1_id, ABILITY_ID)
+            .fire()
+            .err(),
+        None
+    );
+    // There's no phase after the last one.
+    assert!(!server.battle().rounds().has_next_phase());
+    assert_eq!(
+        AdvancePhase::trigger(&mut server)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::NoNextPhase)
+    );
+    assert_eq!(
+        server.battle().rounds().model(),
+        &vec![Phase::Upkeep, Phase::Movement, Phase::Action]
+    );
+    // Phases are discarded once the turn ends.
+    util::end_turn(&mut server);
+    assert_eq!(server.battle().rounds().phase(), None);
+    assert!(server.battle().rounds().phases().is_empty());
+    assert_eq!(duration(&server), 1);
+    // Statuses are updated even if the turn ends before the action phase.
+    util::start_turn(&mut server, &entity_1_id);
+    assert_eq!(duration(&server), 1);
+    util::end_turn(&mut server);
+    assert_eq!(duration(&server), 2);
+}
+
+#[test]
+fn delay_turn() {
+    use weasel::ability::ActivateAbility;
+    use weasel::actor::{Action, ActorRules};
+    use weasel::character::{Character, CharacterRules};
+    use weasel::round::Budget;
+    use weasel::rules::status::SimpleStatus;
+    use weasel::status::{InflictStatus, Potency, Status, StatusId};
+
+    const ABILITY_ID: u32 = 1;
+    const STATUS_ID: u32 = 1;
+
+    #[derive(Default)]
+    struct CustomCharacterRules {}
+
+    impl CharacterRules<CustomRules> for CustomCharacterRules {
+        type CreatureId = u32;
+        type ObjectId = u32;
+        type Statistic = EmptyStat;
+        type StatisticsSeed = ();
+        type StatisticsAlteration = ();
+        type Status = SimpleStatus<u32, u32>;
+        type StatusesAlteration = ();
+
+        fn generate_status(
+            &self,
+            _character: &dyn Character<CustomRules>,
+            status_id: &StatusId<CustomRules>,
+            _potency: &Option<Potency<CustomRules>>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Option<Status<CustomRules>> {
+            Some(SimpleStatus::new(*status_id, 0, None))
+        }
+    }
+
+    #[derive(Default)]
+    struct CustomActorRules {
+        started: RefCell<u32>,
+        ended: RefCell<u32>,
+    }
+
+    impl ActorRules<CustomRules> for CustomActorRules {
+        type Ability = EmptyAbility;
+        type AbilitiesSeed = ();
+        type Activation = ();
+        type AbilitiesAlteration = ();
+
+        fn generate_abilities(
+            &self,
+            _: &Option<Self::AbilitiesSeed>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> Box<dyn Iterator<Item = Self::Ability>> {
+            let v = vec![EmptyAbility { id: ABILITY_ID }];
+            Box::new(v.into_iter())
+        }
+
+        fn on_turn_start(
+            &self,
+            _state: &BattleState<CustomRules>,
+            _actor: &dyn Actor<CustomRules>,
+            _event_queue: &mut Option<EventQueue<CustomRules>>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            *self.started.borrow_mut() += 1;
+        }
+
+        fn on_turn_end(
+            &self,
+            _state: &BattleState<CustomRules>,
+            _actor: &dyn Actor<CustomRules>,
+            _event_queue: &mut Option<EventQueue<CustomRules>>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            *self.ended.borrow_mut() += 1;
+        }
+    }
+
+    #[derive(Default)]
+    struct DelayRoundsRules {}
+
+    impl RoundsRules<CustomRules> for DelayRoundsRules {
+        type RoundsSeed = ();
+        type RoundsModel = u32;
+        type Phase = ();
+
+        fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {
+            0
+        }
+
+        fn on_delay(
+            &self,
+            _entities: &Entities<CustomRules>,
+            _space: &Space<CustomRules>,
+            model: &mut Self::RoundsModel,
+            _actor: &dyn Actor<CustomRules>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            *model += 1;
+        }
+
+        fn auto_end_round(&self, _model: &Self::RoundsModel) -> bool {
+            true
+        }
+
+        fn budget(
+            &self,
+            _model: &Self::RoundsModel,
+            _actor: &dyn Actor<CustomRules>,
+        ) -> Option<Budget> {
+            Some(Budget::new(2, 0))
+        }
+
+        fn activation_cost(
+            &self,
+            _model: &Self::RoundsModel,
+            _action: Action<CustomRules>,
+        ) -> Budget {
+            Budget::new(1, 0)
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        CustomCharacterRules,
+        CustomActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        EmptySpaceRules,
+        DelayRoundsRules,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    let callbacks = |server: &Server<CustomRules>| {
+        let rules = server.battle().rules().actor_rules();
+        (*rules.started.borrow(), *rules.ended.borrow())
+    };
+    let duration = |server: &Server<CustomRules>| {
+        server
+            .battle()
+            .entities()
+            .creature(&CREATURE_1_ID)
+            .unwrap()
+            .status(&STATUS_ID)
+            .unwrap()
+            .duration()
+    };
+    // Initialize the battle.
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, ());
+    util::creature(&mut server, CREATURE_2_ID, TEAM_1_ID, ());
+    assert_eq!(
+        InflictStatus::trigger(&mut server, entity_1_id, STATUS_ID)
+            .fire()
+            .err(),
+        None
+    );
+    // Turns can't be delayed if they aren't started.
+    assert_eq!(
+        DelayTurn::trigger(&mut server)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::NoTurnInProgress)
+    );
+    // Delay a turn after spending part of the budget.
+    util::start_turn(&mut server, &entity_1_id);
+    assert_eq!(duration(&server), 1);
+    assert_eq!(
+        ActivateAbility::trigger(&mut server, entity_1_id, ABILITY_ID)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(DelayTurn::trigger(&mut server).fire().err(), None);
+    assert_eq!(*server.battle().rounds().state(), TurnState::Ready);
+    assert_eq!(*server.battle().rounds().model(), 1);
+    assert_eq!(server.battle().rounds().completed_turns(), 0);
+    assert_eq!(callbacks(&server), (1, 0));
+    assert!(!server.battle().rounds().has_acted(&entity_1_id));
+    assert!(server.battle().rounds().has_delayed(&entity_1_id));
+    // The round doesn't end until the delayed actor acts.
+    util::start_turn(&mut server, &entity_2_id);
+    util::end_turn(&mut server);
+    assert_eq!(server.battle().rounds().completed_rounds(), 0);
+    assert_eq!(server.battle().rounds().completed_turns(), 1);
+    assert_eq!(callbacks(&server), (2, 1));
+    // Resuming the turn doesn't update statuses, refill the budget
+    // nor invoke the turn start callback.
+    util::start_turn(&mut server, &entity_1_id);
+    assert_eq!(duration(&server), 1);
+    assert_eq!(callbacks(&server), (2, 1));
+    assert_eq!(
+        server.battle().rounds().budget(&entity_1_id),
+        Some(&Budget::new(1, 0))
+    );
+    util::end_turn(&mut server);
+    assert!(!server.battle().rounds().has_delayed(&entity_1_id));
+    assert_eq!(server.battle().rounds().completed_rounds(), 1);
+    assert_eq!(server.battle().rounds().completed_turns(), 2);
+    assert_eq!(callbacks(&server), (2, 2));
+    // In the next round everything works as usual.
+    util::start_turn(&mut server, &entity_1_id);
+    assert_eq!(duration(&server), 2);
+    assert_eq!(
+        server.battle().rounds().budget(&entity_1_id),
+        Some(&Budget::new(2, 0))
+    );
+}
@@ -27,6 +27,7 @@ impl SpaceRules<CustomRules> for CustomSpaceRules {
     type SpaceSeed = ();
     type SpaceModel = HashSet<Self::Position>;
     type SpaceAlteration = Self::Position;
+    type Direction = ();
 
     fn generate_model(&self, _: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
         HashSet::new()
@@ -50,6 +51,7 @@ impl SpaceRules<CustomRules> for CustomSpaceRules {
         model: &mut Self::SpaceModel,
         claim: PositionClaim<CustomRules>,
         position: Option<&Self::Position>,
+        _event_queue: &mut Option<EventQueue<CustomRules>>,
         _metrics: &mut WriteMetrics<CustomRules>,
     ) {
         if let Some(position) = position {
@@ -234,3 +236,1669 @@ fn alter_space() {
         ))
     );
 }
+
+#[test]
+fn square_grid() {
+    use weasel::rules::space::{GridSize, Square, SquareGridRules};
+    use weasel::space::{DisplaceEntity, SwapEntities};
+
+    battle_rules_with_space! { SquareGridRules }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    let position = |server: &Server<CustomRules>, id| {
+        *server.battle().entities().entity(&id).unwrap().position()
+    };
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    // Without a seed the grid is empty.
+    assert_eq!(
+        CreateCreature::trigger(&mut server, CREATURE_1_ID, TEAM_1_ID, Square::new(0, 0))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            None,
+            Square::new(0, 0),
+            Box::new(WeaselError::PositionOutOfBounds(Square::new(0, 0)))
+        ))
+    );
+    // Create a 3x2 grid.
+    assert_eq!(
+        ResetSpace::trigger(&mut server)
+            .seed(GridSize::new(3, 2))
+            .fire()
+            .err(),
+        None
+    );
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, Square::new(0, 0));
+    util::creature(&mut server, CREATURE_2_ID, TEAM_1_ID, Square::new(1, 0));
+    // Entities can't move into occupied squares or outside of the grid.
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, Square::new(1, 0))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(Square::new(0, 0)),
+            Square::new(1, 0),
+            Box::new(WeaselError::PositionOccupied(
+                Square::new(1, 0),
+                entity_2_id
+            ))
+        ))
+    );
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, Square::new(3, 0))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(Square::new(0, 0)),
+            Square::new(3, 0),
+            Box::new(WeaselError::PositionOutOfBounds(Square::new(3, 0)))
+        ))
+    );
+    // Move into a free square.
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, Square::new(2, 1))
+            .fire()
+            .err(),
+        None
+    );
+    let grid = server.battle().space().model();
+    assert_eq!(grid.occupant(&Square::new(2, 1)), Some(&entity_1_id));
+    assert!(grid.is_free(&Square::new(0, 0)));
+    // Forced movements stop at the edge of the grid, whatever their length.
+    for direction in &[
+        Square::new(i32::MAX, 0),
+        Square::new(1, 0),
+        Square::new(0, 0),
+    ] {
+        assert_eq!(
+            DisplaceEntity::trigger(&mut server, entity_1_id, *direction, u32::MAX)
+                .fire()
+                .err(),
+            None
+        );
+        assert_eq!(position(&server, entity_1_id), Square::new(2, 1));
+    }
+    assert_eq!(
+        Square::new(i32::MAX, 0)
+            .walk(&Square::new(1, 0), u32::MAX)
+            .count(),
+        1
+    );
+    // Swap two entities, then swap them back.
+    for (first, second) in [
+        (Square::new(1, 0), Square::new(2, 1)),
+        (Square::new(2, 1), Square::new(1, 0)),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            SwapEntities::trigger(&mut server, entity_1_id, entity_2_id)
+                .fire()
+                .err(),
+            None
+        );
+        assert_eq!(position(&server, entity_1_id), *first);
+        let grid = server.battle().space().model();
+        assert_eq!(grid.occupant(first), Some(&entity_1_id));
+        assert_eq!(grid.occupant(second), Some(&entity_2_id));
+    }
+    // Shrink the grid. Entities outside of it are moved to the closest free square.
+    assert_eq!(
+        ResetSpace::trigger(&mut server)
+            .seed(GridSize::new(2, 1))
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), Square::new(0, 0));
+    assert_eq!(position(&server, entity_2_id), Square::new(1, 0));
+    assert_eq!(server.battle().space().model().occupied().count(), 2);
+    // Entities are removed if there's no room left.
+    assert_eq!(
+        ResetSpace::trigger(&mut server)
+            .seed(GridSize::new(1, 1))
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), Square::new(0, 0));
+    assert!(server
+        .battle()
+        .entities()
+        .creature(&CREATURE_2_ID)
+        .is_none());
+    assert_eq!(
+        server.battle().space().model().occupant(&Square::new(0, 0)),
+        Some(&entity_1_id)
+    );
+}
+
+#[test]
+fn hex_grid() {
+    use weasel::rules::space::{Hex, HexGridRules};
+    use weasel::space::Area;
+
+    battle_rules_with_space! { HexGridRules }
+
+    // Check the hexagon utilities.
+    let origin = Hex::new(0, 0);
+    assert_eq!(Hex::new(1, -3).s(), 2);
+    assert!(origin
+        .neighbors()
+        .iter()
+        .all(|neighbor| origin.distance(neighbor) == 1));
+    assert_eq!(Hex::new(-1, -2).distance(&Hex::new(2, 1)), 6);
+    assert_eq!(origin.ring(0), vec![origin]);
+    let ring = Hex::new(1, 1).ring(2);
+    assert_eq!(ring.len(), 12);
+    assert!(ring.iter().all(|hex| hex.distance(&Hex::new(1, 1)) == 2));
+    assert_eq!(
+        origin.line(&Hex::new(3, 0)),
+        vec![
+            Hex::new(0, 0),
+            Hex::new(1, 0),
+            Hex::new(2, 0),
+            Hex::new(3, 0)
+        ]
+    );
+    assert_eq!(
+        origin.line(&Hex::new(2, -2)),
+        vec![Hex::new(0, 0), Hex::new(1, -1), Hex::new(2, -2)]
+    );
+    // Check the grid.
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    let position = |server: &Server<CustomRules>, id| {
+        *server.battle().entities().entity(&id).unwrap().position()
+    };
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    // Without a seed the grid has a single hexagon.
+    assert!(server.battle().space().model().contains(&origin));
+    assert_eq!(
+        CreateCreature::trigger(&mut server, CREATURE_1_ID, TEAM_1_ID, Hex::new(1, 0))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            None,
+            Hex::new(1, 0),
+            Box::new(WeaselError::PositionOutOfBounds(Hex::new(1, 0)))
+        ))
+    );
+    // Create a grid of radius 2.
+    assert_eq!(ResetSpace::trigger(&mut server).seed(2).fire().err(), None);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, Hex::new(2, 0));
+    util::creature(&mut server, CREATURE_2_ID, TEAM_1_ID, origin);
+    // Entities can't move into occupied hexagons or outside of the grid.
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, origin)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(Hex::new(2, 0)),
+            origin,
+            Box::new(WeaselError::PositionOccupied(origin, entity_2_id))
+        ))
+    );
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, Hex::new(2, 1))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(Hex::new(2, 0)),
+            Hex::new(2, 1),
+            Box::new(WeaselError::PositionOutOfBounds(Hex::new(2, 1)))
+        ))
+    );
+    // Extreme coordinates don't overflow.
+    let (min, max) = (i32::MIN, i32::MAX);
+    assert_eq!(Hex::new(min, min).distance(&Hex::new(max, max)), u32::MAX);
+    for hex in &[Hex::new(min, 0), Hex::new(max, max), Hex::new(min, min)] {
+        assert_eq!(
+            MoveEntity::trigger(&mut server, entity_1_id, *hex)
+                .fire()
+                .err()
+                .map(|e| e.unfold()),
+            Some(WeaselError::PositionError(
+                Some(Hex::new(2, 0)),
+                *hex,
+                Box::new(WeaselError::PositionOutOfBounds(*hex))
+            ))
+        );
+    }
+    // Move into a free hexagon.
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, Hex::new(2, -2))
+            .fire()
+            .err(),
+        None
+    );
+    let grid = server.battle().space().model();
+    assert_eq!(grid.occupant(&Hex::new(2, -2)), Some(&entity_1_id));
+    assert!(grid.is_free(&Hex::new(2, 0)));
+    assert_eq!(grid.closest_free(&origin), Some(Hex::new(-1, 1)));
+    // Shrink the grid. Entities outside of it are moved to the closest free hexagon.
+    assert_eq!(ResetSpace::trigger(&mut server).seed(1).fire().err(), None);
+    assert_eq!(position(&server, entity_1_id), Hex::new(1, -1));
+    assert_eq!(position(&server, entity_2_id), origin);
+    assert_eq!(server.battle().space().model().occupied().count(), 2);
+    // Entities are removed if there's no room left.
+    assert_eq!(ResetSpace::trigger(&mut server).seed(0).fire().err(), None);
+    assert_eq!(position(&server, entity_2_id), origin);
+    assert!(server
+        .battle()
+        .entities()
+        .creature(&CREATURE_1_ID)
+        .is_none());
+    // Check spatial queries.
+    let space = server.battle().space();
+    let cone = |target, length| {
+        (-3..=3)
+            .flat_map(|q| (-3..=3).map(move |r| Hex::new(q, r)))
+            .filter(|hex| space.in_area(&Area::Cone(origin, target, length), hex))
+            .count()
+    };
+    assert_eq!(cone(Hex::new(1, 0), 1), 1);
+    assert_eq!(cone(Hex::new(1, 0), 2), 4);
+    assert_eq!(cone(Hex::new(0, 3), 3), 7);
+    assert_eq!(space.distance(&origin, &Hex::new(-2, 3)), 3);
+    assert!(space.in_area(
+        &Area::Rectangle(Hex::new(-1, 2), Hex::new(1, 0)),
+        &Hex::new(1, 2)
+    ));
+}
+
+#[test]
+fn move_along_path() {
+    use weasel::actor::Actor;
+    use weasel::event::{DummyEvent, EventKind};
+    use weasel::round::{Budget, RoundsRules};
+    use weasel::space::{MoveAlongPath, PathCost};
+
+    const LAST_POSITION: u32 = 9;
+    const MUD: u32 = 4;
+    const WALL: u32 = 8;
+
+    #[derive(Default)]
+    struct PathSpaceRules {}
+
+    // A corridor in which each position can be occupied by one entity at most.
+    impl SpaceRules<CustomRules> for PathSpaceRules {
+        type Position = u32;
+        type SpaceSeed = ();
+        type SpaceModel = HashSet<Self::Position>;
+        type SpaceAlteration = ();
+        type Direction = ();
+
+        fn generate_model(&self, _: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
+            HashSet::new()
+        }
+
+        fn check_move(
+            &self,
+            model: &Self::SpaceModel,
+            claim: PositionClaim<CustomRules>,
+            position: &Self::Position,
+        ) -> WeaselResult<(), CustomRules> {
+            match claim {
+                PositionClaim::Movement(entity) if entity.position() == position => Ok(()),
+                _ if model.contains(position) => Err(WeaselError::GenericError),
+                _ => Ok(()),
+            }
+        }
+
+        fn move_entity(
+            &self,
+            model: &mut Self::SpaceModel,
+            claim: PositionClaim<CustomRules>,
+            position: Option<&Self::Position>,
+            _event_queue: &mut Option<EventQueue<CustomRules>>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            if let PositionClaim::Movement(entity) = claim {
+                model.remove(entity.position());
+            }
+            if let Some(position) = position {
+                model.insert(*position);
+            }
+        }
+
+        fn neighbors(
+            &self,
+            _model: &Self::SpaceModel,
+            position: &Self::Position,
+        ) -> Vec<Self::Position> {
+            let mut neighbors = Vec::new();
+            if *position > 0 {
+                neighbors.push(position - 1);
+            }
+            if *position < LAST_POSITION {
+                neighbors.push(position + 1);
+            }
+            neighbors
+        }
+
+        fn step_cost(
+            &self,
+            _model: &Self::SpaceModel,
+            _entity: &dyn Entity<CustomRules>,
+            _from: &Self::Position,
+            to: &Self::Position,
+        ) -> Option<PathCost> {
+            match *to {
+                WALL => None,
+                MUD => Some(3),
+                _ => Some(1),
+            }
+        }
+
+        fn estimate_cost(
+            &self,
+            _model: &Self::SpaceModel,
+            from: &Self::Position,
+            to: &Self::Position,
+        ) -> PathCost {
+            (*from as i64 - *to as i64).unsigned_abs() as PathCost
+        }
+
+        fn on_step(
+            &self,
+            _model: &Self::SpaceModel,
+            _entity: &dyn Entity<CustomRules>,
+            _from: &Self::Position,
+            _to: &Self::Position,
+            event_queue: &mut Option<EventQueue<CustomRules>>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            DummyEvent::trigger(event_queue).fire();
+        }
+    }
+
+    #[derive(Default)]
+    struct PathRoundsRules {}
+
+    impl RoundsRules<CustomRules> for PathRoundsRules {
+        type RoundsSeed = ();
+        type RoundsModel = ();
+        type Phase = ();
+
+        fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {}
+
+        fn budget(
+            &self,
+            _model: &Self::RoundsModel,
+            _actor: &dyn Actor<CustomRules>,
+        ) -> Option<Budget> {
+            Some(Budget::new(0, 5))
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        EmptyCharacterRules,
+        EmptyActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        PathSpaceRules,
+        PathRoundsRules,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    let position = |server: &Server<CustomRules>, id| {
+        *server.battle().entities().entity(&id).unwrap().position()
+    };
+    let steps = |server: &Server<CustomRules>| {
+        server
+            .battle()
+            .history()
+            .events()
+            .iter()
+            .filter(|event| event.kind() == EventKind::DummyEvent)
+            .count()
+    };
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, 0);
+    util::creature(&mut server, CREATURE_2_ID, TEAM_1_ID, LAST_POSITION);
+    // Find paths.
+    let space = server.battle().space();
+    let entity = server.battle().entities().entity(&entity_1_id).unwrap();
+    let path = space.find_path(entity, &6).unwrap();
+    assert_eq!(path, vec![1, 2, 3, 4, 5, 6]);
+    assert_eq!(space.path_cost(entity, &path), Some(8));
+    assert_eq!(space.find_path(entity, &0), Some(vec![]));
+    assert_eq!(space.find_path(entity, &LAST_POSITION), None);
+    // Paths must be made of passable steps.
+    assert_eq!(
+        MoveAlongPath::trigger(&mut server, entity_1_id, vec![])
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::EmptyPath)
+    );
+    assert_eq!(
+        MoveAlongPath::trigger(&mut server, entity_1_id, vec![2])
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::ImpassableStep(0, 2))
+    );
+    assert_eq!(
+        MoveAlongPath::trigger(&mut server, entity_2_id, vec![WALL])
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::ImpassableStep(LAST_POSITION, WALL))
+    );
+    // Actors pay for the whole path.
+    util::start_turn(&mut server, &entity_1_id);
+    assert_eq!(
+        MoveAlongPath::trigger(&mut server, entity_1_id, path)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::InsufficientActionPoints(entity_1_id))
+    );
+    assert_eq!(
+        MoveAlongPath::trigger(&mut server, entity_1_id, vec![1, 2, 3])
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), 3);
+    assert_eq!(
+        server.battle().rounds().budget(&entity_1_id),
+        Some(&Budget::new(0, 2))
+    );
+    assert!(server.battle().space().model().contains(&3));
+    assert!(!server.battle().space().model().contains(&0));
+    // Side effects are triggered on every step.
+    assert_eq!(steps(&server), 3);
+    assert_eq!(
+        MoveAlongPath::trigger(&mut server, entity_1_id, vec![MUD])
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::InsufficientActionPoints(entity_1_id))
+    );
+    assert_eq!(
+        MoveAlongPath::trigger(&mut server, entity_1_id, vec![2])
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), 2);
+    assert_eq!(steps(&server), 4);
+}
+
+#[test]
+fn spatial_queries() {
+    use weasel::rules::space::{GridSize, Square, SquareGridRules};
+    use weasel::space::Area;
+
+    battle_rules_with_space! { SquareGridRules }
+
+    const CREATURE_3_ID: u32 = 3;
+
+    let mut server = util::server(CustomRules::new());
+    assert_eq!(
+        ResetSpace::trigger(&mut server)
+            .seed(GridSize::new(5, 5))
+            .fire()
+            .err(),
+        None
+    );
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, Square::new(0, 0));
+    util::creature(&mut server, CREATURE_2_ID, TEAM_1_ID, Square::new(2, 0));
+    util::creature(&mut server, CREATURE_3_ID, TEAM_1_ID, Square::new(4, 0));
+    let space = server.battle().space();
+    let entities = server.battle().entities();
+    let ids = |iter: &mut dyn Iterator<Item = &EntityId<CustomRules>>| {
+        iter.map(|id| id.creature().unwrap()).collect::<Vec<_>>()
+    };
+    // Distance.
+    assert_eq!(space.distance(&Square::new(0, 0), &Square::new(3, 4)), 4);
+    // Area shapes.
+    assert_eq!(
+        ids(&mut space.entities_within(entities, &Square::new(0, 0), 2)),
+        vec![CREATURE_1_ID, CREATURE_2_ID]
+    );
+    let cone = Area::Cone(Square::new(0, 0), Square::new(4, 0), 4);
+    assert_eq!(
+        ids(&mut space.entities_in_area(entities, cone.clone())),
+        vec![CREATURE_2_ID, CREATURE_3_ID]
+    );
+    assert!(space.in_area(&cone, &Square::new(2, 2)));
+    assert!(!space.in_area(&cone, &Square::new(1, 2)));
+    assert_eq!(
+        ids(&mut space.entities_in_area(entities, Area::Line(Square::new(0, 0), Square::new(4, 0)))),
+        vec![CREATURE_1_ID, CREATURE_2_ID, CREATURE_3_ID]
+    );
+    assert_eq!(
+        ids(&mut space.entities_in_area(
+            entities,
+            Area::Rectangle(Square::new(4, 4), Square::new(1, 0))
+        )),
+        vec![CREATURE_2_ID, CREATURE_3_ID]
+    );
+    // Line of sight.
+    assert!(space.line_of_sight(&Square::new(0, 0), &Square::new(2, 0)));
+    assert!(!space.line_of_sight(&Square::new(0, 0), &Square::new(4, 0)));
+    assert!(space.line_of_sight(&Square::new(0, 0), &Square::new(4, 1)));
+    assert_eq!(
+        ids(&mut space.visible_entities(entities, &Square::new(0, 0))),
+        vec![CREATURE_2_ID]
+    );
+}
+
+#[test]
+fn terrain_hooks() {
+    use std::collections::HashMap;
+    use weasel::entity::RemoveEntity;
+    use weasel::event::{DummyEvent, EventKind};
+    use weasel::space::{MoveAlongPath, PathCost};
+
+    const PLATE: u32 = 2;
+    const MUD: u32 = 4;
+    const LAVA: u32 = 5;
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Terrain {
+        Mud,
+        Lava,
+        Plate,
+    }
+
+    #[derive(Default)]
+    struct TerrainSpaceRules {}
+
+    impl SpaceRules<CustomRules> for TerrainSpaceRules {
+        type Position = u32;
+        type SpaceSeed = ();
+        type SpaceModel = HashMap<Self::Position, Terrain>;
+        type SpaceAlteration = ();
+        type Direction = ();
+
+        fn generate_model(&self, _: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
+            let mut model = HashMap::new();
+            model.insert(PLATE, Terrain::Plate);
+            model.insert(MUD, Terrain::Mud);
+            model.insert(LAVA, Terrain::Lava);
+            model
+        }
+
+        fn move_entity(
+            &self,
+            model: &mut Self::SpaceModel,
+            claim: PositionClaim<CustomRules>,
+            position: Option<&Self::Position>,
+            event_queue: &mut Option<EventQueue<CustomRules>>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            // The pressure plate triggers when an entity steps on it and when it leaves.
+            if let PositionClaim::Movement(entity) = claim {
+                if model.get(entity.position()) == Some(&Terrain::Plate) {
+                    DummyEvent::trigger(event_queue).fire();
+                }
+            }
+            if let Some(position) = position {
+                if model.get(position) == Some(&Terrain::Plate) {
+                    DummyEvent::trigger(event_queue).fire();
+                }
+            }
+        }
+
+        fn neighbors(
+            &self,
+            _model: &Self::SpaceModel,
+            position: &Self::Position,
+        ) -> Vec<Self::Position> {
+            vec![position.saturating_sub(1), position + 1]
+        }
+
+        fn step_cost(
+            &self,
+            model: &Self::SpaceModel,
+            _entity: &dyn Entity<CustomRules>,
+            _from: &Self::Position,
+            to: &Self::Position,
+        ) -> Option<PathCost> {
+            match model.get(to) {
+                Some(Terrain::Mud) => Some(3),
+                _ => Some(1),
+            }
+        }
+
+        fn on_turn_end(
+            &self,
+            model: &Self::SpaceModel,
+            entity: &dyn Entity<CustomRules>,
+            event_queue: &mut Option<EventQueue<CustomRules>>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            // Lava burns the entities that stop on it.
+            if model.get(entity.position()) == Some(&Terrain::Lava) {
+                RemoveEntity::trigger(event_queue, *entity.entity_id()).fire();
+            }
+        }
+    }
+
+    battle_rules_with_space! { TerrainSpaceRules }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let triggers = |server: &Server<CustomRules>| {
+        server
+            .battle()
+            .history()
+            .events()
+            .iter()
+            .filter(|event| event.kind() == EventKind::DummyEvent)
+            .count()
+    };
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, 0);
+    // Terrain defines the cost of moving.
+    let space = server.battle().space();
+    let entity = server.battle().entities().entity(&entity_1_id).unwrap();
+    assert_eq!(space.path_cost(entity, &[1, 2, 3, MUD]), Some(6));
+    // Entering and leaving a position fire events.
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, PLATE)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(triggers(&server), 1);
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, 3)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(triggers(&server), 2);
+    assert_eq!(
+        MoveAlongPath::trigger(&mut server, entity_1_id, vec![PLATE, 1])
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(triggers(&server), 4);
+    // Ending the turn in a position fires events.
+    util::start_turn(&mut server, &entity_1_id);
+    util::end_turn(&mut server);
+    assert!(server
+        .battle()
+        .entities()
+        .creature(&CREATURE_1_ID)
+        .is_some());
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, LAVA)
+            .fire()
+            .err(),
+        None
+    );
+    util::start_turn(&mut server, &entity_1_id);
+    util::end_turn(&mut server);
+    assert!(server
+        .battle()
+        .entities()
+        .creature(&CREATURE_1_ID)
+        .is_none());
+}
+
+#[test]
+fn footprints() {
+    use weasel::rules::space::{
+        Footprint, FootprintGridRules, GridSize, Orientation, Placement, Square,
+    };
+    use weasel::space::Area;
+
+    battle_rules_with_space! { FootprintGridRules }
+
+    const CREATURE_3_ID: u32 = 3;
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    let position = |server: &Server<CustomRules>, id| {
+        server
+            .battle()
+            .entities()
+            .entity(&id)
+            .unwrap()
+            .position()
+            .clone()
+    };
+    let mut server = util::server(CustomRules::new());
+    assert_eq!(
+        ResetSpace::trigger(&mut server)
+            .seed(GridSize::new(6, 6))
+            .fire()
+            .err(),
+        None
+    );
+    util::team(&mut server, TEAM_1_ID);
+    // Create a large creature covering three squares and a small one.
+    let large = Placement::new(Square::new(0, 2), Footprint::rectangle(3, 1));
+    assert_eq!(
+        large.cells(),
+        vec![Square::new(0, 2), Square::new(1, 2), Square::new(2, 2)]
+    );
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, large.clone());
+    util::creature(
+        &mut server,
+        CREATURE_2_ID,
+        TEAM_1_ID,
+        Placement::from(Square::new(3, 2)),
+    );
+    // All covered squares are occupied.
+    let grid = server.battle().space().model();
+    assert_eq!(grid.occupant(&Square::new(2, 2)), Some(&entity_1_id));
+    assert_eq!(grid.occupied().count(), 4);
+    let small = Placement::from(Square::new(1, 2));
+    assert_eq!(
+        CreateCreature::trigger(&mut server, CREATURE_3_ID, TEAM_1_ID, small.clone())
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            None,
+            small.clone(),
+            Box::new(WeaselError::PositionOccupied(small, entity_1_id))
+        ))
+    );
+    let shifted = large.clone().with_anchor(Square::new(1, 2));
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, shifted.clone())
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(large.clone()),
+            shifted.clone(),
+            Box::new(WeaselError::PositionOccupied(shifted, entity_2_id))
+        ))
+    );
+    // The footprint can't change while moving.
+    let squeezed = Placement::new(Square::new(0, 3), Footprint::single());
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, squeezed.clone())
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(large.clone()),
+            squeezed.clone(),
+            Box::new(WeaselError::PositionUnsupported(squeezed))
+        ))
+    );
+    // Deserialized footprints must cover their anchor.
+    #[cfg(feature = "serialization")]
+    {
+        let json = serde_json::to_string(large.footprint()).unwrap();
+        let footprint: Footprint = serde_json::from_str(&json).unwrap();
+        assert_eq!(&footprint, large.footprint());
+        assert!(serde_json::from_str::<Footprint>(r#"{"offsets":[]}"#).is_err());
+    }
+    // Rotate the large creature.
+    let south = large.clone().with_orientation(Orientation::South);
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, south.clone())
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(large.clone()),
+            south.clone(),
+            Box::new(WeaselError::PositionOutOfBounds(south))
+        ))
+    );
+    let east = large.clone().with_orientation(Orientation::East);
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, east.clone())
+            .fire()
+            .err(),
+        None
+    );
+    let grid = server.battle().space().model();
+    assert_eq!(grid.occupant(&Square::new(0, 4)), Some(&entity_1_id));
+    assert!(grid.is_free(&Square::new(2, 2)));
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, large.clone())
+            .fire()
+            .err(),
+        None
+    );
+    // Spatial queries consider all covered squares.
+    let space = server.battle().space();
+    let entities = server.battle().entities();
+    let ids = |iter: &mut dyn Iterator<Item = &EntityId<CustomRules>>| {
+        iter.map(|id| id.creature().unwrap()).collect::<Vec<_>>()
+    };
+    assert_eq!(
+        ids(&mut space.entities_within(entities, &Placement::from(Square::new(5, 2)), 2)),
+        vec![CREATURE_2_ID]
+    );
+    assert_eq!(
+        ids(&mut space.entities_in_area(
+            entities,
+            Area::Rectangle(Square::new(2, 0).into(), Square::new(2, 5).into())
+        )),
+        vec![CREATURE_1_ID]
+    );
+    assert_eq!(
+        space.distance(&large, &Placement::from(Square::new(5, 5))),
+        3
+    );
+    assert!(!space.line_of_sight(&large, &Placement::from(Square::new(5, 2))));
+    assert_eq!(
+        ids(&mut space.visible_entities(entities, &large)),
+        vec![CREATURE_2_ID]
+    );
+    // Shrink the grid. Entities that don't fit are moved or removed.
+    assert_eq!(
+        ResetSpace::trigger(&mut server)
+            .seed(GridSize::new(3, 3))
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), large);
+    assert_eq!(
+        position(&server, entity_2_id),
+        Placement::from(Square::new(2, 1))
+    );
+    assert_eq!(
+        ResetSpace::trigger(&mut server)
+            .seed(GridSize::new(2, 2))
+            .fire()
+            .err(),
+        None
+    );
+    assert!(server
+        .battle()
+        .entities()
+        .creature(&CREATURE_1_ID)
+        .is_none());
+    assert_eq!(
+        position(&server, entity_2_id),
+        Placement::from(Square::new(1, 0))
+    );
+}
+
+#[test]
+fn forced_movement() {
+    use std::collections::HashMap;
+    use weasel::battle::BattleState;
+    use weasel::entropy::Entropy;
+    use weasel::fight::{ApplyImpact, FightRules};
+    use weasel::space::{DisplaceEntity, Distance, SwapEntities};
+
+    const LAST_POSITION: i32 = 9;
+    const PIT: i32 = 7;
+
+    #[derive(Default)]
+    struct CorridorSpaceRules {}
+
+    // A corridor with a pit that entities can be pushed into, but can't enter on their own.
+    impl SpaceRules<CustomRules> for CorridorSpaceRules {
+        type Position = i32;
+        type SpaceSeed = ();
+        type SpaceModel = HashMap<Self::Position, EntityId<CustomRules>>;
+        type SpaceAlteration = ();
+        type Direction = i32;
+
+        fn generate_model(&self, _: &Option<Self::SpaceSeed>) -> Self::SpaceModel {
+            HashMap::new()
+        }
+
+        fn check_move(
+            &self,
+            model: &Self::SpaceModel,
+            claim: PositionClaim<CustomRules>,
+            position: &Self::Position,
+        ) -> WeaselResult<(), CustomRules> {
+            if *position < 0 || *position > LAST_POSITION {
+                return Err(WeaselError::PositionOutOfBounds(*position));
+            }
+            let partner = match claim {
+                PositionClaim::Displacement(_) => None,
+                PositionClaim::Swap(_, other) if *position != PIT => Some(*other),
+                _ if *position == PIT => return Err(WeaselError::GenericError),
+                _ => None,
+            };
+            match model.get(position) {
+                Some(id) if id != claim.entity_id() && Some(*id) != partner => {
+                    Err(WeaselError::PositionOccupied(*position, *id))
+                }
+                _ => Ok(()),
+            }
+        }
+
+        fn move_entity(
+            &self,
+            model: &mut Self::SpaceModel,
+            claim: PositionClaim<CustomRules>,
+            position: Option<&Self::Position>,
+            _event_queue: &mut Option<EventQueue<CustomRules>>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            if let Some(entity) = claim.entity() {
+                if model.get(entity.position()) == Some(entity.entity_id()) {
+                    model.remove(entity.position());
+                }
+            }
+            if let Some(position) = position {
+                model.insert(*position, *claim.entity_id());
+            }
+        }
+
+        fn displacement_path(
+            &self,
+            _model: &Self::SpaceModel,
+            entity: &dyn Entity<CustomRules>,
+            direction: &Self::Direction,
+            distance: Distance,
+        ) -> Vec<Self::Position> {
+            (1..=distance as i32)
+                .map(|step| entity.position() + direction * step)
+                .collect()
+        }
+
+        fn on_collision(
+            &self,
+            _model: &Self::SpaceModel,
+            entity: &dyn Entity<CustomRules>,
+            _obstacle: &Self::Position,
+            remaining: Distance,
+            event_queue: &mut Option<EventQueue<CustomRules>>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+            // Damage the entity proportionally to the remaining distance.
+            ApplyImpact::trigger(event_queue, (*entity.entity_id(), remaining)).fire();
+        }
+    }
+
+    #[derive(Default)]
+    struct CollisionFightRules {}
+
+    impl FightRules<CustomRules> for CollisionFightRules {
+        type Impact = (EntityId<CustomRules>, Distance);
+        type Potency = ();
+
+        fn apply_impact(
+            &self,
+            _state: &BattleState<CustomRules>,
+            _impact: &Self::Impact,
+            _event_queue: &mut Option<EventQueue<CustomRules>>,
+            _entropy: &mut Entropy<CustomRules>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) {
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        EmptyCharacterRules,
+        EmptyActorRules,
+        CollisionFightRules,
+        EmptyUserRules,
+        CorridorSpaceRules,
+        EmptyRoundsRules,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    let position = |server: &Server<CustomRules>, id| {
+        *server.battle().entities().entity(&id).unwrap().position()
+    };
+    let impacts = |server: &Server<CustomRules>| {
+        server
+            .battle()
+            .history()
+            .events()
+            .iter()
+            .filter_map(|event| {
+                event
+                    .event()
+                    .as_any()
+                    .downcast_ref::<ApplyImpact<CustomRules>>()
+                    .map(|impact| *impact.impact())
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, 0);
+    util::creature(&mut server, CREATURE_2_ID, TEAM_1_ID, 4);
+    // Push an entity.
+    assert_eq!(
+        DisplaceEntity::trigger(&mut server, entity_1_id, 1, 2)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), 2);
+    assert!(impacts(&server).is_empty());
+    // Obstacles stop the movement and cause a collision.
+    assert_eq!(
+        DisplaceEntity::trigger(&mut server, entity_1_id, 1, 5)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), 3);
+    assert_eq!(impacts(&server), vec![(entity_1_id, 4)]);
+    // Forced movement can go where voluntary movement can't.
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_2_id, PIT)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(4),
+            PIT,
+            Box::new(WeaselError::GenericError)
+        ))
+    );
+    assert_eq!(
+        DisplaceEntity::trigger(&mut server, entity_2_id, 1, 3)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_2_id), PIT);
+    // The edge of the corridor is an obstacle too.
+    assert_eq!(
+        DisplaceEntity::trigger(&mut server, entity_2_id, 1, 5)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_2_id), LAST_POSITION);
+    assert_eq!(impacts(&server), vec![(entity_1_id, 4), (entity_2_id, 3)]);
+    // Swap two entities.
+    assert_eq!(
+        SwapEntities::trigger(&mut server, entity_1_id, EntityId::Creature(99))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::EntityNotFound(EntityId::Creature(99)))
+    );
+    assert_eq!(
+        SwapEntities::trigger(&mut server, entity_1_id, entity_1_id)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::SelfSwap(entity_1_id))
+    );
+    assert_eq!(
+        SwapEntities::trigger(&mut server, entity_1_id, entity_2_id)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), LAST_POSITION);
+    assert_eq!(position(&server, entity_2_id), 3);
+    let model = server.battle().space().model();
+    assert_eq!(model.get(&LAST_POSITION), Some(&entity_1_id));
+    assert_eq!(model.get(&3), Some(&entity_2_id));
+    assert_eq!(model.len(), 2);
+    // Swaps are atomic.
+    assert_eq!(
+        DisplaceEntity::trigger(&mut server, entity_1_id, -1, 2)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), PIT);
+    assert_eq!(
+        SwapEntities::trigger(&mut server, entity_1_id, entity_2_id)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(3),
+            PIT,
+            Box::new(WeaselError::GenericError)
+        ))
+    );
+    assert_eq!(position(&server, entity_1_id), PIT);
+    assert_eq!(position(&server, entity_2_id), 3);
+}
+
+#[test]
+fn layers() {
+    use weasel::rules::space::{
+        LayerAlteration, LayeredGridRules, LayeredGridSize, LayeredSquare, Square,
+    };
+    use weasel::space::DisplaceEntity;
+
+    battle_rules_with_space! { LayeredGridRules }
+
+    const CREATURE_3_ID: u32 = 3;
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    let entity_3_id = EntityId::Creature(CREATURE_3_ID);
+    let position = |server: &Server<CustomRules>, id| {
+        *server.battle().entities().entity(&id).unwrap().position()
+    };
+    let ground = |x, layer| LayeredSquare::ground(Square::new(x, 0), layer);
+    let flying = |x, layer| LayeredSquare::flying(Square::new(x, 0), layer);
+    let mut server = util::server(CustomRules::new());
+    // A corridor with a step at 2 and a two layers high cliff at 4.
+    assert_eq!(
+        ResetSpace::trigger(&mut server)
+            .seed(LayeredGridSize::new(6, 1, 3))
+            .fire()
+            .err(),
+        None
+    );
+    for (x, layer) in &[(2, 0), (4, 0), (4, 1)] {
+        assert_eq!(
+            AlterSpace::trigger(
+                &mut server,
+                LayerAlteration::Fill(Square::new(*x, 0), *layer)
+            )
+            .fire()
+            .err(),
+            None
+        );
+    }
+    util::team(&mut server, TEAM_1_ID);
+    // Entities can't be inside solid cells, and only flying ones can stay in mid air.
+    assert_eq!(
+        CreateCreature::trigger(&mut server, CREATURE_1_ID, TEAM_1_ID, ground(2, 0))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            None,
+            ground(2, 0),
+            Box::new(WeaselError::PositionBlocked(ground(2, 0)))
+        ))
+    );
+    assert_eq!(
+        CreateCreature::trigger(&mut server, CREATURE_1_ID, TEAM_1_ID, ground(1, 1))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            None,
+            ground(1, 1),
+            Box::new(WeaselError::PositionUnsupported(ground(1, 1)))
+        ))
+    );
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, ground(0, 0));
+    util::creature(&mut server, CREATURE_2_ID, TEAM_1_ID, flying(1, 1));
+    util::creature(&mut server, CREATURE_3_ID, TEAM_1_ID, ground(4, 2));
+    // Different layers of the same square are occupied independently.
+    let grid = server.battle().space().model();
+    assert_eq!(grid.occupant(&Square::new(1, 0), 1), Some(&entity_2_id));
+    assert!(grid.is_free(&Square::new(1, 0), 0));
+    assert!(!grid.is_free(&Square::new(2, 0), 0));
+    // Entities can't change their mobility by moving.
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, flying(0, 1))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(ground(0, 0)),
+            flying(0, 1),
+            Box::new(WeaselError::PositionUnsupported(flying(0, 1)))
+        ))
+    );
+    // Entities on the ground climb one layer at most, paying for it.
+    let space = server.battle().space();
+    let entities = server.battle().entities();
+    let entity_1 = entities.entity(&entity_1_id).unwrap();
+    let path = space.find_path(entity_1, &ground(3, 0)).unwrap();
+    assert_eq!(path, vec![ground(1, 0), ground(2, 1), ground(3, 0)]);
+    assert_eq!(space.path_cost(entity_1, &path), Some(4));
+    assert_eq!(space.find_path(entity_1, &ground(4, 2)), None);
+    let entity_2 = entities.entity(&entity_2_id).unwrap();
+    assert!(space.find_path(entity_2, &flying(3, 2)).is_some());
+    // Entities on the ground fall when pushed off a ledge.
+    assert_eq!(
+        DisplaceEntity::trigger(&mut server, entity_3_id, Square::new(1, 0), 1)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_3_id), ground(5, 0));
+    // Solid cells block line of sight, flying over them doesn't.
+    let space = server.battle().space();
+    assert_eq!(space.distance(&ground(0, 0), &flying(1, 2)), 2);
+    assert!(!space.line_of_sight(&ground(3, 0), &ground(5, 0)));
+    assert!(space.line_of_sight(&flying(3, 2), &flying(5, 2)));
+    // Removing the support of an entity makes it fall, filling its cell crushes it.
+    assert_eq!(
+        AlterSpace::trigger(&mut server, LayerAlteration::Fill(Square::new(3, 0), 0))
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, ground(3, 1))
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(
+        AlterSpace::trigger(&mut server, LayerAlteration::Clear(Square::new(3, 0), 0))
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), ground(3, 0));
+    assert_eq!(
+        AlterSpace::trigger(&mut server, LayerAlteration::Fill(Square::new(1, 0), 1))
+            .fire()
+            .err(),
+        None
+    );
+    assert!(server
+        .battle()
+        .entities()
+        .creature(&CREATURE_2_ID)
+        .is_none());
+    // Shrinking the space moves entities to the closest valid cell.
+    assert_eq!(
+        ResetSpace::trigger(&mut server)
+            .seed(LayeredGridSize::new(3, 1, 1))
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), ground(2, 0));
+    assert_eq!(position(&server, entity_3_id), ground(1, 0));
+}
+
+#[test]
+fn continuous_space() {
+    use weasel::rules::space::{ArenaSeed, Body, ContinuousSpaceRules, Fixed, Point};
+    use weasel::space::{Area, DisplaceEntity};
+
+    battle_rules_with_space! { ContinuousSpaceRules }
+
+    const CREATURE_3_ID: u32 = 3;
+    const CREATURE_4_ID: u32 = 4;
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let entity_2_id = EntityId::Creature(CREATURE_2_ID);
+    let entity_3_id = EntityId::Creature(CREATURE_3_ID);
+    let position = |server: &Server<CustomRules>, id| {
+        *server.battle().entities().entity(&id).unwrap().position()
+    };
+    let body = |x, y| Body::new(Point::new(x, y), 1);
+    // Fixed-point arithmetic is exact for simple values.
+    assert_eq!(
+        Fixed::from_ratio(1, 2) + Fixed::from_ratio(1, 2),
+        Fixed::ONE
+    );
+    assert_eq!(Fixed::from_int(9).sqrt(), Fixed::from_int(3));
+    assert_eq!(
+        Point::new(0, 0).distance(&Point::new(3, 4)),
+        Fixed::from_int(5)
+    );
+    // Arithmetic saturates instead of overflowing.
+    assert_eq!(Fixed::MAX + Fixed::ONE, Fixed::MAX);
+    assert_eq!(Fixed::MAX * Fixed::from_int(2), Fixed::MAX);
+    assert_eq!(Fixed::checked_from_int(i64::MAX), None);
+    assert_eq!(Body::try_new(Point::new(1, 1), -1), None);
+    let mut server = util::server(CustomRules::new());
+    assert_eq!(
+        ResetSpace::trigger(&mut server)
+            .seed(ArenaSeed::new(10, 10).with_max_move(4))
+            .fire()
+            .err(),
+        None
+    );
+    util::team(&mut server, TEAM_1_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, body(1, 2));
+    util::creature(&mut server, CREATURE_2_ID, TEAM_1_ID, body(4, 2));
+    // Bodies must be inside the arena and they can't overlap.
+    assert_eq!(
+        CreateCreature::trigger(&mut server, CREATURE_3_ID, TEAM_1_ID, body(0, 5))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            None,
+            body(0, 5),
+            Box::new(WeaselError::PositionOutOfBounds(body(0, 5)))
+        ))
+    );
+    assert_eq!(
+        CreateCreature::trigger(&mut server, CREATURE_3_ID, TEAM_1_ID, body(5, 2))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            None,
+            body(5, 2),
+            Box::new(WeaselError::PositionOccupied(body(5, 2), entity_2_id))
+        ))
+    );
+    util::creature(&mut server, CREATURE_3_ID, TEAM_1_ID, body(8, 8));
+    // Movements are limited in length and they can't go through other bodies.
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, body(6, 2))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(body(1, 2)),
+            body(6, 2),
+            Box::new(WeaselError::PositionTooFar(body(6, 2)))
+        ))
+    );
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, body(4, 4))
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(body(1, 2)),
+            body(4, 4),
+            Box::new(WeaselError::PositionOccupied(body(4, 4), entity_2_id))
+        ))
+    );
+    for destination in &[body(1, 5), body(4, 4)] {
+        assert_eq!(
+            MoveEntity::trigger(&mut server, entity_1_id, *destination)
+                .fire()
+                .err(),
+            None
+        );
+    }
+    // Radiuses can't change or be negative.
+    let shrunk = Body::new(Point::new(4, 5), 0);
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, shrunk)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(body(4, 4)),
+            shrunk,
+            Box::new(WeaselError::PositionUnsupported(shrunk))
+        ))
+    );
+    let negative = Body::new(Point::new(8, 2), -1);
+    assert_eq!(
+        CreateCreature::trigger(&mut server, CREATURE_4_ID, TEAM_1_ID, negative)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            None,
+            negative,
+            Box::new(WeaselError::PositionUnsupported(negative))
+        ))
+    );
+    // Coordinates far outside of the arena are rejected without overflowing.
+    let far = Body::new(Point::new(Fixed::MAX, Fixed::from_int(2)), 1);
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, far)
+            .fire()
+            .err()
+            .map(|e| e.unfold()),
+        Some(WeaselError::PositionError(
+            Some(body(4, 4)),
+            far,
+            Box::new(WeaselError::PositionOutOfBounds(far))
+        ))
+    );
+    // Radius queries.
+    let space = server.battle().space();
+    let entities = server.battle().entities();
+    let ids = |iter: &mut dyn Iterator<Item = &EntityId<CustomRules>>| {
+        iter.map(|id| id.creature().unwrap()).collect::<Vec<_>>()
+    };
+    assert_eq!(
+        ids(&mut space.model().within(&Point::new(4, 7), Fixed::from_int(2))),
+        vec![CREATURE_1_ID]
+    );
+    assert_eq!(
+        ids(&mut space.entities_within(entities, &Body::from(Point::new(1, 2)), 2)),
+        vec![CREATURE_2_ID]
+    );
+    assert_eq!(space.distance(&body(4, 4), &body(8, 8)), 3);
+    assert!(!space.line_of_sight(&body(1, 2), &body(7, 2)));
+    assert!(space.line_of_sight(&body(1, 8), &body(6, 8)));
+    // Huge areas cover everything.
+    assert!(space.in_area(&Area::Circle(body(1, 2), u32::MAX), &body(8, 8)));
+    assert!(space.in_area(&Area::Cone(body(1, 2), body(8, 8), u32::MAX), &body(8, 8)));
+    // Forced movements stop before a collision.
+    assert_eq!(
+        DisplaceEntity::trigger(&mut server, entity_3_id, Point::new(-1, 0), 3)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_3_id), body(5, 8));
+    assert_eq!(
+        DisplaceEntity::trigger(&mut server, entity_3_id, Point::new(0, -1), 5)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_3_id), body(5, 6));
+    // Shrink the arena. Entities are pushed inside or removed.
+    assert_eq!(
+        ResetSpace::trigger(&mut server)
+            .seed(ArenaSeed::new(6, 6).with_max_move(4))
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), body(4, 4));
+    assert_eq!(position(&server, entity_2_id), body(4, 2));
+    assert!(server
+        .battle()
+        .entities()
+        .creature(&CREATURE_3_ID)
+        .is_none());
+}
+
+#[test]
+fn zones_of_control() {
+    use weasel::actor::Actor;
+    use weasel::event::{DummyEvent, EventKind};
+    use weasel::round::{Budget, RoundsRules};
+    use weasel::space::{MoveAlongPath, ZoneReaction};
+
+    const TEAM_2_ID: u32 = 2;
+    const CREATURE_3_ID: u32 = 3;
+    const REACH: u32 = 2;
+
+    #[derive(Default)]
+    struct ZoneSpaceRules {}
+
+    // A corridor in which creatures control the positions within reach of enemies.
+    impl SpaceRules<CustomRules> for ZoneSpaceRules {
+        type Position = u32;
+        type SpaceSeed = ();
+        type SpaceModel = ();
+        type SpaceAlteration = ();
+        type Direction = ();
+
+        fn generate_model(&self, _: &Option<Self::SpaceSeed>) -> Self::SpaceModel {}
+
+        fn check_move(
+            &self,
+            _model: &Self::SpaceModel,
+            _claim: PositionClaim<CustomRules>,
+            _position: &Self::Position,
+        ) -> WeaselResult<(), CustomRules> {
+            Ok(())
+        }
+
+        fn neighbors(
+            &self,
+            _model: &Self::SpaceModel,
+            position: &Self::Position,
+        ) -> Vec<Self::Position> {
+            vec![position.saturating_sub(1), position + 1]
+        }
+
+        fn in_zone(
+            &self,
+            _model: &Self::SpaceModel,
+            entities: &Entities<CustomRules>,
+            owner: &dyn Entity<CustomRules>,
+            entity: &dyn Entity<CustomRules>,
+            position: &Self::Position,
+        ) -> bool {
+            let team = |entity: &dyn Entity<CustomRules>| {
+                entity
+                    .entity_id()
+                    .creature()
+                    .ok()
+                    .and_then(|id| entities.creature(&id))
+                    .map(|creature| *creature.team_id())
+            };
+            team(owner) != team(entity)
+                && (*owner.position() as i64 - *position as i64).abs() <= REACH as i64
+        }
+
+        fn on_zone_enter(
+            &self,
+            _model: &Self::SpaceModel,
+            _owner: &dyn Entity<CustomRules>,
+            _entity: &dyn Entity<CustomRules>,
+            _from: &Self::Position,
+            _event_queue: &mut Option<EventQueue<CustomRules>>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> ZoneReaction {
+            ZoneReaction::Interrupt
+        }
+
+        fn on_zone_leave(
+            &self,
+            _model: &Self::SpaceModel,
+            _owner: &dyn Entity<CustomRules>,
+            _entity: &dyn Entity<CustomRules>,
+            _from: &Self::Position,
+            event_queue: &mut Option<EventQueue<CustomRules>>,
+            _metrics: &mut WriteMetrics<CustomRules>,
+        ) -> ZoneReaction {
+            // An opportunity reaction.
+            DummyEvent::trigger(event_queue).fire();
+            ZoneReaction::Continue
+        }
+    }
+
+    #[derive(Default)]
+    struct BudgetRoundsRules {}
+
+    impl RoundsRules<CustomRules> for BudgetRoundsRules {
+        type RoundsSeed = ();
+        type RoundsModel = ();
+        type Phase = ();
+
+        fn generate_model(&self, _: &Option<Self::RoundsSeed>) -> Self::RoundsModel {}
+
+        fn budget(
+            &self,
+            _model: &Self::RoundsModel,
+            _actor: &dyn Actor<CustomRules>,
+        ) -> Option<Budget> {
+            Some(Budget::new(0, 10))
+        }
+    }
+
+    battle_rules! {
+        EmptyTeamRules,
+        EmptyCharacterRules,
+        EmptyActorRules,
+        EmptyFightRules,
+        EmptyUserRules,
+        ZoneSpaceRules,
+        BudgetRoundsRules,
+        EmptyEntropyRules
+    }
+
+    let entity_1_id = EntityId::Creature(CREATURE_1_ID);
+    let position = |server: &Server<CustomRules>, id| {
+        *server.battle().entities().entity(&id).unwrap().position()
+    };
+    let reactions = |server: &Server<CustomRules>| {
+        server
+            .battle()
+            .history()
+            .events()
+            .iter()
+            .filter(|event| event.kind() == EventKind::DummyEvent)
+            .count()
+    };
+    let mut server = util::server(CustomRules::new());
+    util::team(&mut server, TEAM_1_ID);
+    util::team(&mut server, TEAM_2_ID);
+    util::creature(&mut server, CREATURE_1_ID, TEAM_1_ID, 0);
+    util::creature(&mut server, CREATURE_2_ID, TEAM_2_ID, 6);
+    util::creature(&mut server, CREATURE_3_ID, TEAM_1_ID, 2);
+    util::start_turn(&mut server, &entity_1_id);
+    // Allies don't have zones of control. Entering an enemy's zone stops the movement.
+    assert_eq!(
+        MoveAlongPath::trigger(&mut server, entity_1_id, vec![1, 2, 3, 4, 5])
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), 4);
+    // Only the steps taken are paid.
+    assert_eq!(
+        server.battle().rounds().budget(&entity_1_id),
+        Some(&Budget::new(0, 6))
+    );
+    // Leaving the zone provokes a reaction, but the movement continues.
+    assert_eq!(
+        MoveAlongPath::trigger(&mut server, entity_1_id, vec![3, 2, 1])
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(position(&server, entity_1_id), 1);
+    assert_eq!(reactions(&server), 1);
+    assert_eq!(
+        server.battle().rounds().budget(&entity_1_id),
+        Some(&Budget::new(0, 3))
+    );
+    // Moving within the zone doesn't trigger it.
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, 5)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, 7)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(reactions(&server), 1);
+    assert_eq!(
+        MoveEntity::trigger(&mut server, entity_1_id, 9)
+            .fire()
+            .err(),
+        None
+    );
+    assert_eq!(reactions(&server), 2);
+}